@@ -0,0 +1,1491 @@
+//Purpose: Contains functions for finding the integer points inside balls (circles, dimonds, squares and spheres) in 2D and 3D,
+//         with integer or rational centers and radii.
+
+use std::cmp;
+use std::cmp::Ordering;
+
+use crate::error::{check_cell_count, GeometryError};
+use crate::point::{Point2D, Point3D, RationalPoint2D, RationalPoint3D};
+use crate::rational::{isqrt, OverflowError, Rational};
+
+//check_ball
+//Purpose:
+//    Returns a GeometryError if the ball with the given center coordinates and radius can not be enumerated without overflowing.
+//Pre-conditions:
+//    None.
+//Notes:
+//    Every coordinate of the center plus or minus the radius must fit in an isize,
+//    and the (2*radius + 1)^dimension points of type T in the bounding box must fit in a Vec.
+fn check_ball<T>(center: &[isize], radius: isize) -> Result<(), GeometryError>{
+    if radius < 0 {
+        return Err(GeometryError::NegativeRadius);
+    }
+    for coordinate in center {
+        if coordinate.checked_add(radius).is_none() || coordinate.checked_sub(radius).is_none() {
+            return Err(GeometryError::RangeTooLarge);
+        }
+    }
+    let side = (radius as usize)*2 + 1;
+    check_cell_count::<T>(side.checked_pow(center.len() as u32))
+}
+
+//integer_points_inside_circle_slow
+//Purpose:
+//    Returns all integer points (x,y) that are within the given radius from the center.
+//Pre-conditions:
+//    radius is non-negaitive.
+//Notes:
+//    This does it the slow way and check every integer tuple would could be in range
+//    Meant to validate integer_points_inside_circle
+pub fn integer_points_inside_circle_slow(center: Point2D, radius: isize) -> Vec<Point2D>{
+    let mut squares: Vec<Point2D> = Vec::new();
+    for x_cord in -radius .. radius +1 {
+        for y_cord in -radius .. radius +1 {
+            if ((x_cord*x_cord) + (y_cord*y_cord)) <= radius*radius{
+                squares.push(Point2D{ x: x_cord + center.x, y: y_cord + center.y,});
+            }
+        }
+    }
+    squares
+}
+
+//integer_points_inside_circle
+//Purpose:
+//    Returns all integer points (x,y) that are within the given radius from the center.
+//Pre-conditions:
+//    radius is non-negaitive.
+//Notes:
+//    This is the standard Euclidean metric, so square roots are needed. They are taken exactly with isqrt.
+pub fn integer_points_inside_circle(center: Point2D, radius: isize) -> Vec<Point2D>{
+    integer_points_inside_sphere_helper(center,radius*radius)
+}
+
+//try_integer_points_inside_circle
+//Purpose:
+//    Returns the same points as integer_points_inside_circle, or a GeometryError if the radius is negative
+//    or the points do not fit in an isize.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_circle(center: Point2D, radius: isize) -> Result<Vec<Point2D>, GeometryError>{
+    check_ball::<Point2D>(&[center.x,center.y],radius)?;
+    Ok(integer_points_inside_circle(center,radius))
+}
+
+//integer_points_inside_dimond_2d
+//Purpose:
+//    Returns all integers points (x,y) such that |x -center.x| + |y - center.y| <= radius.
+//    Equivalent to returing all integer points (x,y) that are within the given radius (using the L 1 metric) from the center.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    Since this is the L 1 metric only loops are needed. This is also know as the taxi-cab metric.
+pub fn integer_points_inside_dimond_2d(center: Point2D, radius: isize) -> Vec<Point2D>{
+    let mut squares: Vec<Point2D> = Vec::new();
+    for x_cord in -radius .. radius +1 {
+        for y_cord in (-radius + x_cord.abs()) .. radius - x_cord.abs() + 1 {
+            squares.push(Point2D{ x: x_cord + center.x, y: y_cord + center.y,});
+        }
+    }
+    squares
+}
+
+//try_integer_points_inside_dimond_2d
+//Purpose:
+//    Returns the same points as integer_points_inside_dimond_2d, or a GeometryError if the radius is negative
+//    or the points do not fit in an isize.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_dimond_2d(center: Point2D, radius: isize) -> Result<Vec<Point2D>, GeometryError>{
+    check_ball::<Point2D>(&[center.x,center.y],radius)?;
+    Ok(integer_points_inside_dimond_2d(center,radius))
+}
+
+//integer_points_inside_square
+//Purpose:
+//    Returns all integers points (x,y) such that max(|x -center.x|,|y - center.y|) <= radius.
+//    Equivalent to returing all integer points that are within the given radius (using the L infinity metric) from the center.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    Since this is the L infinity metric only loops are needed.
+pub fn integer_points_inside_square(center: Point2D, radius: isize) -> Vec<Point2D>{
+    let mut squares: Vec<Point2D> = Vec::new();
+    for x_cord in -radius .. radius + 1 {
+        for y_cord in -radius .. radius + 1 {
+            squares.push(Point2D{ x: x_cord + center.x, y: y_cord + center.y,});
+        }
+    }
+    squares
+}
+
+//try_integer_points_inside_square
+//Purpose:
+//    Returns the same points as integer_points_inside_square, or a GeometryError if the radius is negative
+//    or the points do not fit in an isize.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_square(center: Point2D, radius: isize) -> Result<Vec<Point2D>, GeometryError>{
+    check_ball::<Point2D>(&[center.x,center.y],radius)?;
+    Ok(integer_points_inside_square(center,radius))
+}
+
+//integer_points_inside_sphere_slow
+//Purpose:
+//    Returns all integer points (x,y,z) that are within the given radius from the center.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    This does it the slow way and check every integer triple would could be in range and uses the square root
+//    Meant to validate integer_points_inside_sphere
+pub fn integer_points_inside_sphere_slow(center: Point3D, radius: isize) -> Vec<Point3D>{
+    let mut cubes: Vec<Point3D> = Vec::new();
+    for x_cord in -radius .. radius +1 {
+        for y_cord in -radius .. radius +1 {
+            for z_cord in -radius .. radius +1 {
+                if ((x_cord*x_cord) + (y_cord*y_cord) + (z_cord*z_cord)) <= radius*radius{
+                    cubes.push(Point3D{ x: x_cord + center.x, y: y_cord + center.y, z: z_cord + center.z,});
+                }
+            }
+        }
+    }
+    cubes
+}
+
+//integer_points_inside_sphere_helper
+//Purpose:
+//    Returns all integer points (x,y) that are within the square root of radius_squared from the center.
+//Pre-conditions:
+//    radius_squared is non-negaitive.
+//Notes:
+//    This is a helper function for integer_points_inside_circle and integer_points_inside_sphere.
+//    Square roots are taken with isqrt, so the bounds are exact however large radius_squared is.
+fn integer_points_inside_sphere_helper(center: Point2D, radius_squared: isize) -> Vec<Point2D>{
+    let mut squares: Vec<Point2D> = Vec::new();
+    let radius: isize = isqrt(radius_squared as usize) as isize;
+    let farthest_digonal_integer = isqrt(radius_squared as usize/2) as isize;
+    for x_cords in -farthest_digonal_integer .. farthest_digonal_integer +1 {
+        for y_cords in -farthest_digonal_integer .. farthest_digonal_integer +1 {
+            squares.push(Point2D{ x: x_cords + center.x, y: y_cords + center.y,});
+        }
+    }
+    for x_cord in farthest_digonal_integer + 1 .. radius + 1 {
+        squares.push(Point2D{ x: x_cord + center.x, y: center.y,});
+        squares.push(Point2D{ x: -x_cord + center.x, y: center.y,});
+        squares.push(Point2D{ x: center.x, y: x_cord + center.y,});
+        squares.push(Point2D{ x: center.x, y: -x_cord + center.y,});
+    }
+    for x_cord in farthest_digonal_integer + 1 .. radius + 1 {
+        for y_cord in 1 .. (isqrt((radius_squared - x_cord*x_cord) as usize) as isize) + 1 {
+            squares.push(Point2D{ x: x_cord + center.x, y: y_cord + center.y,});
+            squares.push(Point2D{ x: x_cord + center.x, y: -y_cord + center.y,});
+            squares.push(Point2D{ x: -x_cord + center.x, y: y_cord + center.y,});
+            squares.push(Point2D{ x: -x_cord + center.x, y: -y_cord + center.y,});
+            squares.push(Point2D{ x: y_cord + center.x, y: x_cord + center.y,});
+            squares.push(Point2D{ x: -y_cord + center.x, y: x_cord + center.y,});
+            squares.push(Point2D{ x: y_cord + center.x, y: -x_cord + center.y,});
+            squares.push(Point2D{ x: -y_cord + center.x, y: -x_cord + center.y,});
+        }
+    }
+    squares
+}
+
+//integer_points_inside_sphere
+//Purpose:
+//    Returns all integer points (x,y,z) that are within the given radius from the center.
+//Pre-conditions:
+//    radius is non-negitive
+pub fn integer_points_inside_sphere(center: Point3D, radius: isize) -> Vec<Point3D>{
+    let mut cubes: Vec<Point3D> = Vec::new();
+    let mut squares: Vec<Point2D>;
+    squares = integer_points_inside_sphere_helper(Point2D{ x: center.x, y: center.y,}, radius*radius);
+    for square in squares {
+        cubes.push(Point3D{x: square.x, y: square.y, z: center.z});
+    }
+    for z_cord in 1 .. radius+1 {
+        squares = integer_points_inside_sphere_helper(Point2D{ x: center.x, y: center.y,}, radius*radius - z_cord*z_cord);
+       for square in squares {
+            cubes.push(Point3D{x: square.x, y: square.y, z: center.z + z_cord});
+            cubes.push(Point3D{x: square.x, y: square.y, z: center.z - z_cord});
+        }
+    }
+    cubes
+}
+
+//try_integer_points_inside_sphere
+//Purpose:
+//    Returns the same points as integer_points_inside_sphere, or a GeometryError if the radius is negative
+//    or the points do not fit in an isize.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_sphere(center: Point3D, radius: isize) -> Result<Vec<Point3D>, GeometryError>{
+    check_ball::<Point3D>(&[center.x,center.y,center.z],radius)?;
+    Ok(integer_points_inside_sphere(center,radius))
+}
+
+//integer_points_inside_dimond_3d
+//Purpose:
+//    Returns all integers points (x,y,z) such that |x -center.x| + |y - center.y| + |z - center.z| <= radius.
+//    Equivalent to returing all integer points (x,y,z) that are within the given radius (using the L 1 metric) from the center.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    Since this is the L 1 metric only loops are needed. This is also know as the taxi-cab metric.
+pub fn integer_points_inside_dimond_3d(center: Point3D, radius: isize) -> Vec<Point3D>{
+    let mut cubes: Vec<Point3D> = Vec::new();
+    for x_cords in -radius .. radius +1 {
+        for y_cords in (-radius + x_cords.abs()) .. radius - x_cords.abs() + 1 {
+            for z_cords in (-radius + x_cords.abs() + y_cords.abs()) .. radius - x_cords.abs() - y_cords.abs() + 1 {
+                cubes.push(Point3D{ x: x_cords + center.x, y: y_cords + center.y, z: z_cords + center.z,});
+            }
+        }
+    }
+    cubes
+}
+
+//try_integer_points_inside_dimond_3d
+//Purpose:
+//    Returns the same points as integer_points_inside_dimond_3d, or a GeometryError if the radius is negative
+//    or the points do not fit in an isize.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_dimond_3d(center: Point3D, radius: isize) -> Result<Vec<Point3D>, GeometryError>{
+    check_ball::<Point3D>(&[center.x,center.y,center.z],radius)?;
+    Ok(integer_points_inside_dimond_3d(center,radius))
+}
+
+//integer_points_inside_square
+//Purpose:
+//    Returns all integers points (x,y,z) such that max(|x -center.x|,|y - center.y|,|z - center.z|) <= radius.
+//    Equivalent to returing all integer points that are within the given radius (using the L infinity metric) from the center.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    Since this is the L infinity metric only loops are needed.
+pub fn integer_points_inside_l_infinity_sphere(center: Point3D, radius: isize) -> Vec<Point3D>{
+    let mut cubes: Vec<Point3D> = Vec::new();
+    for x_cords in -radius .. radius + 1 {
+        for y_cords in -radius .. radius + 1 {
+            for z_cords in -radius .. radius + 1 {
+                cubes.push(Point3D{ x: x_cords + center.x, y: y_cords + center.y, z: z_cords + center.z,});
+            }
+        }
+    }
+    cubes
+}
+
+//try_integer_points_inside_l_infinity_sphere
+//Purpose:
+//    Returns the same points as integer_points_inside_l_infinity_sphere, or a GeometryError if the radius is negative
+//    or the points do not fit in an isize.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_l_infinity_sphere(center: Point3D, radius: isize) -> Result<Vec<Point3D>, GeometryError>{
+    check_ball::<Point3D>(&[center.x,center.y,center.z],radius)?;
+    Ok(integer_points_inside_l_infinity_sphere(center,radius))
+}
+
+//integer_root
+//Purpose:
+//    Returns the largest integer x such that x^exponent <= value.
+//Pre-conditions:
+//    exponent is at least 1.
+//Notes:
+//    The floating point estimate is only a starting guess, the result is corrected with exact integer powers.
+fn integer_root(value: u128, exponent: u32) -> u128{
+    if exponent == 1 {
+        return value;
+    }
+    let fits = |root: u128| root.checked_pow(exponent).is_some_and(|power| power <= value);
+    let mut root = (value as f64).powf(1.0/(exponent as f64)) as u128;
+    while !fits(root) {
+        root -= 1;
+    }
+    while fits(root + 1) {
+        root += 1;
+    }
+    root
+}
+
+//lp_ball_coordinates
+//Purpose:
+//    Calls emit with every integer tuple of length dimension (after the prefix) whose coordinates raised to exponent sum to at most budget.
+//Pre-conditions:
+//    exponent is at least 1.
+fn lp_ball_coordinates(dimension: usize, budget: u128, exponent: u32, prefix: &mut Vec<isize>, emit: &mut dyn FnMut(&[isize])){
+    let width = integer_root(budget,exponent) as isize;
+    for coordinate in -width ..= width {
+        prefix.push(coordinate);
+        if prefix.len() == dimension {
+            emit(prefix);
+        }else{
+            //|coordinate| <= width so its power is at most budget.
+            let used = (coordinate.unsigned_abs() as u128).pow(exponent);
+            lp_ball_coordinates(dimension,budget - used,exponent,prefix,emit);
+        }
+        prefix.pop();
+    }
+}
+
+//WidePower
+//Purpose:
+//    A non-negative integer stored as little-endian u64 limbs with no leading zero limbs,
+//    for the sums of powers of an Lp ball that do not fit in a u128.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct WidePower(Vec<u64>);
+
+impl WidePower {
+    //Returns base^exponent.
+    fn power(base: u64, exponent: u32) -> WidePower{
+        let mut limbs: Vec<u64> = if base == 0 && exponent > 0 { Vec::new() } else { vec![1] };
+        if base > 1 {
+            for _ in 0 .. exponent {
+                let mut carry: u128 = 0;
+                for limb in limbs.iter_mut() {
+                    let product = *limb as u128*base as u128 + carry;
+                    *limb = product as u64;
+                    carry = product >> 64;
+                }
+                if carry > 0 {
+                    limbs.push(carry as u64);
+                }
+            }
+        }
+        WidePower(limbs)
+    }
+
+    //Returns self + rhs.
+    fn add(&self, rhs: &WidePower) -> WidePower{
+        let mut limbs: Vec<u64> = Vec::with_capacity(cmp::max(self.0.len(),rhs.0.len()) + 1);
+        let mut carry: u128 = 0;
+        for index in 0 .. cmp::max(self.0.len(),rhs.0.len()) {
+            let sum = *self.0.get(index).unwrap_or(&0) as u128 + *rhs.0.get(index).unwrap_or(&0) as u128 + carry;
+            limbs.push(sum as u64);
+            carry = sum >> 64;
+        }
+        if carry > 0 {
+            limbs.push(carry as u64);
+        }
+        WidePower(limbs)
+    }
+}
+
+impl Ord for WidePower {
+    fn cmp(&self, rhs: &WidePower) -> Ordering {
+        self.0.len().cmp(&rhs.0.len()).then_with(|| self.0.iter().rev().cmp(rhs.0.iter().rev()))
+    }
+}
+
+impl PartialOrd for WidePower {
+    fn partial_cmp(&self, rhs: &WidePower) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+//lp_ball_coordinates_wide
+//Purpose:
+//    Calls emit with every integer tuple of length dimension (after the prefix) whose coordinates, each at most radius,
+//    raised to exponent sum to at most budget - used.
+//Pre-conditions:
+//    exponent is at least 1 and used is at most budget.
+//Notes:
+//    The largest coordinate of a row is found by a binary search between 0 and radius, comparing the powers exactly.
+fn lp_ball_coordinates_wide(dimension: usize, budget: &WidePower, used: &WidePower, radius: u64, exponent: u32, prefix: &mut Vec<isize>, emit: &mut dyn FnMut(&[isize])){
+    let fits = |coordinate: u64| used.add(&WidePower::power(coordinate,exponent)) <= *budget;
+    let (mut width, mut outside) = (0, radius + 1); //fits(width) holds and fits(outside) does not
+    while outside - width > 1 {
+        let middle = width + (outside - width)/2;
+        if middle <= radius && fits(middle) {
+            width = middle;
+        }else{
+            outside = middle;
+        }
+    }
+    let width = width as isize;
+    for coordinate in -width ..= width {
+        prefix.push(coordinate);
+        if prefix.len() == dimension {
+            emit(prefix);
+        }else{
+            let next = used.add(&WidePower::power(coordinate.unsigned_abs() as u64,exponent));
+            lp_ball_coordinates_wide(dimension,budget,&next,radius,exponent,prefix,emit);
+        }
+        prefix.pop();
+    }
+}
+
+//try_lp_ball
+//Purpose:
+//    Calls emit with every integer offset from the center within the Lp ball, after checking the input.
+//Pre-conditions:
+//    None.
+//Notes:
+//    When radius^p fits in a u128 the rows are found with integer roots, otherwise (only for p of 3 or more) with WidePower.
+fn try_lp_ball<T>(center: &[isize], radius: isize, exponent: u32, emit: &mut dyn FnMut(&[isize])) -> Result<(), GeometryError>{
+    if exponent == 0 {
+        return Err(GeometryError::InvalidExponent);
+    }
+    check_ball::<T>(center,radius)?;
+    match (radius as u128).checked_pow(exponent) {
+        Some(budget) => lp_ball_coordinates(center.len(),budget,exponent,&mut Vec::new(),emit),
+        None => {
+            let radius = radius as u64;
+            lp_ball_coordinates_wide(center.len(),&WidePower::power(radius,exponent),&WidePower(Vec::new()),radius,exponent,&mut Vec::new(),emit);
+        },
+    }
+    Ok(())
+}
+
+//lp_ball_or_panic
+//Purpose:
+//    Unwraps the points of an Lp ball, returning no points for a negative radius and panicking otherwise.
+//Pre-conditions:
+//    None.
+fn lp_ball_or_panic<T>(points: Result<Vec<T>, GeometryError>) -> Vec<T>{
+    match points {
+        Ok(points) => points,
+        Err(GeometryError::NegativeRadius) => Vec::new(),
+        Err(GeometryError::InvalidExponent) => panic!("The exponent of an Lp ball must be at least 1!"),
+        Err(_) => panic!("Overflow while finding the points inside a ball!"),
+    }
+}
+
+//integer_points_inside_lp_ball_2d
+//Purpose:
+//    Returns all integer points (x,y) such that |x - center.x|^p + |y - center.y|^p <= radius^p.
+//Pre-conditions:
+//    p is at least 1.
+//Notes:
+//    A negative radius has no points.
+//    p = 1 gives integer_points_inside_dimond_2d and p = 2 gives integer_points_inside_circle,
+//    while larger p approach integer_points_inside_square.
+pub fn integer_points_inside_lp_ball_2d(center: Point2D, radius: isize, p: u32) -> Vec<Point2D>{
+    lp_ball_or_panic(try_integer_points_inside_lp_ball_2d(center,radius,p))
+}
+
+//try_integer_points_inside_lp_ball_2d
+//Purpose:
+//    Returns the same points as integer_points_inside_lp_ball_2d, or a GeometryError if the radius is negative, p is 0,
+//    or the points do not fit in an isize.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_lp_ball_2d(center: Point2D, radius: isize, p: u32) -> Result<Vec<Point2D>, GeometryError>{
+    let mut squares: Vec<Point2D> = Vec::new();
+    try_lp_ball::<Point2D>(&[center.x,center.y],radius,p,&mut |offset| squares.push(Point2D{ x: offset[0] + center.x, y: offset[1] + center.y,}))?;
+    Ok(squares)
+}
+
+//integer_points_inside_lp_ball_3d
+//Purpose:
+//    Returns all integer points (x,y,z) such that |x - center.x|^p + |y - center.y|^p + |z - center.z|^p <= radius^p.
+//Pre-conditions:
+//    p is at least 1.
+//Notes:
+//    A negative radius has no points.
+//    p = 1 gives integer_points_inside_dimond_3d and p = 2 gives integer_points_inside_sphere,
+//    while larger p approach integer_points_inside_l_infinity_sphere.
+pub fn integer_points_inside_lp_ball_3d(center: Point3D, radius: isize, p: u32) -> Vec<Point3D>{
+    lp_ball_or_panic(try_integer_points_inside_lp_ball_3d(center,radius,p))
+}
+
+//try_integer_points_inside_lp_ball_3d
+//Purpose:
+//    Returns the same points as integer_points_inside_lp_ball_3d, or a GeometryError if the radius is negative, p is 0,
+//    or the points do not fit in an isize.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_lp_ball_3d(center: Point3D, radius: isize, p: u32) -> Result<Vec<Point3D>, GeometryError>{
+    let mut cubes: Vec<Point3D> = Vec::new();
+    try_lp_ball::<Point3D>(&[center.x,center.y,center.z],radius,p,&mut |offset| cubes.push(Point3D{ x: offset[0] + center.x, y: offset[1] + center.y, z: offset[2] + center.z,}))?;
+    Ok(cubes)
+}
+
+//count_disk
+//Purpose:
+//    Returns the number of integer points (x,y) with x^2 + y^2 <= radius_squared, or None on overflow.
+//Pre-conditions:
+//    None.
+//Notes:
+//    Sums the rows of the disk, each found with an exact integer square root, so this takes time proportional to the radius.
+fn count_disk(radius_squared: usize) -> Option<usize>{
+    let radius = isqrt(radius_squared);
+    let mut count = 2*radius + 1;
+    for y_cord in 1 ..= radius {
+        let row = 2*isqrt(radius_squared - y_cord*y_cord) + 1;
+        count = count.checked_add(row.checked_mul(2)?)?;
+    }
+    Some(count)
+}
+
+//count_ball
+//Purpose:
+//    Returns the number of integer points within radius of the origin under the given metric, or None on overflow.
+//Pre-conditions:
+//    dimension is 2 or 3.
+fn count_ball(dimension: u32, radius: isize, metric: Metric) -> Option<usize>{
+    if radius < 0 {
+        return Some(0);
+    }
+    let radius = radius as usize;
+    match (dimension, metric) {
+        (_, Metric::LInfinity) => radius.checked_mul(2)?.checked_add(1)?.checked_pow(dimension),
+        (2, Metric::L1) => radius.checked_mul(radius)?.checked_add(radius)?.checked_mul(2)?.checked_add(1),
+        (_, Metric::L1) => {
+            //(2r + 1)(2r^2 + 2r + 3)/3, dividing whichever factor is a multiple of 3 first.
+            let side = radius.checked_mul(2)?.checked_add(1)?;
+            let other = radius.checked_mul(radius)?.checked_add(radius)?.checked_mul(2)?.checked_add(3)?;
+            if side % 3 == 0 {
+                (side/3).checked_mul(other)
+            }else{
+                side.checked_mul(other/3)
+            }
+        },
+        (2, Metric::L2) => count_disk(radius.checked_mul(radius)?),
+        (_, Metric::L2) => {
+            let radius_squared = radius.checked_mul(radius)?;
+            let mut count = count_disk(radius_squared)?;
+            for z_cord in 1 ..= radius {
+                count = count.checked_add(count_disk(radius_squared - z_cord*z_cord)?.checked_mul(2)?)?;
+            }
+            Some(count)
+        },
+    }
+}
+
+//count_or_panic
+//Purpose:
+//    Unwraps a count, panicking if it overflowed.
+//Pre-conditions:
+//    None.
+fn count_or_panic(count: Option<usize>) -> usize{
+    match count {
+        Some(count) => count,
+        None => panic!("Overflow while counting the points inside a ball!"),
+    }
+}
+
+//count_integer_points_inside_circle
+//Purpose:
+//    Returns the number of points integer_points_inside_circle returns for the given radius, without finding them.
+//Pre-conditions:
+//    The count fits in a usize.
+//Notes:
+//    A negative radius has no points.
+//    Takes time proportional to the radius.
+pub fn count_integer_points_inside_circle(radius: isize) -> usize{
+    count_or_panic(count_ball(2,radius,Metric::L2))
+}
+
+//checked_count_integer_points_inside_circle
+//Purpose:
+//    Returns the same count as count_integer_points_inside_circle, or an OverflowError if it does not fit in a usize.
+//Pre-conditions:
+//    None.
+pub fn checked_count_integer_points_inside_circle(radius: isize) -> Result<usize, OverflowError>{
+    count_ball(2,radius,Metric::L2).ok_or(OverflowError)
+}
+
+//count_integer_points_inside_dimond_2d
+//Purpose:
+//    Returns the number of points integer_points_inside_dimond_2d returns for the given radius, which is 2r^2 + 2r + 1.
+//Pre-conditions:
+//    The count fits in a usize.
+//Notes:
+//    A negative radius has no points.
+pub fn count_integer_points_inside_dimond_2d(radius: isize) -> usize{
+    count_or_panic(count_ball(2,radius,Metric::L1))
+}
+
+//checked_count_integer_points_inside_dimond_2d
+//Purpose:
+//    Returns the same count as count_integer_points_inside_dimond_2d, or an OverflowError if it does not fit in a usize.
+//Pre-conditions:
+//    None.
+pub fn checked_count_integer_points_inside_dimond_2d(radius: isize) -> Result<usize, OverflowError>{
+    count_ball(2,radius,Metric::L1).ok_or(OverflowError)
+}
+
+//count_integer_points_inside_square
+//Purpose:
+//    Returns the number of points integer_points_inside_square returns for the given radius, which is (2r + 1)^2.
+//Pre-conditions:
+//    The count fits in a usize.
+//Notes:
+//    A negative radius has no points.
+pub fn count_integer_points_inside_square(radius: isize) -> usize{
+    count_or_panic(count_ball(2,radius,Metric::LInfinity))
+}
+
+//checked_count_integer_points_inside_square
+//Purpose:
+//    Returns the same count as count_integer_points_inside_square, or an OverflowError if it does not fit in a usize.
+//Pre-conditions:
+//    None.
+pub fn checked_count_integer_points_inside_square(radius: isize) -> Result<usize, OverflowError>{
+    count_ball(2,radius,Metric::LInfinity).ok_or(OverflowError)
+}
+
+//count_integer_points_inside_sphere
+//Purpose:
+//    Returns the number of points integer_points_inside_sphere returns for the given radius, without finding them.
+//Pre-conditions:
+//    The count fits in a usize.
+//Notes:
+//    A negative radius has no points.
+//    Counts every slice of the ball as a disk, so this takes time proportional to the radius squared (instead of its cube).
+pub fn count_integer_points_inside_sphere(radius: isize) -> usize{
+    count_or_panic(count_ball(3,radius,Metric::L2))
+}
+
+//checked_count_integer_points_inside_sphere
+//Purpose:
+//    Returns the same count as count_integer_points_inside_sphere, or an OverflowError if it does not fit in a usize.
+//Pre-conditions:
+//    None.
+pub fn checked_count_integer_points_inside_sphere(radius: isize) -> Result<usize, OverflowError>{
+    count_ball(3,radius,Metric::L2).ok_or(OverflowError)
+}
+
+//count_integer_points_inside_dimond_3d
+//Purpose:
+//    Returns the number of points integer_points_inside_dimond_3d returns for the given radius, which is (2r + 1)(2r^2 + 2r + 3)/3.
+//Pre-conditions:
+//    The count fits in a usize.
+//Notes:
+//    A negative radius has no points.
+pub fn count_integer_points_inside_dimond_3d(radius: isize) -> usize{
+    count_or_panic(count_ball(3,radius,Metric::L1))
+}
+
+//checked_count_integer_points_inside_dimond_3d
+//Purpose:
+//    Returns the same count as count_integer_points_inside_dimond_3d, or an OverflowError if it does not fit in a usize.
+//Pre-conditions:
+//    None.
+pub fn checked_count_integer_points_inside_dimond_3d(radius: isize) -> Result<usize, OverflowError>{
+    count_ball(3,radius,Metric::L1).ok_or(OverflowError)
+}
+
+//count_integer_points_inside_l_infinity_sphere
+//Purpose:
+//    Returns the number of points integer_points_inside_l_infinity_sphere returns for the given radius, which is (2r + 1)^3.
+//Pre-conditions:
+//    The count fits in a usize.
+//Notes:
+//    A negative radius has no points.
+pub fn count_integer_points_inside_l_infinity_sphere(radius: isize) -> usize{
+    count_or_panic(count_ball(3,radius,Metric::LInfinity))
+}
+
+//checked_count_integer_points_inside_l_infinity_sphere
+//Purpose:
+//    Returns the same count as count_integer_points_inside_l_infinity_sphere, or an OverflowError if it does not fit in a usize.
+//Pre-conditions:
+//    None.
+pub fn checked_count_integer_points_inside_l_infinity_sphere(radius: isize) -> Result<usize, OverflowError>{
+    count_ball(3,radius,Metric::LInfinity).ok_or(OverflowError)
+}
+
+//CircleConnectivity
+//Purpose:
+//    Selects which points of a disk make up its boundary.
+//    EightConnected: the points with one of their 4 neighbours outside the disk, a thin curve where consecutive points
+//                    share an edge or a corner.
+//    FourConnected: the points with one of their 8 neighbours outside the disk, a thicker curve where consecutive points share an edge.
+//Notes:
+//    Both are inner borders of integer_points_inside_circle, every point returned is inside the disk.
+//    This is not the curve the midpoint circle algorithm draws, which also uses points just outside the disk.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CircleConnectivity {
+    EightConnected,
+    FourConnected,
+}
+
+//SphereConnectivity
+//Purpose:
+//    Selects which points of a ball make up its boundary.
+//    TwentySixConnected: the points with one of their 6 face neighbours outside the ball, a thin shell.
+//    SixConnected: the points with one of their 26 neighbours outside the ball, a thicker shell whose points are joined by faces.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SphereConnectivity {
+    TwentySixConnected,
+    SixConnected,
+}
+
+//half_width
+//Purpose:
+//    Returns the largest integer x with x*x <= remaining, or -1 if remaining is negative (the row is empty).
+//Pre-conditions:
+//    None.
+fn half_width(remaining: isize) -> isize{
+    if remaining < 0 {
+        -1
+    }else{
+        isqrt(remaining as usize) as isize
+    }
+}
+
+//inner_half_width
+//Purpose:
+//    Returns the smallest non-negative integer x with x*x >= remaining.
+//Pre-conditions:
+//    None.
+fn inner_half_width(remaining: isize) -> isize{
+    if remaining <= 0 {
+        0
+    }else{
+        isqrt(remaining as usize - 1) as isize + 1
+    }
+}
+
+//push_row_ends
+//Purpose:
+//    Calls emit with every x such that threshold <= |x| <= width.
+//Pre-conditions:
+//    threshold is non-negative.
+fn push_row_ends(threshold: isize, width: isize, emit: &mut dyn FnMut(isize)){
+    for x_cord in threshold ..= width {
+        emit(x_cord);
+        if x_cord != 0 {
+            emit(-x_cord);
+        }
+    }
+}
+
+//integer_points_on_circle
+//Purpose:
+//    Returns the points of integer_points_inside_circle(center,radius) on its boundary, using the given CircleConnectivity.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    Every row of the disk is the interval from -w(y) to w(y), where w(y) = isqrt(radius^2 - y^2),
+//    so a point has a neighbour outside exactly when |x| is large enough compared to the widths of the nearby rows.
+pub fn integer_points_on_circle(center: Point2D, radius: isize, connectivity: CircleConnectivity) -> Vec<Point2D>{
+    let mut squares: Vec<Point2D> = Vec::new();
+    let width = |y_cord: isize| half_width(radius*radius - y_cord*y_cord);
+    for y_cord in -radius ..= radius {
+        let threshold = match connectivity {
+            CircleConnectivity::EightConnected => cmp::min(width(y_cord),cmp::min(width(y_cord - 1),width(y_cord + 1)) + 1),
+            CircleConnectivity::FourConnected => cmp::min(width(y_cord),cmp::min(width(y_cord - 1),width(y_cord + 1))),
+        };
+        push_row_ends(cmp::max(threshold,0),width(y_cord),&mut |x_cord| squares.push(Point2D{ x: x_cord + center.x, y: y_cord + center.y,}));
+    }
+    squares
+}
+
+//try_integer_points_on_circle
+//Purpose:
+//    Returns the same points as integer_points_on_circle, or a GeometryError if the radius is negative
+//    or the points do not fit in an isize.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_on_circle(center: Point2D, radius: isize, connectivity: CircleConnectivity) -> Result<Vec<Point2D>, GeometryError>{
+    check_ball::<Point2D>(&[center.x,center.y],radius)?;
+    Ok(integer_points_on_circle(center,radius,connectivity))
+}
+
+//integer_points_inside_annulus
+//Purpose:
+//    Returns all integer points (x,y) whose distance from the center is at least inner_radius and at most outer_radius.
+//Pre-conditions:
+//    inner_radius and outer_radius are non-negative.
+//Notes:
+//    If inner_radius is greater than outer_radius there are no points.
+pub fn integer_points_inside_annulus(center: Point2D, inner_radius: isize, outer_radius: isize) -> Vec<Point2D>{
+    let mut squares: Vec<Point2D> = Vec::new();
+    for y_cord in -outer_radius ..= outer_radius {
+        let width = half_width(outer_radius*outer_radius - y_cord*y_cord);
+        let threshold = inner_half_width(inner_radius*inner_radius - y_cord*y_cord);
+        push_row_ends(threshold,width,&mut |x_cord| squares.push(Point2D{ x: x_cord + center.x, y: y_cord + center.y,}));
+    }
+    squares
+}
+
+//try_integer_points_inside_annulus
+//Purpose:
+//    Returns the same points as integer_points_inside_annulus, or a GeometryError if a radius is negative
+//    or the points do not fit in an isize.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_annulus(center: Point2D, inner_radius: isize, outer_radius: isize) -> Result<Vec<Point2D>, GeometryError>{
+    if inner_radius < 0 {
+        return Err(GeometryError::NegativeRadius);
+    }
+    check_ball::<Point2D>(&[center.x,center.y],outer_radius)?;
+    Ok(integer_points_inside_annulus(center,inner_radius,outer_radius))
+}
+
+//integer_points_on_sphere
+//Purpose:
+//    Returns the points of integer_points_inside_sphere(center,radius) on its boundary, using the given SphereConnectivity.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    As with integer_points_on_circle, every row along x is the interval from -w(y,z) to w(y,z),
+//    and the neighbours of a point are compared through the widths of the nearby rows.
+pub fn integer_points_on_sphere(center: Point3D, radius: isize, connectivity: SphereConnectivity) -> Vec<Point3D>{
+    let mut cubes: Vec<Point3D> = Vec::new();
+    let width = |y_cord: isize, z_cord: isize| half_width(radius*radius - y_cord*y_cord - z_cord*z_cord);
+    for y_cord in -radius ..= radius {
+        for z_cord in -radius ..= radius {
+            let threshold = match connectivity {
+                SphereConnectivity::TwentySixConnected => {
+                    let faces = cmp::min(cmp::min(width(y_cord - 1,z_cord),width(y_cord + 1,z_cord)),cmp::min(width(y_cord,z_cord - 1),width(y_cord,z_cord + 1)));
+                    cmp::min(width(y_cord,z_cord),faces + 1)
+                },
+                SphereConnectivity::SixConnected => {
+                    let mut smallest = width(y_cord,z_cord);
+                    for dy in -1 ..= 1 {
+                        for dz in -1 ..= 1 {
+                            smallest = cmp::min(smallest,width(y_cord + dy,z_cord + dz));
+                        }
+                    }
+                    smallest
+                },
+            };
+            push_row_ends(cmp::max(threshold,0),width(y_cord,z_cord),&mut |x_cord| cubes.push(Point3D{ x: x_cord + center.x, y: y_cord + center.y, z: z_cord + center.z,}));
+        }
+    }
+    cubes
+}
+
+//try_integer_points_on_sphere
+//Purpose:
+//    Returns the same points as integer_points_on_sphere, or a GeometryError if the radius is negative
+//    or the points do not fit in an isize.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_on_sphere(center: Point3D, radius: isize, connectivity: SphereConnectivity) -> Result<Vec<Point3D>, GeometryError>{
+    check_ball::<Point3D>(&[center.x,center.y,center.z],radius)?;
+    Ok(integer_points_on_sphere(center,radius,connectivity))
+}
+
+//integer_points_inside_spherical_shell
+//Purpose:
+//    Returns all integer points (x,y,z) whose distance from the center is at least inner_radius and at most outer_radius.
+//Pre-conditions:
+//    inner_radius and outer_radius are non-negative.
+//Notes:
+//    If inner_radius is greater than outer_radius there are no points.
+pub fn integer_points_inside_spherical_shell(center: Point3D, inner_radius: isize, outer_radius: isize) -> Vec<Point3D>{
+    let mut cubes: Vec<Point3D> = Vec::new();
+    for y_cord in -outer_radius ..= outer_radius {
+        for z_cord in -outer_radius ..= outer_radius {
+            let width = half_width(outer_radius*outer_radius - y_cord*y_cord - z_cord*z_cord);
+            let threshold = inner_half_width(inner_radius*inner_radius - y_cord*y_cord - z_cord*z_cord);
+            push_row_ends(threshold,width,&mut |x_cord| cubes.push(Point3D{ x: x_cord + center.x, y: y_cord + center.y, z: z_cord + center.z,}));
+        }
+    }
+    cubes
+}
+
+//try_integer_points_inside_spherical_shell
+//Purpose:
+//    Returns the same points as integer_points_inside_spherical_shell, or a GeometryError if a radius is negative
+//    or the points do not fit in an isize.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_spherical_shell(center: Point3D, inner_radius: isize, outer_radius: isize) -> Result<Vec<Point3D>, GeometryError>{
+    if inner_radius < 0 {
+        return Err(GeometryError::NegativeRadius);
+    }
+    check_ball::<Point3D>(&[center.x,center.y,center.z],outer_radius)?;
+    Ok(integer_points_inside_spherical_shell(center,inner_radius,outer_radius))
+}
+
+//Metric
+//Purpose:
+//    The metric a ball with a rational center and radius, or a thick line segment, is measured in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Metric {
+    L1,
+    L2,
+    LInfinity,
+}
+
+//l_1_range
+//Purpose:
+//    Returns the smallest and largest integers within radius of center, or None if a computation overflows.
+//    The smallest is greater than the largest when there are none.
+//Pre-conditions:
+//    None.
+fn l_1_range(center: Rational, radius: Rational) -> Option<(isize, isize)>{
+    if radius.is_neg() {
+        return Some((1, 0));
+    }
+    Some((center.checked_sub(radius)?.checked_ceil()?, center.checked_add(radius)?.checked_floor()?))
+}
+
+//l_2_range
+//Purpose:
+//    Returns the smallest and largest integers y with (y - center)^2 <= radius_squared, or None if a computation overflows.
+//    The smallest is greater than the largest when there are none.
+//Pre-conditions:
+//    None.
+//Notes:
+//    floor(sqrt(radius_squared)) is isqrt(floor(radius_squared)), which gives each end up to one, an exact comparison settles it.
+pub(crate) fn l_2_range(center: Rational, radius_squared: Rational) -> Option<(isize, isize)>{
+    if radius_squared.is_neg() {
+        return Some((1, 0));
+    }
+    let root = isqrt(radius_squared.checked_floor()? as usize) as isize;
+    let within = |y: isize| -> Option<bool>{
+        let offset = Rational::new_rational_from_integer(y).checked_sub(center)?;
+        Some(offset.checked_mul(offset)? <= radius_squared)
+    };
+    let mut low = center.checked_ceil()?.checked_sub(root)?;
+    let below = low.checked_sub(1)?;
+    if Rational::new_rational_from_integer(below) < center && within(below)? {
+        low = below;
+    }
+    let mut high = center.checked_floor()?.checked_add(root)?;
+    let above = high.checked_add(1)?;
+    if Rational::new_rational_from_integer(above) > center && within(above)? {
+        high = above;
+    }
+    Some((low, high))
+}
+
+//rational_ball_coordinates
+//Purpose:
+//    Calls emit with the coordinates of every integer point whose remaining coordinates (after prefix) are within budget of center.
+//    The budget is the radius left over for the L1 and L infinity metrics and the squared radius left over for the L2 metric.
+//    Returns None if a computation overflows.
+//Pre-conditions:
+//    None.
+fn rational_ball_coordinates(center: &[Rational], budget: Rational, metric: Metric, prefix: &mut Vec<isize>, emit: &mut dyn FnMut(&[isize])) -> Option<()>{
+    if center.is_empty() {
+        emit(prefix);
+        return Some(());
+    }
+    let (low, high) = if metric == Metric::L2 { l_2_range(center[0],budget)? } else { l_1_range(center[0],budget)? };
+    for coordinate in low ..= high {
+        let offset = Rational::new_rational_from_integer(coordinate).checked_sub(center[0])?;
+        let next_budget = match metric {
+            Metric::L1 => budget.checked_sub(offset.abs())?,
+            Metric::L2 => budget.checked_sub(offset.checked_mul(offset)?)?,
+            Metric::LInfinity => budget,
+        };
+        prefix.push(coordinate);
+        rational_ball_coordinates(&center[1 ..],next_budget,metric,prefix,emit)?;
+        prefix.pop();
+    }
+    Some(())
+}
+
+//tiny_rational_ball
+//Purpose:
+//    Calls emit with the coordinates of the integer point within radius of center in the L2 metric, if there is one,
+//    or returns GeometryError::Overflow if a computation overflows.
+//Pre-conditions:
+//    radius is non-negative and less than 1/2.
+//Notes:
+//    Two integer points are at least 1 apart, so at most one of them is this close to center, the one each coordinate rounds to.
+//    Its offsets are each at most radius, so the squares (which overflow for radii with large denominators) are only needed
+//    when two or more of them are non-zero.
+fn tiny_rational_ball(center: &[Rational], radius: Rational, emit: &mut dyn FnMut(&[isize])) -> Result<(), GeometryError>{
+    let zero = Rational::new_rational_from_integer(0);
+    let mut point: Vec<isize> = Vec::with_capacity(center.len());
+    let mut offsets: Vec<Rational> = Vec::new();
+    for coordinate in center {
+        let (low, high) = l_1_range(*coordinate,radius).ok_or(GeometryError::Overflow)?;
+        if low > high {
+            return Ok(());
+        }
+        let offset = Rational::new_rational_from_integer(low).checked_sub(*coordinate).ok_or(GeometryError::Overflow)?;
+        if offset != zero {
+            offsets.push(offset);
+        }
+        point.push(low);
+    }
+    if offsets.len() > 1 {
+        let mut distance_squared = zero;
+        for offset in offsets {
+            distance_squared = offset.checked_mul(offset).and_then(|square| distance_squared.checked_add(square)).ok_or(GeometryError::Overflow)?;
+        }
+        if distance_squared > radius.checked_mul(radius).ok_or(GeometryError::Overflow)? {
+            return Ok(());
+        }
+    }
+    emit(&point);
+    Ok(())
+}
+
+//rational_ball
+//Purpose:
+//    Calls emit with the coordinates of every integer point within radius of center in the given metric,
+//    or returns a GeometryError if the radius is negative, the points do not fit in a Vec of T or a computation overflows.
+//Pre-conditions:
+//    None.
+fn rational_ball<T>(center: &[Rational], radius: Rational, metric: Metric, emit: &mut dyn FnMut(&[isize])) -> Result<(), GeometryError>{
+    if radius.is_neg() {
+        return Err(GeometryError::NegativeRadius);
+    }
+    if metric == Metric::L2 && radius < Rational::new_rational(1,2) {
+        return tiny_rational_ball(center,radius,emit);
+    }
+    let mut count: Option<usize> = Some(1);
+    for coordinate in center {
+        let (low, high) = l_1_range(*coordinate,radius).ok_or(GeometryError::Overflow)?;
+        count = count.and_then(|count| count.checked_mul(high.checked_sub(low)?.checked_add(1)? as usize));
+    }
+    check_cell_count::<T>(count)?;
+    let budget = if metric == Metric::L2 { radius.checked_mul(radius).ok_or(GeometryError::Overflow)? } else { radius };
+    rational_ball_coordinates(center,budget,metric,&mut Vec::with_capacity(center.len()),emit).ok_or(GeometryError::Overflow)
+}
+
+fn try_rational_ball_2d(center: RationalPoint2D, radius: Rational, metric: Metric) -> Result<Vec<Point2D>, GeometryError>{
+    let mut squares: Vec<Point2D> = Vec::new();
+    rational_ball::<Point2D>(&[center.x,center.y],radius,metric,&mut |cords| squares.push(Point2D{ x: cords[0], y: cords[1],}))?;
+    Ok(squares)
+}
+
+fn try_rational_ball_3d(center: RationalPoint3D, radius: Rational, metric: Metric) -> Result<Vec<Point3D>, GeometryError>{
+    let mut cubes: Vec<Point3D> = Vec::new();
+    rational_ball::<Point3D>(&[center.x,center.y,center.z],radius,metric,&mut |cords| cubes.push(Point3D{ x: cords[0], y: cords[1], z: cords[2],}))?;
+    Ok(cubes)
+}
+
+//The functions without the try_ prefix return no points for a negative radius and panic if a computation overflows.
+fn rational_ball_or_panic<T>(points: Result<Vec<T>, GeometryError>) -> Vec<T>{
+    match points {
+        Ok(points) => points,
+        Err(GeometryError::NegativeRadius) => Vec::new(),
+        Err(_) => panic!("Overflow while finding the points inside a ball (Rationals)!"),
+    }
+}
+
+//integer_points_inside_circle_rational
+//Purpose:
+//    Returns all integer points (x,y) that are within the given rational radius from the rational center.
+//Pre-conditions:
+//    None.
+//Notes:
+//    The squared distances are compared with the squared radius exactly. A negative radius gives no points.
+pub fn integer_points_inside_circle_rational(center: RationalPoint2D, radius: Rational) -> Vec<Point2D>{
+    rational_ball_or_panic(try_integer_points_inside_circle_rational(center,radius))
+}
+
+//try_integer_points_inside_circle_rational
+//Purpose:
+//    Returns the same points as integer_points_inside_circle_rational, or a GeometryError if the radius is negative,
+//    the points do not fit in a Vec or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_circle_rational(center: RationalPoint2D, radius: Rational) -> Result<Vec<Point2D>, GeometryError>{
+    try_rational_ball_2d(center,radius,Metric::L2)
+}
+
+//integer_points_inside_dimond_2d_rational
+//Purpose:
+//    Returns all integer points (x,y) such that |x - center.x| + |y - center.y| <= radius, for a rational center and radius.
+//Pre-conditions:
+//    None.
+//Notes:
+//    A negative radius gives no points.
+pub fn integer_points_inside_dimond_2d_rational(center: RationalPoint2D, radius: Rational) -> Vec<Point2D>{
+    rational_ball_or_panic(try_integer_points_inside_dimond_2d_rational(center,radius))
+}
+
+//try_integer_points_inside_dimond_2d_rational
+//Purpose:
+//    Returns the same points as integer_points_inside_dimond_2d_rational, or a GeometryError if the radius is negative,
+//    the points do not fit in a Vec or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_dimond_2d_rational(center: RationalPoint2D, radius: Rational) -> Result<Vec<Point2D>, GeometryError>{
+    try_rational_ball_2d(center,radius,Metric::L1)
+}
+
+//integer_points_inside_square_rational
+//Purpose:
+//    Returns all integer points (x,y) such that max(|x - center.x|,|y - center.y|) <= radius, for a rational center and radius.
+//Pre-conditions:
+//    None.
+//Notes:
+//    A negative radius gives no points.
+pub fn integer_points_inside_square_rational(center: RationalPoint2D, radius: Rational) -> Vec<Point2D>{
+    rational_ball_or_panic(try_integer_points_inside_square_rational(center,radius))
+}
+
+//try_integer_points_inside_square_rational
+//Purpose:
+//    Returns the same points as integer_points_inside_square_rational, or a GeometryError if the radius is negative,
+//    the points do not fit in a Vec or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_square_rational(center: RationalPoint2D, radius: Rational) -> Result<Vec<Point2D>, GeometryError>{
+    try_rational_ball_2d(center,radius,Metric::LInfinity)
+}
+
+//integer_points_inside_sphere_rational
+//Purpose:
+//    Returns all integer points (x,y,z) that are within the given rational radius from the rational center.
+//Pre-conditions:
+//    None.
+//Notes:
+//    The squared distances are compared with the squared radius exactly. A negative radius gives no points.
+pub fn integer_points_inside_sphere_rational(center: RationalPoint3D, radius: Rational) -> Vec<Point3D>{
+    rational_ball_or_panic(try_integer_points_inside_sphere_rational(center,radius))
+}
+
+//try_integer_points_inside_sphere_rational
+//Purpose:
+//    Returns the same points as integer_points_inside_sphere_rational, or a GeometryError if the radius is negative,
+//    the points do not fit in a Vec or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_sphere_rational(center: RationalPoint3D, radius: Rational) -> Result<Vec<Point3D>, GeometryError>{
+    try_rational_ball_3d(center,radius,Metric::L2)
+}
+
+//integer_points_inside_dimond_3d_rational
+//Purpose:
+//    Returns all integer points (x,y,z) such that |x - center.x| + |y - center.y| + |z - center.z| <= radius, for a rational center and radius.
+//Pre-conditions:
+//    None.
+//Notes:
+//    A negative radius gives no points.
+pub fn integer_points_inside_dimond_3d_rational(center: RationalPoint3D, radius: Rational) -> Vec<Point3D>{
+    rational_ball_or_panic(try_integer_points_inside_dimond_3d_rational(center,radius))
+}
+
+//try_integer_points_inside_dimond_3d_rational
+//Purpose:
+//    Returns the same points as integer_points_inside_dimond_3d_rational, or a GeometryError if the radius is negative,
+//    the points do not fit in a Vec or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_dimond_3d_rational(center: RationalPoint3D, radius: Rational) -> Result<Vec<Point3D>, GeometryError>{
+    try_rational_ball_3d(center,radius,Metric::L1)
+}
+
+//integer_points_inside_l_infinity_sphere_rational
+//Purpose:
+//    Returns all integer points (x,y,z) such that max(|x - center.x|,|y - center.y|,|z - center.z|) <= radius, for a rational center and radius.
+//Pre-conditions:
+//    None.
+//Notes:
+//    A negative radius gives no points.
+pub fn integer_points_inside_l_infinity_sphere_rational(center: RationalPoint3D, radius: Rational) -> Vec<Point3D>{
+    rational_ball_or_panic(try_integer_points_inside_l_infinity_sphere_rational(center,radius))
+}
+
+//try_integer_points_inside_l_infinity_sphere_rational
+//Purpose:
+//    Returns the same points as integer_points_inside_l_infinity_sphere_rational, or a GeometryError if the radius is negative,
+//    the points do not fit in a Vec or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_l_infinity_sphere_rational(center: RationalPoint3D, radius: Rational) -> Result<Vec<Point3D>, GeometryError>{
+    try_rational_ball_3d(center,radius,Metric::LInfinity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort;
+
+    #[test]
+    #[ignore]
+    fn points_in_sphere(){
+        assert_eq!(sort(integer_points_inside_sphere_slow(Point3D{ x: 0, y: 0, z: 0,},0)),
+                   sort(integer_points_inside_sphere(Point3D{ x: 0, y: 0, z: 0,},0)));
+        assert_eq!(sort(integer_points_inside_sphere_slow(Point3D{ x: 0, y: 0, z: 0,},1)),
+                   sort(integer_points_inside_sphere(Point3D{ x: 0, y: 0, z: 0,},1)));
+        assert_eq!(sort(integer_points_inside_sphere_slow(Point3D{ x: 0, y: 0, z: 0,},3)),
+                   sort(integer_points_inside_sphere(Point3D{ x: 0, y: 0, z: 0,},3)));
+        assert_eq!(sort(integer_points_inside_sphere_slow(Point3D{ x: 0, y: 0, z: 0,},10)),
+                   sort(integer_points_inside_sphere(Point3D{ x: 0, y: 0, z: 0,},10)));
+        assert_eq!(sort(integer_points_inside_sphere_slow(Point3D{ x: 0, y: 0, z: 0,},100)),
+                   sort(integer_points_inside_sphere(Point3D{ x: 0, y: 0, z: 0,},100)));
+    }
+
+    #[test]
+    fn points_in_circle(){
+        assert_eq!(sort(integer_points_inside_circle_slow(Point2D{ x: 0, y: 0,},0)),
+                   sort(integer_points_inside_circle(Point2D{ x: 0, y: 0,},0)));
+        assert_eq!(sort(integer_points_inside_circle_slow(Point2D{ x: 0, y: 0,},1)),
+                   sort(integer_points_inside_circle(Point2D{ x: 0, y: 0,},1)));
+        assert_eq!(sort(integer_points_inside_circle_slow(Point2D{ x: 0, y: 0,},10)),
+                   sort(integer_points_inside_circle(Point2D{ x: 0, y: 0,},10)));
+        assert_eq!(sort(integer_points_inside_circle_slow(Point2D{ x: 0, y: 0,},100)),
+                   sort(integer_points_inside_circle(Point2D{ x: 0, y: 0,},100)));
+    }
+
+    #[test]
+    fn fallible_balls(){
+        let center = Point2D{ x: 3, y: -2,};
+        assert_eq!(try_integer_points_inside_circle(center,4),Ok(integer_points_inside_circle(center,4)));
+        assert_eq!(try_integer_points_inside_dimond_2d(center,4),Ok(integer_points_inside_dimond_2d(center,4)));
+        assert_eq!(try_integer_points_inside_square(center,0),Ok(vec![center]));
+        assert_eq!(try_integer_points_inside_circle(center,-1),Err(GeometryError::NegativeRadius));
+        assert_eq!(try_integer_points_inside_square(Point2D{ x: isize::MAX, y: 0,},1),Err(GeometryError::RangeTooLarge));
+        assert_eq!(try_integer_points_inside_dimond_2d(center,1 << 32),Err(GeometryError::RangeTooLarge));
+        let center = Point3D{ x: 1, y: 0, z: -7,};
+        assert_eq!(try_integer_points_inside_sphere(center,3),Ok(integer_points_inside_sphere(center,3)));
+        assert_eq!(try_integer_points_inside_dimond_3d(center,3),Ok(integer_points_inside_dimond_3d(center,3)));
+        assert_eq!(try_integer_points_inside_l_infinity_sphere(center,2),Ok(integer_points_inside_l_infinity_sphere(center,2)));
+        assert_eq!(try_integer_points_inside_dimond_3d(center,-5),Err(GeometryError::NegativeRadius));
+        assert_eq!(try_integer_points_inside_sphere(Point3D{ x: 0, y: isize::MIN, z: 0,},1),Err(GeometryError::RangeTooLarge));
+        assert_eq!(try_integer_points_inside_l_infinity_sphere(center,1 << 22),Err(GeometryError::RangeTooLarge));
+    }
+
+    #[test]
+    fn exact_l_2_balls(){
+        for radius in 0 .. 40 {
+            assert_eq!(sort(integer_points_inside_circle_slow(Point2D{ x: 3, y: -1,},radius)),
+                       sort(integer_points_inside_circle(Point2D{ x: 3, y: -1,},radius)));
+        }
+        for radius in 0 .. 12 {
+            assert_eq!(sort(integer_points_inside_sphere_slow(Point3D{ x: -2, y: 0, z: 5,},radius)),
+                       sort(integer_points_inside_sphere(Point3D{ x: -2, y: 0, z: 5,},radius)));
+        }
+    }
+
+    fn rational_distance(offsets: &[Rational], metric: Metric) -> Rational{
+        match metric {
+            Metric::L1 => offsets.iter().fold(Rational::new_rational(0,1),|sum, offset| sum + offset.abs()),
+            Metric::L2 => offsets.iter().fold(Rational::new_rational(0,1),|sum, offset| sum + *offset**offset),
+            Metric::LInfinity => offsets.iter().fold(Rational::new_rational(0,1),|largest, offset| std::cmp::max(largest,offset.abs())),
+        }
+    }
+
+    #[test]
+    fn rational_balls(){
+        let values = [Rational::new_rational_from_integers(-3,2), Rational::new_rational_from_integers(-1,3), Rational::new_rational(0,1),
+                      Rational::new_rational(1,2), Rational::new_rational(7,5), Rational::new_rational(2,1)];
+        let radii = [Rational::new_rational(0,1), Rational::new_rational(1,3), Rational::new_rational(1,2), Rational::new_rational(5,7), Rational::new_rational(1,1),
+                     Rational::new_rational(13,6), Rational::new_rational(5,2), Rational::new_rational(3,1)];
+        for cx in values.iter() {
+            for cy in values.iter() {
+                for radius in radii.iter() {
+                    let center = RationalPoint2D{ x: *cx, y: *cy,};
+                    let center_3d = RationalPoint3D{ x: *cx, y: *cy, z: -*cx,};
+                    for metric in [Metric::L1, Metric::L2, Metric::LInfinity].iter() {
+                        let bound = if *metric == Metric::L2 { *radius**radius } else { *radius };
+                        let mut expected: Vec<Point2D> = Vec::new();
+                        let mut expected_3d: Vec<Point3D> = Vec::new();
+                        for x_cord in -6 .. 7 {
+                            for y_cord in -6 .. 7 {
+                                let dx = Rational::new_rational_from_integer(x_cord) - *cx;
+                                let dy = Rational::new_rational_from_integer(y_cord) - *cy;
+                                if rational_distance(&[dx,dy],*metric) <= bound {
+                                    expected.push(Point2D{ x: x_cord, y: y_cord,});
+                                }
+                                for z_cord in -6 .. 7 {
+                                    let dz = Rational::new_rational_from_integer(z_cord) + *cx;
+                                    if rational_distance(&[dx,dy,dz],*metric) <= bound {
+                                        expected_3d.push(Point3D{ x: x_cord, y: y_cord, z: z_cord,});
+                                    }
+                                }
+                            }
+                        }
+                        assert_eq!(try_rational_ball_2d(center,*radius,*metric).map(sort),Ok(expected));
+                        assert_eq!(try_rational_ball_3d(center_3d,*radius,*metric).map(sort),Ok(expected_3d));
+                    }
+                }
+            }
+        }
+        let origin = RationalPoint2D{ x: Rational::new_rational(0,1), y: Rational::new_rational(0,1),};
+        let center = Point3D{ x: 2, y: -1, z: 0,};
+        let rational_center = RationalPoint3D::from(center);
+        for radius in 0 .. 8 {
+            let rational_radius = Rational::new_rational_from_integer(radius);
+            assert_eq!(sort(integer_points_inside_circle_rational(origin,rational_radius)),sort(integer_points_inside_circle(Point2D{ x: 0, y: 0,},radius)));
+            assert_eq!(sort(integer_points_inside_dimond_2d_rational(origin,rational_radius)),sort(integer_points_inside_dimond_2d(Point2D{ x: 0, y: 0,},radius)));
+            assert_eq!(sort(integer_points_inside_square_rational(origin,rational_radius)),sort(integer_points_inside_square(Point2D{ x: 0, y: 0,},radius)));
+            assert_eq!(sort(integer_points_inside_sphere_rational(rational_center,rational_radius)),sort(integer_points_inside_sphere(center,radius)));
+            assert_eq!(sort(integer_points_inside_dimond_3d_rational(rational_center,rational_radius)),sort(integer_points_inside_dimond_3d(center,radius)));
+            assert_eq!(sort(integer_points_inside_l_infinity_sphere_rational(rational_center,rational_radius)),
+                       sort(integer_points_inside_l_infinity_sphere(center,radius)));
+        }
+        let negative = Rational::new_rational_from_integers(-1,2);
+        assert_eq!(integer_points_inside_circle_rational(origin,negative),vec![]);
+        assert_eq!(try_integer_points_inside_square_rational(origin,negative),Err(GeometryError::NegativeRadius));
+        assert_eq!(try_integer_points_inside_dimond_3d_rational(rational_center,Rational::new_rational(1 << 40,1)),Err(GeometryError::RangeTooLarge));
+        assert_eq!(try_integer_points_inside_sphere_rational(rational_center,Rational::new_rational(1 << 40,3)),Err(GeometryError::RangeTooLarge));
+        //squaring a radius this small overflows, but only the point the center rounds to can be that close.
+        let tiny = Rational::new_rational(1,1 << 40);
+        assert_eq!(try_integer_points_inside_circle_rational(origin,tiny),Ok(vec![Point2D{ x: 0, y: 0,}]));
+        assert_eq!(try_integer_points_inside_sphere_rational(rational_center,tiny),Ok(vec![center]));
+        let near = RationalPoint2D{ x: Rational::new_rational(1,1 << 41), y: Rational::new_rational(0,1),};
+        assert_eq!(try_integer_points_inside_circle_rational(near,tiny),Ok(vec![Point2D{ x: 0, y: 0,}]));
+        let near = RationalPoint2D{ x: Rational::new_rational(1,1 << 41), y: Rational::new_rational(1,1 << 41),};
+        assert_eq!(try_integer_points_inside_circle_rational(near,tiny),Err(GeometryError::Overflow));
+        let far = RationalPoint3D{ x: Rational::new_rational(1,3), y: Rational::new_rational(0,1), z: Rational::new_rational(2,1),};
+        assert_eq!(try_integer_points_inside_sphere_rational(far,tiny),Ok(vec![]));
+    }
+
+    //Returns true if every point can be reached from the first by repeatedly stepping to a neighbour.
+    fn connected<T: Copy + PartialEq>(points: &[T], neighbours: &dyn Fn(T, T) -> bool) -> bool{
+        let mut reached = vec![false; points.len()];
+        let mut stack = vec![0];
+        reached[0] = true;
+        while let Some(index) = stack.pop() {
+            for other in 0 .. points.len() {
+                if !reached[other] && neighbours(points[index],points[other]) {
+                    reached[other] = true;
+                    stack.push(other);
+                }
+            }
+        }
+        reached.iter().all(|reached| *reached)
+    }
+
+    #[test]
+    fn circle_boundaries(){
+        let center = Point2D{ x: 1, y: -3,};
+        for radius in 0 .. 15 {
+            let disk = integer_points_inside_circle(center,radius);
+            let outside = |x: isize, y: isize| !disk.contains(&Point2D{ x, y,});
+            let mut thin: Vec<Point2D> = Vec::new();
+            let mut thick: Vec<Point2D> = Vec::new();
+            for point in disk.iter() {
+                let Point2D{ x, y } = *point;
+                if outside(x + 1,y) || outside(x - 1,y) || outside(x,y + 1) || outside(x,y - 1) {
+                    thin.push(*point);
+                }
+                if (-1 ..= 1).any(|dx| (-1 ..= 1).any(|dy| outside(x + dx,y + dy))) {
+                    thick.push(*point);
+                }
+            }
+            let eight = integer_points_on_circle(center,radius,CircleConnectivity::EightConnected);
+            let four = integer_points_on_circle(center,radius,CircleConnectivity::FourConnected);
+            assert_eq!(sort(eight.clone()),sort(thin));
+            assert_eq!(sort(four.clone()),sort(thick));
+            assert!(connected(&eight,&|a: Point2D, b: Point2D| (a.x - b.x).abs() <= 1 && (a.y - b.y).abs() <= 1));
+            assert!(connected(&four,&|a: Point2D, b: Point2D| (a.x - b.x).abs() + (a.y - b.y).abs() == 1));
+            for inner in 0 .. radius + 2 {
+                let expected: Vec<Point2D> = disk.iter().cloned()
+                    .filter(|point| (point.x - center.x).pow(2) + (point.y - center.y).pow(2) >= inner*inner).collect();
+                assert_eq!(sort(integer_points_inside_annulus(center,inner,radius)),sort(expected));
+            }
+        }
+        assert_eq!(integer_points_on_circle(center,0,CircleConnectivity::FourConnected),vec![center]);
+        assert_eq!(try_integer_points_on_circle(center,-1,CircleConnectivity::EightConnected),Err(GeometryError::NegativeRadius));
+        assert_eq!(try_integer_points_inside_annulus(center,-1,3),Err(GeometryError::NegativeRadius));
+        assert_eq!(try_integer_points_inside_annulus(center,2,5),Ok(integer_points_inside_annulus(center,2,5)));
+    }
+
+    #[test]
+    fn sphere_boundaries(){
+        let center = Point3D{ x: 0, y: 2, z: -1,};
+        for radius in 0 .. 7 {
+            let ball = integer_points_inside_sphere(center,radius);
+            let outside = |x: isize, y: isize, z: isize| !ball.contains(&Point3D{ x, y, z,});
+            let mut thin: Vec<Point3D> = Vec::new();
+            let mut thick: Vec<Point3D> = Vec::new();
+            for point in ball.iter() {
+                let Point3D{ x, y, z } = *point;
+                if outside(x + 1,y,z) || outside(x - 1,y,z) || outside(x,y + 1,z) || outside(x,y - 1,z) || outside(x,y,z + 1) || outside(x,y,z - 1) {
+                    thin.push(*point);
+                }
+                if (-1 ..= 1).any(|dx| (-1 ..= 1).any(|dy| (-1 ..= 1).any(|dz| outside(x + dx,y + dy,z + dz)))) {
+                    thick.push(*point);
+                }
+            }
+            let twenty_six = integer_points_on_sphere(center,radius,SphereConnectivity::TwentySixConnected);
+            let six = integer_points_on_sphere(center,radius,SphereConnectivity::SixConnected);
+            assert_eq!(sort(twenty_six.clone()),sort(thin));
+            assert_eq!(sort(six.clone()),sort(thick));
+            assert!(connected(&twenty_six,&|a: Point3D, b: Point3D| (a.x - b.x).abs() <= 1 && (a.y - b.y).abs() <= 1 && (a.z - b.z).abs() <= 1));
+            assert!(connected(&six,&|a: Point3D, b: Point3D| (a.x - b.x).abs() + (a.y - b.y).abs() + (a.z - b.z).abs() == 1));
+            for inner in 0 .. radius + 2 {
+                let expected: Vec<Point3D> = ball.iter().cloned()
+                    .filter(|point| (point.x - center.x).pow(2) + (point.y - center.y).pow(2) + (point.z - center.z).pow(2) >= inner*inner).collect();
+                assert_eq!(sort(integer_points_inside_spherical_shell(center,inner,radius)),sort(expected));
+            }
+        }
+        assert_eq!(try_integer_points_on_sphere(center,3,SphereConnectivity::SixConnected),Ok(integer_points_on_sphere(center,3,SphereConnectivity::SixConnected)));
+        assert_eq!(try_integer_points_inside_spherical_shell(center,1,1 << 30),Err(GeometryError::RangeTooLarge));
+    }
+
+    #[test]
+    fn counting_points(){
+        let center2 = Point2D{ x: -4, y: 7,};
+        let center3 = Point3D{ x: 2, y: 0, z: -9,};
+        for radius in -2 .. 30 {
+            let points = if radius < 0 { 0 }else{ integer_points_inside_circle_slow(center2,radius).len() };
+            assert_eq!(count_integer_points_inside_circle(radius),points);
+            assert_eq!(count_integer_points_inside_dimond_2d(radius),integer_points_inside_dimond_2d(center2,radius).len());
+            assert_eq!(count_integer_points_inside_square(radius),integer_points_inside_square(center2,radius).len());
+        }
+        for radius in -2 .. 12 {
+            let points = if radius < 0 { 0 }else{ integer_points_inside_sphere(center3,radius).len() };
+            assert_eq!(count_integer_points_inside_sphere(radius),points);
+            assert_eq!(count_integer_points_inside_dimond_3d(radius),integer_points_inside_dimond_3d(center3,radius).len());
+            assert_eq!(count_integer_points_inside_l_infinity_sphere(radius),integer_points_inside_l_infinity_sphere(center3,radius).len());
+        }
+        //the sphere count sums a disk count per slice, check it over radii where the slices vary a lot, and against known values.
+        for radius in 12 .. 41 {
+            assert_eq!(count_integer_points_inside_sphere(radius),integer_points_inside_sphere(center3,radius).len());
+        }
+        let known = [1,7,33,123,257,515,925,1419,2109,3071,4169];
+        for (radius, points) in known.iter().enumerate() {
+            assert_eq!(count_integer_points_inside_sphere(radius as isize),*points);
+        }
+        //Gauss circle problem values for radius 10^k.
+        assert_eq!(count_integer_points_inside_circle(100),31417);
+        assert_eq!(count_integer_points_inside_circle(1000),3141549);
+        assert_eq!(count_integer_points_inside_circle(1_000_000),3141592649625);
+        assert_eq!(checked_count_integer_points_inside_circle(isize::MAX),Err(OverflowError));
+        assert_eq!(checked_count_integer_points_inside_square(isize::MAX),Err(OverflowError));
+        assert_eq!(checked_count_integer_points_inside_dimond_3d(1 << 21),Ok(((1 << 22) + 1)*(((1 << 43) + (1 << 22) + 3)/3)));
+        assert_eq!(checked_count_integer_points_inside_dimond_3d(1 << 40),Err(OverflowError));
+        assert_eq!(checked_count_integer_points_inside_l_infinity_sphere(1 << 20),Ok(((1 << 21) + 1usize).pow(3)));
+    }
+
+    #[test]
+    fn lp_balls(){
+        let center2 = Point2D{ x: 3, y: -1,};
+        let center3 = Point3D{ x: -2, y: 5, z: 1,};
+        for radius in 0 .. 12 {
+            assert_eq!(sort(integer_points_inside_lp_ball_2d(center2,radius,1)),sort(integer_points_inside_dimond_2d(center2,radius)));
+            assert_eq!(sort(integer_points_inside_lp_ball_2d(center2,radius,2)),sort(integer_points_inside_circle(center2,radius)));
+            assert_eq!(sort(integer_points_inside_lp_ball_3d(center3,radius,1)),sort(integer_points_inside_dimond_3d(center3,radius)));
+            assert_eq!(sort(integer_points_inside_lp_ball_3d(center3,radius,2)),sort(integer_points_inside_sphere(center3,radius)));
+            for p in 1 .. 8 {
+                let budget = (radius as i128).pow(p);
+                let expected: Vec<Point2D> = integer_points_inside_square(center2,radius).into_iter()
+                    .filter(|point| ((point.x - center2.x).abs() as i128).pow(p) + ((point.y - center2.y).abs() as i128).pow(p) <= budget).collect();
+                assert_eq!(sort(integer_points_inside_lp_ball_2d(center2,radius,p)),sort(expected));
+                let expected: Vec<Point3D> = integer_points_inside_l_infinity_sphere(center3,radius).into_iter()
+                    .filter(|point| ((point.x - center3.x).abs() as i128).pow(p) + ((point.y - center3.y).abs() as i128).pow(p) + ((point.z - center3.z).abs() as i128).pow(p) <= budget).collect();
+                assert_eq!(sort(integer_points_inside_lp_ball_3d(center3,radius,p)),sort(expected));
+            }
+        }
+        //Exact at the boundary: 3^3 + 4^3 + 5^3 = 6^3.
+        assert!(integer_points_inside_lp_ball_3d(center3,6,3).contains(&Point3D{ x: center3.x + 3, y: center3.y - 4, z: center3.z + 5,}));
+        assert!(!integer_points_inside_lp_ball_3d(center3,6,3).contains(&Point3D{ x: center3.x + 3, y: center3.y - 4, z: center3.z + 6,}));
+        //For large p the ball is the square of radius - 1 plus the four points on the axes at distance radius.
+        assert_eq!(integer_points_inside_lp_ball_2d(center2,1,100).len(),5);
+        assert_eq!(integer_points_inside_lp_ball_2d(center2,2,100).len(),13);
+        assert_eq!(integer_points_inside_lp_ball_2d(center2,-1,3),Vec::new());
+        assert_eq!(try_integer_points_inside_lp_ball_2d(center2,-1,3),Err(GeometryError::NegativeRadius));
+        assert_eq!(try_integer_points_inside_lp_ball_2d(center2,4,0),Err(GeometryError::InvalidExponent));
+        assert_eq!(try_integer_points_inside_lp_ball_3d(center3,4,0),Err(GeometryError::InvalidExponent));
+        //radius^p overflows a u128 for these, the powers are then compared with wide integers.
+        for base in [0u64,1,7,12,1 << 40,u64::MAX].iter() {
+            for exponent in 0 .. 4 {
+                let power = match (*base as u128).checked_pow(exponent) {
+                    Some(power) => power,
+                    None => continue,
+                };
+                let limbs: Vec<u64> = [power as u64,(power >> 64) as u64].iter().cloned().filter(|_| power > 0).collect();
+                let limbs = if limbs.last() == Some(&0) { limbs[.. 1].to_vec() } else { limbs };
+                assert_eq!(WidePower::power(*base,exponent),WidePower(limbs));
+            }
+        }
+        assert_eq!(WidePower::power(u64::MAX,2).add(&WidePower::power(2,65)),WidePower(vec![1,0,1]));
+        assert!(WidePower::power(2,128) > WidePower::power(u64::MAX,2));
+        assert_eq!(integer_points_inside_lp_ball_2d(center2,10,50).len(),19*19 + 4);
+        assert_eq!(integer_points_inside_lp_ball_3d(center3,10,50).len(),19*19*19 + 6);
+        assert_eq!(integer_points_inside_lp_ball_2d(center2,3,1000).len(),5*5 + 4);
+        for (radius, p) in [(12,36),(12,39),(12,43),(100,20)].iter() {
+            let budget = WidePower::power(*radius as u64,*p);
+            let expected: Vec<Point2D> = integer_points_inside_square(center2,*radius).into_iter()
+                .filter(|point| WidePower::power((point.x - center2.x).unsigned_abs() as u64,*p).add(&WidePower::power((point.y - center2.y).unsigned_abs() as u64,*p)) <= budget).collect();
+            assert_eq!(try_integer_points_inside_lp_ball_2d(center2,*radius,*p).map(sort),Ok(sort(expected)));
+        }
+    }
+}
@@ -0,0 +1,665 @@
+//Purpose: Contains an arbitrary precision rational struct, for computations whose numerators and denominators do not fit in a usize.
+//         Only compiled with the big-rational feature.
+
+use std::cmp;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Add;
+use std::ops::Sub;
+use std::ops::Mul;
+use std::ops::Div;
+use std::ops::Neg;
+
+use crate::rational::{decompose_f64, Rational, RationalNumber};
+
+//BigUint
+//Purpose:
+//    A non-negative integer of any size, stored as base 2^32 digits with the least significant digit first.
+//Notes:
+//    The most significant digit is never zero, so zero has no digits and every number has exactly one representation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BigUint {
+    digits: Vec<u32>,
+}
+
+impl BigUint {
+    fn zero() -> BigUint{
+        BigUint{ digits: Vec::new() }
+    }
+
+    fn from_u128(mut number: u128) -> BigUint{
+        let mut digits = Vec::new();
+        while number != 0 {
+            digits.push(number as u32);
+            number >>= 32;
+        }
+        BigUint{ digits }
+    }
+
+    fn to_u128(&self) -> Option<u128>{
+        if self.digits.len() > 4 {
+            return None;
+        }
+        Some(self.digits.iter().rev().fold(0, |number, digit| (number << 32) | *digit as u128))
+    }
+
+    fn is_zero(&self) -> bool{
+        self.digits.is_empty()
+    }
+
+    fn is_one(&self) -> bool{
+        self.digits == [1]
+    }
+
+    //removes leading zero digits.
+    fn normalize(&mut self){
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+    }
+
+    fn add(&self, rhs: &BigUint) -> BigUint{
+        let mut digits = Vec::with_capacity(cmp::max(self.digits.len(),rhs.digits.len()) + 1);
+        let mut carry = 0;
+        for index in 0 .. cmp::max(self.digits.len(),rhs.digits.len()) {
+            let sum = *self.digits.get(index).unwrap_or(&0) as u64 + *rhs.digits.get(index).unwrap_or(&0) as u64 + carry;
+            digits.push(sum as u32);
+            carry = sum >> 32;
+        }
+        if carry != 0 {
+            digits.push(carry as u32);
+        }
+        BigUint{ digits }
+    }
+
+    //Pre-conditions: rhs is at most self.
+    fn sub(&self, rhs: &BigUint) -> BigUint{
+        let mut difference = self.clone();
+        difference.sub_assign(rhs);
+        difference
+    }
+
+    //Pre-conditions: rhs is at most self.
+    fn sub_assign(&mut self, rhs: &BigUint){
+        let mut borrow = 0;
+        for index in 0 .. self.digits.len() {
+            let subtrahend = *rhs.digits.get(index).unwrap_or(&0) as i64 + borrow;
+            let mut digit = self.digits[index] as i64 - subtrahend;
+            if digit < 0 {
+                digit += 1 << 32;
+                borrow = 1;
+            }else{
+                borrow = 0;
+            }
+            self.digits[index] = digit as u32;
+            if borrow == 0 && index >= rhs.digits.len() {
+                break;
+            }
+        }
+        self.normalize();
+    }
+
+    fn mul(&self, rhs: &BigUint) -> BigUint{
+        if self.is_zero() || rhs.is_zero() {
+            return BigUint::zero();
+        }
+        let mut digits = vec![0u32; self.digits.len() + rhs.digits.len()];
+        for (i, left) in self.digits.iter().enumerate() {
+            let mut carry = 0;
+            for (j, right) in rhs.digits.iter().enumerate() {
+                let product = *left as u64 * *right as u64 + digits[i + j] as u64 + carry;
+                digits[i + j] = product as u32;
+                carry = product >> 32;
+            }
+            digits[i + rhs.digits.len()] = carry as u32;
+        }
+        let mut product = BigUint{ digits };
+        product.normalize();
+        product
+    }
+
+    fn shl(&self, bits: usize) -> BigUint{
+        if self.is_zero() {
+            return BigUint::zero();
+        }
+        let mut digits = vec![0u32; bits / 32];
+        let shift = bits % 32;
+        if shift == 0 {
+            digits.extend_from_slice(&self.digits);
+        }else{
+            let mut carry = 0;
+            for digit in &self.digits {
+                digits.push((digit << shift) | carry);
+                carry = digit >> (32 - shift);
+            }
+            if carry != 0 {
+                digits.push(carry);
+            }
+        }
+        BigUint{ digits }
+    }
+
+    fn shr(&self, bits: usize) -> BigUint{
+        if bits / 32 >= self.digits.len() {
+            return BigUint::zero();
+        }
+        let shift = bits % 32;
+        let high = &self.digits[bits / 32 ..];
+        let mut digits = Vec::with_capacity(high.len());
+        for index in 0 .. high.len() {
+            let mut digit = high[index] >> shift;
+            if shift != 0 && index + 1 < high.len() {
+                digit |= high[index + 1] << (32 - shift);
+            }
+            digits.push(digit);
+        }
+        let mut quotient = BigUint{ digits };
+        quotient.normalize();
+        quotient
+    }
+
+    //Pre-conditions: self is not zero.
+    fn trailing_zeros(&self) -> usize{
+        let mut zeros = 0;
+        for digit in &self.digits {
+            if *digit != 0 {
+                return zeros + digit.trailing_zeros() as usize;
+            }
+            zeros += 32;
+        }
+        zeros
+    }
+
+    fn bits(&self) -> usize{
+        match self.digits.last() {
+            Some(digit) => 32*self.digits.len() - digit.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    //Returns the quotient and remainder of self divided by rhs.
+    //Pre-conditions: rhs is not zero.
+    fn div_rem(&self, rhs: &BigUint) -> (BigUint, BigUint){
+        if *self < *rhs {
+            return (BigUint::zero(), self.clone());
+        }
+        if rhs.digits.len() == 1 { //short division, one digit at a time.
+            let divisor = rhs.digits[0] as u64;
+            let mut digits = vec![0u32; self.digits.len()];
+            let mut remainder = 0;
+            for index in (0 .. self.digits.len()).rev() {
+                let current = (remainder << 32) | self.digits[index] as u64;
+                digits[index] = (current / divisor) as u32;
+                remainder = current % divisor;
+            }
+            let mut quotient = BigUint{ digits };
+            quotient.normalize();
+            return (quotient, BigUint::from_u128(remainder as u128));
+        }
+        //long division, one bit at a time.
+        let mut digits = vec![0u32; self.digits.len()];
+        let mut remainder = BigUint::zero();
+        for bit in (0 .. self.bits()).rev() {
+            remainder = remainder.shl(1);
+            if (self.digits[bit / 32] >> (bit % 32)) & 1 == 1 {
+                if remainder.is_zero() {
+                    remainder.digits.push(1);
+                }else{
+                    remainder.digits[0] |= 1;
+                }
+            }
+            if remainder >= *rhs {
+                remainder.sub_assign(rhs);
+                digits[bit / 32] |= 1 << (bit % 32);
+            }
+        }
+        let mut quotient = BigUint{ digits };
+        quotient.normalize();
+        (quotient, remainder)
+    }
+
+    //Returns the gcd of two numbers, using the binary gcd algorithm.
+    //Pre-conditions: the numbers are not both zero.
+    fn gcd(&self, rhs: &BigUint) -> BigUint{
+        if self.is_zero() {
+            return rhs.clone();
+        }else if rhs.is_zero() {
+            return self.clone();
+        }
+        let shift = cmp::min(self.trailing_zeros(),rhs.trailing_zeros());
+        let mut first = self.shr(self.trailing_zeros());
+        let mut second = rhs.clone();
+        loop {
+            second = second.shr(second.trailing_zeros()); //both first and second are now odd.
+            if first > second {
+                std::mem::swap(&mut first,&mut second);
+            }
+            second.sub_assign(&first);
+            if second.is_zero() {
+                return first.shl(shift);
+            }
+        }
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, rhs: &BigUint) -> Ordering {
+        self.digits.len().cmp(&rhs.digits.len()).then_with(|| self.digits.iter().rev().cmp(rhs.digits.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, rhs: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        //split into base 10^9 chunks, least significant first.
+        let billion = BigUint::from_u128(1_000_000_000);
+        let mut chunks = Vec::new();
+        let mut rest = self.clone();
+        while !rest.is_zero() {
+            let (quotient, remainder) = rest.div_rem(&billion);
+            chunks.push(remainder.to_u128().unwrap_or(0));
+            rest = quotient;
+        }
+        write!(f, "{}", chunks[chunks.len() - 1])?;
+        for chunk in chunks.iter().rev().skip(1) {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+//BigRational
+//Purpose:
+//    A rational number whose numerator and denominator can be any size, so computations with it never overflow.
+//    It implements RationalNumber, so it can be used in place of Rational in the line algorithms.
+//Notes:
+//    Like Rational it is always kept in lowest form, with zero having a positive sign and a denominator of 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigRational {
+    sign: bool, //is true if a negative number, false if zero or positive
+    numerator: BigUint,
+    denominator: BigUint,
+}
+
+impl BigRational {
+    //Reduces numerator/denominator to lowest form.
+    //Pre-conditions: denominator is not zero.
+    fn new_reduced(sign: bool, numerator: BigUint, denominator: BigUint) -> BigRational{
+        if numerator.is_zero() {
+            return BigRational{ sign: false, numerator, denominator: BigUint::from_u128(1),};
+        }
+        let common = numerator.gcd(&denominator);
+        if common.is_one() {
+            BigRational{ sign, numerator, denominator,}
+        }else{
+            BigRational{ sign, numerator: numerator.div_rem(&common).0, denominator: denominator.div_rem(&common).0,}
+        }
+    }
+
+    pub fn new_big_rational_from_integers(numerator: isize, denominator: isize) -> BigRational{
+        if denominator == 0 {
+            panic!("Attempted creation of a rational with zero denominator!");
+        }
+        BigRational::new_reduced((numerator < 0) ^ (denominator < 0),
+                                 BigUint::from_u128(numerator.unsigned_abs() as u128),
+                                 BigUint::from_u128(denominator.unsigned_abs() as u128))
+    }
+
+    pub fn new_big_rational_from_integer(integer: isize) -> BigRational{
+        BigRational::new_big_rational_from_integers(integer,1)
+    }
+
+    pub fn is_neg(&self) -> bool{
+        self.sign
+    }
+
+    //from_f64_exact
+    //Purpose:
+    //    Returns the rational equal to the given f64.
+    //Pre-conditions:
+    //    None.
+    //Notes:
+    //    Returns None if x is infinite or NaN. Unlike Rational::from_f64_exact every finite f64 can be converted.
+    pub fn from_f64_exact(x: f64) -> Option<BigRational>{
+        let (sign, mantissa, exponent) = decompose_f64(x)?;
+        let mantissa = BigUint::from_u128(mantissa as u128);
+        let one = BigUint::from_u128(1);
+        if exponent >= 0 {
+            Some(BigRational::new_reduced(sign,mantissa.shl(exponent as usize),one))
+        }else{
+            Some(BigRational::new_reduced(sign,mantissa,one.shl(-exponent as usize)))
+        }
+    }
+
+    //to_rational
+    //Purpose:
+    //    Returns the current rational as a Rational, or None if its numerator or denominator does not fit in a usize.
+    //Pre-conditions:
+    //    None.
+    pub fn to_rational(&self) -> Option<Rational>{
+        let numerator = usize::try_from(self.numerator.to_u128()?).ok()?;
+        let denominator = usize::try_from(self.denominator.to_u128()?).ok()?;
+        Some(Rational::new_signed_rational(numerator,denominator,self.sign))
+    }
+
+    //checked_floor
+    //Purpose:
+    //    Returns the largest integer less than or equal to the current rational, or None if it does not fit in an isize.
+    //Pre-conditions:
+    //    None.
+    pub fn checked_floor(&self) -> Option<isize>{
+        self.rounded(!self.sign)
+    }
+
+    //checked_ceil
+    //Purpose:
+    //    Returns the smallest integer greater than or equal to the current rational, or None if it does not fit in an isize.
+    //Pre-conditions:
+    //    None.
+    pub fn checked_ceil(&self) -> Option<isize>{
+        self.rounded(self.sign)
+    }
+
+    //Rounds towards zero if truncate is true and away from zero otherwise, returning None if the result does not fit in an isize.
+    fn rounded(&self, truncate: bool) -> Option<isize>{
+        let (whole, remainder) = self.numerator.div_rem(&self.denominator);
+        let mut whole = i128::try_from(whole.to_u128()?).ok()?;
+        if !truncate && !remainder.is_zero() {
+            whole += 1;
+        }
+        isize::try_from(if self.sign { -whole } else { whole }).ok()
+    }
+
+    fn add_ref(&self, rhs: &BigRational) -> BigRational{
+        let left = self.numerator.mul(&rhs.denominator);
+        let right = rhs.numerator.mul(&self.denominator);
+        let denominator = self.denominator.mul(&rhs.denominator);
+        if self.sign == rhs.sign {
+            BigRational::new_reduced(self.sign,left.add(&right),denominator)
+        }else if left >= right {
+            BigRational::new_reduced(self.sign,left.sub(&right),denominator)
+        }else{
+            BigRational::new_reduced(rhs.sign,right.sub(&left),denominator)
+        }
+    }
+
+    fn mul_ref(&self, rhs: &BigRational) -> BigRational{
+        BigRational::new_reduced(self.sign ^ rhs.sign,self.numerator.mul(&rhs.numerator),self.denominator.mul(&rhs.denominator))
+    }
+
+    //Pre-conditions: rhs is not zero.
+    fn div_ref(&self, rhs: &BigRational) -> BigRational{
+        BigRational::new_reduced(self.sign ^ rhs.sign,self.numerator.mul(&rhs.denominator),self.denominator.mul(&rhs.numerator))
+    }
+}
+
+impl From<Rational> for BigRational {
+    fn from(rational: Rational) -> BigRational {
+        BigRational{
+            sign: rational.is_neg(),
+            numerator: BigUint::from_u128(rational.numerator() as u128),
+            denominator: BigUint::from_u128(rational.denominator() as u128),
+        }
+    }
+}
+
+impl fmt::Display for BigRational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.sign {
+            write!(f, "-")?;
+        }
+        if self.denominator.is_one() {
+            write!(f, "{}", self.numerator)
+        }else{
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl Ord for BigRational {
+    fn cmp(&self, rhs: &BigRational) -> Ordering {
+        match (self.sign, rhs.sign) {
+            (true,false) => Ordering::Less,
+            (false,true) => Ordering::Greater,
+            (false,false) => self.numerator.mul(&rhs.denominator).cmp(&rhs.numerator.mul(&self.denominator)),
+            (true,true) => rhs.numerator.mul(&self.denominator).cmp(&self.numerator.mul(&rhs.denominator)),
+        }
+    }
+}
+
+impl PartialOrd for BigRational {
+    fn partial_cmp(&self, rhs: &BigRational) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl Add<BigRational> for BigRational {
+    type Output = BigRational;
+
+    fn add(self, rhs: BigRational) -> BigRational {
+        self.add_ref(&rhs)
+    }
+}
+
+impl Sub<BigRational> for BigRational {
+    type Output = BigRational;
+
+    fn sub(self, rhs: BigRational) -> BigRational {
+        self.add_ref(&-rhs)
+    }
+}
+
+impl Mul<BigRational> for BigRational {
+    type Output = BigRational;
+
+    fn mul(self, rhs: BigRational) -> BigRational {
+        self.mul_ref(&rhs)
+    }
+}
+
+impl Div<BigRational> for BigRational {
+    type Output = BigRational;
+
+    fn div(self, rhs: BigRational) -> BigRational {
+        if rhs.numerator.is_zero() {
+            panic!("Attempted division by zero (Rationals)!");
+        }
+        self.div_ref(&rhs)
+    }
+}
+
+impl Neg for BigRational {
+    type Output = BigRational;
+
+    fn neg(self) -> BigRational {
+        BigRational{
+            sign: !self.sign && !self.numerator.is_zero(), //zero always has sign being false
+            numerator: self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl RationalNumber for BigRational {
+    fn from_integer(integer: isize) -> BigRational {
+        BigRational::new_big_rational_from_integer(integer)
+    }
+
+    fn from_integers(numerator: isize, denominator: isize) -> BigRational {
+        BigRational::new_big_rational_from_integers(numerator,denominator)
+    }
+
+    fn checked_add(&self, rhs: &BigRational) -> Option<BigRational> {
+        Some(self.add_ref(rhs))
+    }
+
+    fn checked_sub(&self, rhs: &BigRational) -> Option<BigRational> {
+        Some(self.add_ref(&-rhs.clone()))
+    }
+
+    fn checked_mul(&self, rhs: &BigRational) -> Option<BigRational> {
+        Some(self.mul_ref(rhs))
+    }
+
+    fn checked_div(&self, rhs: &BigRational) -> Option<BigRational> {
+        if rhs.numerator.is_zero() {
+            return None;
+        }
+        Some(self.div_ref(rhs))
+    }
+
+    fn checked_floor(&self) -> Option<isize> {
+        BigRational::checked_floor(self)
+    }
+
+    fn checked_ceil(&self) -> Option<isize> {
+        BigRational::checked_ceil(self)
+    }
+
+    fn is_integer(&self) -> bool {
+        self.denominator.is_one()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line::*;
+    use crate::point::{Point2D, Point3D, RationalPoint2D, RationalPoint3D};
+    use crate::traversal::voxel_crossings_on_line_segment_rational_3d;
+
+    fn big(numerator: isize, denominator: isize) -> BigRational{
+        BigRational::new_big_rational_from_integers(numerator,denominator)
+    }
+
+    #[test]
+    fn big_uint_arithmetic(){
+        let values: [u128; 8] = [0, 1, 7, (1 << 32) - 1, 1 << 32, 0xdead_beef_1234_5678, (1 << 64) + 3, u64::MAX as u128];
+        for a in values.iter() {
+            for b in values.iter() {
+                let left = BigUint::from_u128(*a);
+                let right = BigUint::from_u128(*b);
+                assert_eq!(left.add(&right).to_u128(), a.checked_add(*b));
+                assert_eq!(left.mul(&right).to_u128(), a.checked_mul(*b));
+                assert_eq!(left.cmp(&right), a.cmp(b));
+                if a >= b {
+                    assert_eq!(left.sub(&right).to_u128(), Some(a - b));
+                }
+                if *b != 0 {
+                    let (quotient, remainder) = left.div_rem(&right);
+                    assert_eq!((quotient.to_u128(),remainder.to_u128()), (Some(a / b),Some(a % b)));
+                }
+                if *a != 0 || *b != 0 {
+                    let mut expected = (*a, *b);
+                    while expected.1 != 0 {
+                        expected = (expected.1, expected.0 % expected.1);
+                    }
+                    assert_eq!(left.gcd(&right).to_u128(), Some(expected.0));
+                }
+            }
+        }
+        let large = BigUint::from_u128(u128::MAX).mul(&BigUint::from_u128(u128::MAX));
+        assert_eq!(large.to_u128(), None);
+        assert_eq!(large.div_rem(&BigUint::from_u128(u128::MAX)).0.to_u128(), Some(u128::MAX));
+        assert_eq!(large.shr(128).shl(128).bits(), 256);
+        assert_eq!(format!("{}",BigUint::from_u128(1_000_000_000_000_000_007)), "1000000000000000007");
+    }
+
+    #[test]
+    fn big_rational_operations(){
+        assert_eq!(big(2,4), big(-1,-2));
+        assert_eq!(big(1,2) + big(1,3), big(5,6));
+        assert_eq!(big(1,2) - big(5,6), big(-1,3));
+        assert_eq!(big(-2,3) * big(9,4), big(-3,2));
+        assert_eq!(big(-2,3) / big(-4,9), big(3,2));
+        assert_eq!(big(3,5) - big(3,5), big(0,1));
+        assert_eq!(-big(0,1), big(0,1));
+        assert!(big(-1,2) < big(-1,3));
+        assert!(big(1,3) < big(1,2));
+        assert_eq!(big(7,2).checked_floor(), Some(3));
+        assert_eq!(big(-7,2).checked_floor(), Some(-4));
+        assert_eq!(big(-7,2).checked_ceil(), Some(-3));
+        assert_eq!(big(isize::MIN,1).checked_floor(), Some(isize::MIN));
+        assert_eq!(big(isize::MIN,1).checked_ceil(), Some(isize::MIN));
+        assert_eq!((big(isize::MAX,1) + big(1,1)).checked_floor(), None);
+        assert_eq!(format!("{}",big(-6,4)), "-3/2");
+        assert_eq!(format!("{}",big(isize::MAX,1) * big(isize::MAX,1)), "85070591730234615847396907784232501249");
+        assert_eq!(BigRational::from(Rational::new_rational(3,4)), big(3,4));
+        assert_eq!(big(-3,4).to_rational(), Some(Rational::new_signed_rational(3,4,true)));
+        assert_eq!((big(isize::MAX,1) * big(4,1)).to_rational(), None);
+        assert_eq!(BigRational::from_f64_exact(-0.375), Some(big(-3,8)));
+        assert_eq!(BigRational::from_f64_exact(f64::NAN), None);
+        assert_eq!(format!("{}",BigRational::from_f64_exact(f64::MIN_POSITIVE).unwrap()).len(), 2 + 308);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted division by zero (Rationals)!")]
+    fn big_rational_division_by_zero(){
+        let _ = big(1,2) / big(0,1);
+    }
+
+    #[test]
+    fn big_rational_lines_do_not_overflow(){
+        let point1 = RationalPoint2D{ x: big(1,(1 << 33) + 1), y: big(0,1),};
+        let point2 = RationalPoint2D{ x: big(1,(1 << 33) - 1), y: big(1,1),};
+        assert_eq!(checked_l_infinity_line_rational_endpoints(point1,point2),
+                   Ok(vec![Point2D{ x: 0, y: 0,},Point2D{ x: 0, y: 1,}]));
+        let point1 = RationalPoint3D{ x: big(0,1), y: big(0,1), z: big(1,(1 << 33) + 1),};
+        let point2 = RationalPoint3D{ x: big(1,1), y: big(0,1), z: big(1,(1 << 33) - 1),};
+        assert_eq!(checked_l_infinity_line_rational_endpoints_3d(point1,point2),
+                   Ok(vec![Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 1, y: 0, z: 0,}]));
+        assert_eq!(checked_integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 0, y: 0,},Point2D{ x: 3, y: 1,},big(1,isize::MAX)),
+                   Ok(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 0, y: 0,},Point2D{ x: 3, y: 1,},Rational::new_rational(1,1 << 20))));
+    }
+
+    #[test]
+    fn big_rational_lines_match_rational_lines(){
+        let values: Vec<(isize, isize)> = vec![(-3,2),(-1,1),(-1,3),(0,1),(1,2),(2,3),(5,4),(2,1)];
+        let rational = |(numerator, denominator): (isize, isize)| Rational::new_rational_from_integers(numerator,denominator);
+        let big_rational = |(numerator, denominator): (isize, isize)| big(numerator,denominator);
+        for a in values.iter() {
+            for b in values.iter() {
+                for c in values.iter() {
+                    for d in values.iter() {
+                        let point1 = RationalPoint2D{ x: rational(*a), y: rational(*b),};
+                        let point2 = RationalPoint2D{ x: rational(*c), y: rational(*d),};
+                        let big_point1 = RationalPoint2D{ x: big_rational(*a), y: big_rational(*b),};
+                        let big_point2 = RationalPoint2D{ x: big_rational(*c), y: big_rational(*d),};
+                        assert_eq!(l_infinity_line_rational_endpoints(big_point1.clone(),big_point2.clone()),
+                                   l_infinity_line_rational_endpoints(point1,point2));
+                        assert_eq!(l_infinity_line_rational_endpoints_ordered(big_point1,big_point2),
+                                   l_infinity_line_rational_endpoints_ordered(point1,point2));
+                        let point1 = RationalPoint3D{ x: rational(*a), y: rational(*b), z: rational(*c),};
+                        let point2 = RationalPoint3D{ x: rational(*d), y: rational(*a), z: rational(*b),};
+                        let big_point1 = RationalPoint3D{ x: big_rational(*a), y: big_rational(*b), z: big_rational(*c),};
+                        let big_point2 = RationalPoint3D{ x: big_rational(*d), y: big_rational(*a), z: big_rational(*b),};
+                        assert_eq!(l_infinity_line_rational_endpoints_3d_ordered(big_point1.clone(),big_point2.clone()),
+                                   l_infinity_line_rational_endpoints_3d_ordered(point1,point2));
+                        let crossings = voxel_crossings_on_line_segment_rational_3d(point1,point2);
+                        let big_crossings = voxel_crossings_on_line_segment_rational_3d(big_point1,big_point2);
+                        assert_eq!(big_crossings.len(), crossings.len());
+                        for (big_crossing, crossing) in big_crossings.iter().zip(crossings.iter()) {
+                            assert_eq!((big_crossing.cell,big_crossing.entry_face), (crossing.cell,crossing.entry_face));
+                            assert_eq!(big_crossing.entry.to_rational(), Some(crossing.entry));
+                            assert_eq!(big_crossing.exit.to_rational(), Some(crossing.exit));
+                        }
+                    }
+                }
+            }
+        }
+        for numerator in -4 .. 5 {
+            let radius = Rational::new_rational_from_integers(numerator,4);
+            assert_eq!(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: -2, y: 1,},Point2D{ x: 5, y: 3,},big(numerator,4)),
+                       integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: -2, y: 1,},Point2D{ x: 5, y: 3,},radius));
+        }
+    }
+}
@@ -0,0 +1,652 @@
+//Purpose: Contains functions for finding the integer points inside and on the boundary of ellipses and ellipsoids,
+//         axis-aligned or rotated, with integer or rational centers and semi-axes.
+//Notes:
+//    A rotated ellipse (or ellipsoid) is given by integer directions for its axes, so every computation stays exact.
+//    Every row of integer points along the x axis is found by solving a quadratic exactly, as is done for the rational balls.
+
+use std::cmp;
+use std::convert::TryFrom;
+
+use crate::ball::{l_2_range, CircleConnectivity, SphereConnectivity};
+use crate::error::{check_cell_count, GeometryError};
+use crate::point::{Point2D, Point3D, RationalPoint2D, RationalPoint3D};
+use crate::rational::Rational;
+
+//Ellipsoid
+//Purpose:
+//    The points p with sum_i ((p - center).directions[i])^2*weights[i] <= 1, where weights[i] = 1/(semi_axes[i]^2*|directions[i]|^2).
+//    The directions are non-zero and orthogonal, so semi_axes[i] is the distance from the center to the boundary along directions[i].
+//    A zero semi-axis has no weight, instead (p - center).directions[i] must be 0, which flattens the ellipsoid down to a lower dimension.
+struct Ellipsoid {
+    center: Vec<Rational>,
+    directions: Vec<Vec<isize>>,
+    semi_axes: Vec<Rational>,
+    lengths: Vec<Rational>,
+    weights: Vec<Option<Rational>>,
+}
+
+//Fill
+//Purpose:
+//    Which points of an ellipsoid to return.
+//    Solid: all of them.
+//    Thin: the points with one of their face neighbours outside (8-connected in 2D, 26-connected in 3D).
+//    Thick: the points with any of their neighbours outside (4-connected in 2D, 6-connected in 3D).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Fill {
+    Solid,
+    Thin,
+    Thick,
+}
+
+//dot
+//Purpose:
+//    Returns the dot product of two integer vectors, or None if it does not fit in an isize.
+//Pre-conditions:
+//    The vectors have the same length.
+fn dot(left: &[isize], right: &[isize]) -> Option<isize>{
+    let sum: i128 = left.iter().zip(right.iter()).map(|(a, b)| *a as i128 * *b as i128).sum();
+    isize::try_from(sum).ok()
+}
+
+impl Ellipsoid {
+    //new
+    //Purpose:
+    //    Returns the ellipsoid with the given center, axis directions and semi-axes,
+    //    or a GeometryError if a semi-axis is negative (NegativeRadius), the directions are zero
+    //    or not orthogonal (InvalidAxes), or a computation overflows.
+    //Pre-conditions:
+    //    There are as many directions and semi-axes as coordinates in the center, and every direction has that many coordinates.
+    fn new(center: Vec<Rational>, directions: Vec<Vec<isize>>, semi_axes: Vec<Rational>) -> Result<Ellipsoid, GeometryError>{
+        let zero = Rational::new_rational_from_integer(0);
+        if semi_axes.iter().any(|semi_axis| semi_axis.is_neg()) {
+            return Err(GeometryError::NegativeRadius);
+        }
+        let mut lengths: Vec<Rational> = Vec::new();
+        let mut weights: Vec<Option<Rational>> = Vec::new();
+        for (index, direction) in directions.iter().enumerate() {
+            let length = dot(direction,direction).ok_or(GeometryError::Overflow)?;
+            if length == 0 {
+                return Err(GeometryError::InvalidAxes);
+            }
+            for other in directions[.. index].iter() {
+                if dot(direction,other) != Some(0) {
+                    return Err(GeometryError::InvalidAxes);
+                }
+            }
+            let length = Rational::new_rational_from_integer(length);
+            if semi_axes[index] == zero {
+                weights.push(None);
+            }else{
+                let scale = semi_axes[index].checked_mul(semi_axes[index]).and_then(|square| square.checked_mul(length)).ok_or(GeometryError::Overflow)?;
+                weights.push(Some(Rational::new_rational_from_integer(1).try_div(scale)?));
+            }
+            lengths.push(length);
+        }
+        Ok(Ellipsoid{ center, directions, semi_axes, lengths, weights,})
+    }
+
+    //extent_range
+    //Purpose:
+    //    Returns the smallest and largest integer values of the given coordinate over the ellipsoid, or None if a computation overflows.
+    //Pre-conditions:
+    //    coordinate is less than the dimension.
+    //Notes:
+    //    The ellipsoid reaches sqrt(sum_i semi_axes[i]^2*directions[i][coordinate]^2/|directions[i]|^2) from the center along a coordinate axis.
+    fn extent_range(&self, coordinate: usize) -> Option<(isize, isize)>{
+        let mut extent_squared = Rational::new_rational_from_integer(0);
+        for index in 0 .. self.directions.len() {
+            let component = Rational::new_rational_from_integer(self.directions[index][coordinate]);
+            let term = self.semi_axes[index].checked_mul(component)?;
+            extent_squared = extent_squared.checked_add(term.checked_mul(term)?.checked_div(self.lengths[index])?)?;
+        }
+        l_2_range(self.center[coordinate],extent_squared)
+    }
+
+    //row
+    //Purpose:
+    //    Returns the smallest and largest x such that (x, rest...) is inside the ellipsoid, or None if a computation overflows.
+    //    The smallest is greater than the largest when there are none.
+    //Pre-conditions:
+    //    rest has one coordinate less than the dimension.
+    //Notes:
+    //    In X = x - center[0] the ellipsoid is alpha*X^2 + 2*beta*X + gamma <= 0,
+    //    that is (X + beta/alpha)^2 <= (beta/alpha)^2 - gamma/alpha.
+    //    A zero semi-axis whose direction has a non-zero x coordinate fixes X, which then only has to be checked.
+    //    Otherwise alpha is positive, since the directions with a weight span the directions orthogonal to those without one.
+    fn row(&self, rest: &[isize]) -> Option<(isize, isize)>{
+        let zero = Rational::new_rational_from_integer(0);
+        let mut alpha = zero;
+        let mut beta = zero;
+        let mut gamma = Rational::new_rational_from_integer(-1);
+        let mut fixed: Option<Rational> = None;
+        for index in 0 .. self.directions.len() {
+            let direction = &self.directions[index];
+            let mut along = zero;
+            for coordinate in 1 .. direction.len() {
+                let offset = Rational::new_rational_from_integer(rest[coordinate - 1]).checked_sub(self.center[coordinate])?;
+                along = along.checked_add(offset.checked_mul(Rational::new_rational_from_integer(direction[coordinate]))?)?;
+            }
+            let first = Rational::new_rational_from_integer(direction[0]);
+            match self.weights[index] {
+                Some(weight) => {
+                    alpha = alpha.checked_add(first.checked_mul(first)?.checked_mul(weight)?)?;
+                    beta = beta.checked_add(first.checked_mul(along)?.checked_mul(weight)?)?;
+                    gamma = gamma.checked_add(along.checked_mul(along)?.checked_mul(weight)?)?;
+                },
+                //need first*X + along = 0.
+                None if first == zero => {
+                    if along != zero {
+                        return Some((1, 0));
+                    }
+                },
+                None => {
+                    let value = -along.checked_div(first)?;
+                    if fixed.is_some_and(|fixed| fixed != value) {
+                        return Some((1, 0));
+                    }
+                    fixed = Some(value);
+                },
+            }
+        }
+        if let Some(offset) = fixed {
+            let x_cord = self.center[0].checked_add(offset)?;
+            let value = alpha.checked_mul(offset)?.checked_add(beta.checked_mul(Rational::new_rational_from_integer(2))?)?.checked_mul(offset)?.checked_add(gamma)?;
+            if value > zero || x_cord.checked_floor()? != x_cord.checked_ceil()? {
+                return Some((1, 0));
+            }
+            let x_cord = x_cord.checked_floor()?;
+            return Some((x_cord, x_cord));
+        }
+        let shift = beta.checked_div(alpha)?;
+        l_2_range(self.center[0].checked_sub(shift)?,shift.checked_mul(shift)?.checked_sub(gamma.checked_div(alpha)?)?)
+    }
+}
+
+//rest_tuples
+//Purpose:
+//    Returns every integer tuple inside the box with the given ranges, in lexicographic order.
+//Pre-conditions:
+//    None.
+fn rest_tuples(ranges: &[(isize, isize)]) -> Vec<Vec<isize>>{
+    let mut tuples: Vec<Vec<isize>> = vec![Vec::new()];
+    for (low, high) in ranges {
+        let mut longer: Vec<Vec<isize>> = Vec::new();
+        for tuple in tuples {
+            for coordinate in *low ..= *high {
+                let mut next = tuple.clone();
+                next.push(coordinate);
+                longer.push(next);
+            }
+        }
+        tuples = longer;
+    }
+    tuples
+}
+
+//ellipsoid_points
+//Purpose:
+//    Calls emit with the coordinates of the points of the ellipsoid selected by fill,
+//    or returns a GeometryError if the points do not fit in a Vec of T or a computation overflows.
+//Pre-conditions:
+//    None.
+//Notes:
+//    For the boundaries every row is compared with its neighbouring rows, the same way integer_points_on_circle does,
+//    where a point has a neighbour outside when it is at most max(l') (or at least min(h')) over the neighbouring rows [l', h'],
+//    shifted by one for the rows that only share a face with it.
+fn ellipsoid_points<T>(ellipsoid: &Ellipsoid, fill: Fill, emit: &mut dyn FnMut(&[isize])) -> Result<(), GeometryError>{
+    let dimension = ellipsoid.center.len();
+    let mut ranges: Vec<(isize, isize)> = Vec::new();
+    for coordinate in 0 .. dimension {
+        ranges.push(ellipsoid.extent_range(coordinate).ok_or(GeometryError::Overflow)?);
+    }
+    let mut count: Option<usize> = Some(1);
+    for (low, high) in ranges.iter() {
+        count = count.and_then(|count| count.checked_mul(cmp::max(high.checked_sub(*low)?.checked_add(1)?,0) as usize));
+    }
+    check_cell_count::<T>(count)?;
+    let tuples = rest_tuples(&ranges[1 ..]);
+    let mut rows: Vec<(isize, isize)> = Vec::with_capacity(tuples.len());
+    for tuple in tuples.iter() {
+        let (low, high) = ellipsoid.row(tuple).ok_or(GeometryError::Overflow)?;
+        //empty rows are stored so that every point is at most their low end and at least their high end.
+        rows.push(if low > high { (isize::MAX, isize::MIN) } else { (low, high) });
+    }
+    let (shift, neighbours) = match fill {
+        Fill::Solid => (0, Vec::new()),
+        Fill::Thin => (1, rest_tuples(&vec![(-1, 1); dimension - 1]).into_iter().filter(|offset| offset.iter().map(|step| step.abs()).sum::<isize>() == 1).collect()),
+        Fill::Thick => (0, rest_tuples(&vec![(-1, 1); dimension - 1]).into_iter().filter(|offset| offset.iter().any(|step| *step != 0)).collect()),
+    };
+    let mut coordinates: Vec<isize> = Vec::with_capacity(dimension);
+    for (index, tuple) in tuples.iter().enumerate() {
+        let (low, high) = rows[index];
+        if low > high {
+            continue;
+        }
+        let (mut last_low, mut first_high) = if fill == Fill::Solid { (high, high.saturating_add(1)) } else { (low, high) };
+        for offset in neighbours.iter() {
+            //the index of the neighbouring row, if it is inside the box.
+            let mut neighbour: Option<usize> = Some(0);
+            for coordinate in 1 .. dimension {
+                let value = tuple[coordinate - 1] + offset[coordinate - 1];
+                let (box_low, box_high) = ranges[coordinate];
+                neighbour = neighbour.filter(|_| box_low <= value && value <= box_high)
+                    .map(|neighbour| neighbour*((box_high - box_low + 1) as usize) + (value - box_low) as usize);
+            }
+            let (neighbour_low, neighbour_high) = neighbour.map_or((isize::MAX, isize::MIN),|neighbour| rows[neighbour]);
+            last_low = cmp::max(last_low,neighbour_low.saturating_sub(shift));
+            first_high = cmp::min(first_high,neighbour_high.saturating_add(shift));
+        }
+        last_low = cmp::min(last_low,high);
+        first_high = cmp::max(first_high,last_low.saturating_add(1));
+        for x_cord in (low ..= last_low).chain(first_high ..= high) {
+            coordinates.clear();
+            coordinates.push(x_cord);
+            coordinates.extend_from_slice(tuple);
+            emit(&coordinates);
+        }
+    }
+    Ok(())
+}
+
+fn ellipse_2d(center: RationalPoint2D, axis: Point2D, semi_axis_along: Rational, semi_axis_across: Rational, fill: Fill) -> Result<Vec<Point2D>, GeometryError>{
+    let ellipsoid = Ellipsoid::new(vec![center.x,center.y],vec![vec![axis.x,axis.y],vec![-axis.y,axis.x]],vec![semi_axis_along,semi_axis_across])?;
+    let mut squares: Vec<Point2D> = Vec::new();
+    ellipsoid_points::<Point2D>(&ellipsoid,fill,&mut |cords| squares.push(Point2D{ x: cords[0], y: cords[1],}))?;
+    Ok(squares)
+}
+
+fn ellipsoid_3d(center: RationalPoint3D, axis1: Point3D, axis2: Point3D, semi_axes: [Rational; 3], fill: Fill) -> Result<Vec<Point3D>, GeometryError>{
+    let cross = |a: isize, b: isize, c: isize, d: isize| isize::try_from(a as i128*b as i128 - c as i128*d as i128).map_err(|_| GeometryError::Overflow);
+    let axis3 = vec![cross(axis1.y,axis2.z,axis1.z,axis2.y)?,cross(axis1.z,axis2.x,axis1.x,axis2.z)?,cross(axis1.x,axis2.y,axis1.y,axis2.x)?];
+    let ellipsoid = Ellipsoid::new(vec![center.x,center.y,center.z],vec![vec![axis1.x,axis1.y,axis1.z],vec![axis2.x,axis2.y,axis2.z],axis3],semi_axes.to_vec())?;
+    let mut cubes: Vec<Point3D> = Vec::new();
+    ellipsoid_points::<Point3D>(&ellipsoid,fill,&mut |cords| cubes.push(Point3D{ x: cords[0], y: cords[1], z: cords[2],}))?;
+    Ok(cubes)
+}
+
+fn rational_center_2d(center: Point2D) -> RationalPoint2D{
+    RationalPoint2D{ x: Rational::new_rational_from_integer(center.x), y: Rational::new_rational_from_integer(center.y),}
+}
+
+fn rational_center_3d(center: Point3D) -> RationalPoint3D{
+    RationalPoint3D{ x: Rational::new_rational_from_integer(center.x), y: Rational::new_rational_from_integer(center.y), z: Rational::new_rational_from_integer(center.z),}
+}
+
+fn circle_fill(connectivity: CircleConnectivity) -> Fill{
+    match connectivity {
+        CircleConnectivity::EightConnected => Fill::Thin,
+        CircleConnectivity::FourConnected => Fill::Thick,
+    }
+}
+
+fn sphere_fill(connectivity: SphereConnectivity) -> Fill{
+    match connectivity {
+        SphereConnectivity::TwentySixConnected => Fill::Thin,
+        SphereConnectivity::SixConnected => Fill::Thick,
+    }
+}
+
+//The functions without the try_ prefix return no points for a negative semi-axis and panic on any other error.
+fn ellipse_or_panic<T>(points: Result<Vec<T>, GeometryError>) -> Vec<T>{
+    match points {
+        Ok(points) => points,
+        Err(GeometryError::NegativeRadius) => Vec::new(),
+        Err(GeometryError::InvalidAxes) => panic!("The axes of an ellipse must be non-zero and orthogonal!"),
+        Err(_) => panic!("Overflow while finding the points inside an ellipse (Rationals)!"),
+    }
+}
+
+//integer_points_inside_ellipse
+//Purpose:
+//    Returns all integer points (x,y) such that ((x - center.x)/semi_axis_x)^2 + ((y - center.y)/semi_axis_y)^2 <= 1.
+//Pre-conditions:
+//    semi_axis_x and semi_axis_y are non-negative.
+//Notes:
+//    A zero semi-axis flattens the ellipse, the points must then have that coordinate equal to the center's,
+//    so the ellipse is a segment, or just the center when both semi-axes are zero.
+//    With equal semi-axes this is integer_points_inside_circle.
+pub fn integer_points_inside_ellipse(center: Point2D, semi_axis_x: isize, semi_axis_y: isize) -> Vec<Point2D>{
+    ellipse_or_panic(try_integer_points_inside_ellipse(center,semi_axis_x,semi_axis_y))
+}
+
+//try_integer_points_inside_ellipse
+//Purpose:
+//    Returns the same points as integer_points_inside_ellipse, or a GeometryError if a semi-axis is negative (NegativeRadius)
+//    the points do not fit in an isize or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_ellipse(center: Point2D, semi_axis_x: isize, semi_axis_y: isize) -> Result<Vec<Point2D>, GeometryError>{
+    ellipse_2d(rational_center_2d(center),Point2D{ x: 1, y: 0,},Rational::new_rational_from_integer(semi_axis_x),Rational::new_rational_from_integer(semi_axis_y),Fill::Solid)
+}
+
+//integer_points_on_ellipse
+//Purpose:
+//    Returns the points of integer_points_inside_ellipse on its boundary, using the given CircleConnectivity.
+//Pre-conditions:
+//    semi_axis_x and semi_axis_y are non-negative.
+pub fn integer_points_on_ellipse(center: Point2D, semi_axis_x: isize, semi_axis_y: isize, connectivity: CircleConnectivity) -> Vec<Point2D>{
+    ellipse_or_panic(try_integer_points_on_ellipse(center,semi_axis_x,semi_axis_y,connectivity))
+}
+
+//try_integer_points_on_ellipse
+//Purpose:
+//    Returns the same points as integer_points_on_ellipse, or a GeometryError as try_integer_points_inside_ellipse does.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_on_ellipse(center: Point2D, semi_axis_x: isize, semi_axis_y: isize, connectivity: CircleConnectivity) -> Result<Vec<Point2D>, GeometryError>{
+    ellipse_2d(rational_center_2d(center),Point2D{ x: 1, y: 0,},Rational::new_rational_from_integer(semi_axis_x),Rational::new_rational_from_integer(semi_axis_y),circle_fill(connectivity))
+}
+
+//integer_points_inside_ellipse_rational
+//Purpose:
+//    Returns all integer points inside the ellipse with the given center whose first semi-axis, of length semi_axis_along,
+//    points along axis and whose second semi-axis, of length semi_axis_across, is perpendicular to it.
+//Pre-conditions:
+//    axis is not (0,0).
+//    semi_axis_along and semi_axis_across are non-negative.
+//Notes:
+//    An axis of (1,0) gives an axis-aligned ellipse, other axes rotate it by the angle of axis.
+//    Zero semi-axes flatten the ellipse as in integer_points_inside_ellipse.
+pub fn integer_points_inside_ellipse_rational(center: RationalPoint2D, axis: Point2D, semi_axis_along: Rational, semi_axis_across: Rational) -> Vec<Point2D>{
+    ellipse_or_panic(try_integer_points_inside_ellipse_rational(center,axis,semi_axis_along,semi_axis_across))
+}
+
+//try_integer_points_inside_ellipse_rational
+//Purpose:
+//    Returns the same points as integer_points_inside_ellipse_rational, or a GeometryError if a semi-axis is negative (NegativeRadius),
+//    axis is (0,0) (InvalidAxes), the points do not fit in an isize or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_ellipse_rational(center: RationalPoint2D, axis: Point2D, semi_axis_along: Rational, semi_axis_across: Rational) -> Result<Vec<Point2D>, GeometryError>{
+    ellipse_2d(center,axis,semi_axis_along,semi_axis_across,Fill::Solid)
+}
+
+//integer_points_on_ellipse_rational
+//Purpose:
+//    Returns the points of integer_points_inside_ellipse_rational on its boundary, using the given CircleConnectivity.
+//Pre-conditions:
+//    axis is not (0,0).
+//    semi_axis_along and semi_axis_across are non-negative.
+pub fn integer_points_on_ellipse_rational(center: RationalPoint2D, axis: Point2D, semi_axis_along: Rational, semi_axis_across: Rational, connectivity: CircleConnectivity) -> Vec<Point2D>{
+    ellipse_or_panic(try_integer_points_on_ellipse_rational(center,axis,semi_axis_along,semi_axis_across,connectivity))
+}
+
+//try_integer_points_on_ellipse_rational
+//Purpose:
+//    Returns the same points as integer_points_on_ellipse_rational, or a GeometryError as try_integer_points_inside_ellipse_rational does.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_on_ellipse_rational(center: RationalPoint2D, axis: Point2D, semi_axis_along: Rational, semi_axis_across: Rational, connectivity: CircleConnectivity) -> Result<Vec<Point2D>, GeometryError>{
+    ellipse_2d(center,axis,semi_axis_along,semi_axis_across,circle_fill(connectivity))
+}
+
+//integer_points_inside_ellipsoid
+//Purpose:
+//    Returns all integer points (x,y,z) such that
+//    ((x - center.x)/semi_axis_x)^2 + ((y - center.y)/semi_axis_y)^2 + ((z - center.z)/semi_axis_z)^2 <= 1.
+//Pre-conditions:
+//    semi_axis_x, semi_axis_y and semi_axis_z are non-negative.
+//Notes:
+//    Zero semi-axes flatten the ellipsoid as in integer_points_inside_ellipse, down to an ellipse, a segment or just the center.
+//    With equal semi-axes this is integer_points_inside_sphere.
+pub fn integer_points_inside_ellipsoid(center: Point3D, semi_axis_x: isize, semi_axis_y: isize, semi_axis_z: isize) -> Vec<Point3D>{
+    ellipse_or_panic(try_integer_points_inside_ellipsoid(center,semi_axis_x,semi_axis_y,semi_axis_z))
+}
+
+//try_integer_points_inside_ellipsoid
+//Purpose:
+//    Returns the same points as integer_points_inside_ellipsoid, or a GeometryError if a semi-axis is negative (NegativeRadius)
+//    the points do not fit in an isize or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_ellipsoid(center: Point3D, semi_axis_x: isize, semi_axis_y: isize, semi_axis_z: isize) -> Result<Vec<Point3D>, GeometryError>{
+    let semi_axes = [Rational::new_rational_from_integer(semi_axis_x),Rational::new_rational_from_integer(semi_axis_y),Rational::new_rational_from_integer(semi_axis_z)];
+    ellipsoid_3d(rational_center_3d(center),Point3D{ x: 1, y: 0, z: 0,},Point3D{ x: 0, y: 1, z: 0,},semi_axes,Fill::Solid)
+}
+
+//integer_points_on_ellipsoid
+//Purpose:
+//    Returns the points of integer_points_inside_ellipsoid on its boundary, using the given SphereConnectivity.
+//Pre-conditions:
+//    semi_axis_x, semi_axis_y and semi_axis_z are non-negative.
+pub fn integer_points_on_ellipsoid(center: Point3D, semi_axis_x: isize, semi_axis_y: isize, semi_axis_z: isize, connectivity: SphereConnectivity) -> Vec<Point3D>{
+    ellipse_or_panic(try_integer_points_on_ellipsoid(center,semi_axis_x,semi_axis_y,semi_axis_z,connectivity))
+}
+
+//try_integer_points_on_ellipsoid
+//Purpose:
+//    Returns the same points as integer_points_on_ellipsoid, or a GeometryError as try_integer_points_inside_ellipsoid does.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_on_ellipsoid(center: Point3D, semi_axis_x: isize, semi_axis_y: isize, semi_axis_z: isize, connectivity: SphereConnectivity) -> Result<Vec<Point3D>, GeometryError>{
+    let semi_axes = [Rational::new_rational_from_integer(semi_axis_x),Rational::new_rational_from_integer(semi_axis_y),Rational::new_rational_from_integer(semi_axis_z)];
+    ellipsoid_3d(rational_center_3d(center),Point3D{ x: 1, y: 0, z: 0,},Point3D{ x: 0, y: 1, z: 0,},semi_axes,sphere_fill(connectivity))
+}
+
+//integer_points_inside_ellipsoid_rational
+//Purpose:
+//    Returns all integer points inside the ellipsoid with the given center whose semi-axes point along axis1, axis2
+//    and their cross product axis1 x axis2, with lengths semi_axes[0], semi_axes[1] and semi_axes[2].
+//Pre-conditions:
+//    axis1 and axis2 are non-zero and orthogonal.
+//    Every semi-axis is non-negative.
+//Notes:
+//    Axes of (1,0,0) and (0,1,0) give an axis-aligned ellipsoid.
+//    Zero semi-axes flatten the ellipsoid as in integer_points_inside_ellipsoid.
+pub fn integer_points_inside_ellipsoid_rational(center: RationalPoint3D, axis1: Point3D, axis2: Point3D, semi_axes: [Rational; 3]) -> Vec<Point3D>{
+    ellipse_or_panic(try_integer_points_inside_ellipsoid_rational(center,axis1,axis2,semi_axes))
+}
+
+//try_integer_points_inside_ellipsoid_rational
+//Purpose:
+//    Returns the same points as integer_points_inside_ellipsoid_rational, or a GeometryError if a semi-axis is negative (NegativeRadius),
+//    the axes are zero or not orthogonal (InvalidAxes), the points do not fit in an isize or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_ellipsoid_rational(center: RationalPoint3D, axis1: Point3D, axis2: Point3D, semi_axes: [Rational; 3]) -> Result<Vec<Point3D>, GeometryError>{
+    ellipsoid_3d(center,axis1,axis2,semi_axes,Fill::Solid)
+}
+
+//integer_points_on_ellipsoid_rational
+//Purpose:
+//    Returns the points of integer_points_inside_ellipsoid_rational on its boundary, using the given SphereConnectivity.
+//Pre-conditions:
+//    axis1 and axis2 are non-zero and orthogonal.
+//    Every semi-axis is non-negative.
+pub fn integer_points_on_ellipsoid_rational(center: RationalPoint3D, axis1: Point3D, axis2: Point3D, semi_axes: [Rational; 3], connectivity: SphereConnectivity) -> Vec<Point3D>{
+    ellipse_or_panic(try_integer_points_on_ellipsoid_rational(center,axis1,axis2,semi_axes,connectivity))
+}
+
+//try_integer_points_on_ellipsoid_rational
+//Purpose:
+//    Returns the same points as integer_points_on_ellipsoid_rational, or a GeometryError as try_integer_points_inside_ellipsoid_rational does.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_on_ellipsoid_rational(center: RationalPoint3D, axis1: Point3D, axis2: Point3D, semi_axes: [Rational; 3], connectivity: SphereConnectivity) -> Result<Vec<Point3D>, GeometryError>{
+    ellipsoid_3d(center,axis1,axis2,semi_axes,sphere_fill(connectivity))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ball::{integer_points_inside_circle, integer_points_inside_sphere, integer_points_on_circle, integer_points_on_sphere};
+    use crate::{rational, sort};
+
+    //Returns the points of filled with a neighbour outside of it, the face neighbours only if thin.
+    fn boundary_2d(filled: &[Point2D], thin: bool) -> Vec<Point2D>{
+        filled.iter().cloned().filter(|point| {
+            (-1 ..= 1).any(|dx: isize| (-1 ..= 1).any(|dy: isize| (!thin || dx.abs() + dy.abs() == 1) && !filled.contains(&Point2D{ x: point.x + dx, y: point.y + dy,})))
+        }).collect()
+    }
+
+    fn boundary_3d(filled: &[Point3D], thin: bool) -> Vec<Point3D>{
+        filled.iter().cloned().filter(|point| {
+            (-1 ..= 1).any(|dx: isize| (-1 ..= 1).any(|dy: isize| (-1 ..= 1).any(|dz: isize| {
+                (!thin || dx.abs() + dy.abs() + dz.abs() == 1) && !filled.contains(&Point3D{ x: point.x + dx, y: point.y + dy, z: point.z + dz,})
+            })))
+        }).collect()
+    }
+
+    //Returns true if the offset is inside the ellipsoid with the given directions and semi-axes, computed directly.
+    fn inside(offset: &[Rational], directions: &[Vec<isize>], semi_axes: &[Rational]) -> bool{
+        let mut total = rational(0,1);
+        for (direction, semi_axis) in directions.iter().zip(semi_axes.iter()) {
+            let mut along = rational(0,1);
+            let mut length = 0;
+            for (component, value) in direction.iter().zip(offset.iter()) {
+                along = along + *value*(*component);
+                length += component*component;
+            }
+            if *semi_axis == rational(0,1) {
+                if along != rational(0,1) {
+                    return false;
+                }
+                continue;
+            }
+            total = total + along*along/(*semi_axis*(*semi_axis)*length);
+        }
+        total <= rational(1,1)
+    }
+
+    #[test]
+    fn axis_aligned_ellipses(){
+        let center = Point2D{ x: 2, y: -5,};
+        for radius in 0 .. 12 {
+            assert_eq!(sort(integer_points_inside_ellipse(center,radius,radius)),sort(integer_points_inside_circle(center,radius)));
+            for connectivity in [CircleConnectivity::EightConnected,CircleConnectivity::FourConnected].iter() {
+                assert_eq!(sort(integer_points_on_ellipse(center,radius,radius,*connectivity)),sort(integer_points_on_circle(center,radius,*connectivity)));
+            }
+        }
+        for semi_axis_x in 1isize .. 9 {
+            for semi_axis_y in 1 .. 9 {
+                let mut expected: Vec<Point2D> = Vec::new();
+                for x in -semi_axis_x ..= semi_axis_x {
+                    for y in -semi_axis_y ..= semi_axis_y {
+                        if (x*semi_axis_y).pow(2) + (y*semi_axis_x).pow(2) <= (semi_axis_x*semi_axis_y).pow(2) {
+                            expected.push(Point2D{ x: x + center.x, y: y + center.y,});
+                        }
+                    }
+                }
+                assert_eq!(sort(integer_points_inside_ellipse(center,semi_axis_x,semi_axis_y)),sort(expected.clone()));
+                assert_eq!(sort(integer_points_on_ellipse(center,semi_axis_x,semi_axis_y,CircleConnectivity::EightConnected)),sort(boundary_2d(&expected,true)));
+                assert_eq!(sort(integer_points_on_ellipse(center,semi_axis_x,semi_axis_y,CircleConnectivity::FourConnected)),sort(boundary_2d(&expected,false)));
+            }
+        }
+        let center = Point3D{ x: -1, y: 0, z: 4,};
+        for radius in 0 .. 6 {
+            assert_eq!(sort(integer_points_inside_ellipsoid(center,radius,radius,radius)),sort(integer_points_inside_sphere(center,radius)));
+            for connectivity in [SphereConnectivity::TwentySixConnected,SphereConnectivity::SixConnected].iter() {
+                assert_eq!(sort(integer_points_on_ellipsoid(center,radius,radius,radius,*connectivity)),sort(integer_points_on_sphere(center,radius,*connectivity)));
+            }
+        }
+        let filled = integer_points_inside_ellipsoid(center,5,2,3);
+        let expected: Vec<Point3D> = integer_points_inside_sphere(center,5).into_iter()
+            .filter(|point| 36*(point.x - center.x).pow(2) + 225*(point.y - center.y).pow(2) + 100*(point.z - center.z).pow(2) <= 900).collect();
+        assert_eq!(sort(filled.clone()),sort(expected));
+        assert_eq!(sort(integer_points_on_ellipsoid(center,5,2,3,SphereConnectivity::TwentySixConnected)),sort(boundary_3d(&filled,true)));
+        assert_eq!(sort(integer_points_on_ellipsoid(center,5,2,3,SphereConnectivity::SixConnected)),sort(boundary_3d(&filled,false)));
+    }
+
+    #[test]
+    fn rotated_ellipses(){
+        let center = RationalPoint2D{ x: rational(1,2), y: rational(-1,3),};
+        let axes = [Point2D{ x: 1, y: 0,},Point2D{ x: 1, y: 2,},Point2D{ x: -3, y: 1,},Point2D{ x: 0, y: -5,}];
+        for axis in axes.iter() {
+            let directions = vec![vec![axis.x,axis.y],vec![-axis.y,axis.x]];
+            let semi_axes = [rational(13,2),rational(5,3)];
+            let mut expected: Vec<Point2D> = Vec::new();
+            for x in -8 ..= 8 {
+                for y in -8 ..= 8 {
+                    if inside(&[rational(x,1) - center.x,rational(y,1) - center.y],&directions,&semi_axes) {
+                        expected.push(Point2D{ x, y,});
+                    }
+                }
+            }
+            let filled = integer_points_inside_ellipse_rational(center,*axis,semi_axes[0],semi_axes[1]);
+            assert_eq!(sort(filled),sort(expected.clone()));
+            assert_eq!(sort(integer_points_on_ellipse_rational(center,*axis,semi_axes[0],semi_axes[1],CircleConnectivity::EightConnected)),sort(boundary_2d(&expected,true)));
+            assert_eq!(sort(integer_points_on_ellipse_rational(center,*axis,semi_axes[0],semi_axes[1],CircleConnectivity::FourConnected)),sort(boundary_2d(&expected,false)));
+        }
+        let center = RationalPoint3D{ x: rational(1,3), y: rational(0,1), z: rational(-1,2),};
+        let (axis1, axis2) = (Point3D{ x: 1, y: 1, z: 0,},Point3D{ x: 1, y: -1, z: 1,});
+        let directions = vec![vec![1,1,0],vec![1,-1,1],vec![1,-1,-2]];
+        let semi_axes = [rational(9,2),rational(2,1),rational(7,3)];
+        let mut expected: Vec<Point3D> = Vec::new();
+        for x in -6 ..= 6 {
+            for y in -6 ..= 6 {
+                for z in -6 ..= 6 {
+                    if inside(&[rational(x,1) - center.x,rational(y,1) - center.y,rational(z,1) - center.z],&directions,&semi_axes) {
+                        expected.push(Point3D{ x, y, z,});
+                    }
+                }
+            }
+        }
+        assert_eq!(sort(integer_points_inside_ellipsoid_rational(center,axis1,axis2,semi_axes)),sort(expected.clone()));
+        assert_eq!(sort(integer_points_on_ellipsoid_rational(center,axis1,axis2,semi_axes,SphereConnectivity::TwentySixConnected)),sort(boundary_3d(&expected,true)));
+        assert_eq!(sort(integer_points_on_ellipsoid_rational(center,axis1,axis2,semi_axes,SphereConnectivity::SixConnected)),sort(boundary_3d(&expected,false)));
+    }
+
+    #[test]
+    fn degenerate_ellipses(){
+        let center = Point2D{ x: 2, y: -5,};
+        let segment: Vec<Point2D> = (-3 ..= 3).map(|y| Point2D{ x: 2, y: y - 5,}).collect();
+        assert_eq!(sort(integer_points_inside_ellipse(center,0,3)),sort(segment.clone()));
+        assert_eq!(sort(integer_points_on_ellipse(center,0,3,CircleConnectivity::FourConnected)),sort(segment));
+        let center3 = Point3D{ x: -1, y: 0, z: 4,};
+        assert_eq!(integer_points_inside_ellipsoid(center3,0,0,0),vec![center3]);
+        assert_eq!(sort(integer_points_inside_ellipsoid(center3,0,2,0)),sort((-2 ..= 2).map(|y| Point3D{ x: -1, y, z: 4,}).collect::<Vec<Point3D>>()));
+        let flat: Vec<Point3D> = integer_points_inside_ellipse(Point2D{ x: -1, y: 4,},3,2).into_iter().map(|point| Point3D{ x: point.x, y: 0, z: point.y,}).collect();
+        assert_eq!(sort(integer_points_inside_ellipsoid(center3,3,0,2)),sort(flat.clone()));
+        assert_eq!(sort(integer_points_on_ellipsoid(center3,3,0,2,SphereConnectivity::TwentySixConnected)),sort(flat));
+        //rotated, with rational centers that the flattened ellipse may or may not pass through.
+        let centers = [RationalPoint2D{ x: rational(1,1), y: rational(-1,1),},RationalPoint2D{ x: rational(1,2), y: rational(0,1),},RationalPoint2D{ x: rational(1,3), y: rational(1,3),}];
+        for center in centers.iter() {
+            let axis = Point2D{ x: 1, y: 2,};
+            let directions = vec![vec![1,2],vec![-2,1]];
+            for semi_axes in [[rational(0,1),rational(9,2)],[rational(7,3),rational(0,1)],[rational(0,1),rational(0,1)]].iter() {
+                let mut expected: Vec<Point2D> = Vec::new();
+                for x in -8 ..= 8 {
+                    for y in -8 ..= 8 {
+                        if inside(&[rational(x,1) - center.x,rational(y,1) - center.y],&directions,semi_axes) {
+                            expected.push(Point2D{ x, y,});
+                        }
+                    }
+                }
+                assert_eq!(sort(integer_points_inside_ellipse_rational(*center,axis,semi_axes[0],semi_axes[1])),sort(expected.clone()));
+                assert_eq!(sort(integer_points_on_ellipse_rational(*center,axis,semi_axes[0],semi_axes[1],CircleConnectivity::EightConnected)),sort(boundary_2d(&expected,true)));
+            }
+        }
+        let center = RationalPoint3D{ x: rational(0,1), y: rational(1,2), z: rational(-1,1),};
+        let (axis1, axis2) = (Point3D{ x: 1, y: 1, z: 0,},Point3D{ x: 1, y: -1, z: 1,});
+        let directions = vec![vec![1,1,0],vec![1,-1,1],vec![1,-1,-2]];
+        for semi_axes in [[rational(0,1),rational(7,2),rational(3,1)],[rational(5,1),rational(0,1),rational(0,1)],[rational(0,1),rational(0,1),rational(0,1)]].iter() {
+            let mut expected: Vec<Point3D> = Vec::new();
+            for x in -6 ..= 6 {
+                for y in -6 ..= 6 {
+                    for z in -6 ..= 6 {
+                        if inside(&[rational(x,1) - center.x,rational(y,1) - center.y,rational(z,1) - center.z],&directions,semi_axes) {
+                            expected.push(Point3D{ x, y, z,});
+                        }
+                    }
+                }
+            }
+            assert_eq!(sort(integer_points_inside_ellipsoid_rational(center,axis1,axis2,*semi_axes)),sort(expected.clone()));
+            assert_eq!(sort(integer_points_on_ellipsoid_rational(center,axis1,axis2,*semi_axes,SphereConnectivity::SixConnected)),sort(boundary_3d(&expected,false)));
+        }
+    }
+
+    #[test]
+    fn invalid_ellipses(){
+        let center = Point2D{ x: 0, y: 0,};
+        assert_eq!(integer_points_inside_ellipse(center,-1,3),Vec::new());
+        assert_eq!(try_integer_points_inside_ellipse(center,-1,3),Err(GeometryError::NegativeRadius));
+        assert_eq!(try_integer_points_on_ellipse(center,-3,0,CircleConnectivity::FourConnected),Err(GeometryError::NegativeRadius));
+        let center = RationalPoint2D{ x: rational(0,1), y: rational(0,1),};
+        assert_eq!(try_integer_points_inside_ellipse_rational(center,Point2D{ x: 0, y: 0,},rational(1,1),rational(1,1)),Err(GeometryError::InvalidAxes));
+        let center = RationalPoint3D{ x: rational(0,1), y: rational(0,1), z: rational(0,1),};
+        let semi_axes = [rational(1,1),rational(2,1),rational(3,1)];
+        assert_eq!(try_integer_points_inside_ellipsoid_rational(center,Point3D{ x: 1, y: 1, z: 0,},Point3D{ x: 1, y: 0, z: 0,},semi_axes),Err(GeometryError::InvalidAxes));
+        assert_eq!(try_integer_points_inside_ellipsoid(Point3D{ x: 0, y: 0, z: 0,},1 << 28,1 << 28,1 << 28),Err(GeometryError::RangeTooLarge));
+        assert_eq!(try_integer_points_inside_ellipsoid_rational(center,Point3D{ x: 1, y: 0, z: 0,},Point3D{ x: 0, y: 1, z: 0,},[rational(1,1),rational(1,isize::MAX),rational(1,1)]),Err(GeometryError::Overflow));
+    }
+}
@@ -0,0 +1,65 @@
+//Purpose: Contains the error type returned by the fallible (try_) constructors and rasterizers.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::rational::OverflowError;
+
+//GeometryError
+//Purpose:
+//    The reasons a try_ function can reject its input instead of panicking.
+//    ZeroDenominator: a rational was created with a zero denominator.
+//    DivisionByZero: a rational was divided by zero.
+//    NegativeRadius: a ball or thick line was given a negative radius.
+//    InvalidExponent: an Lp ball was given an exponent p less than 1.
+//    InvalidAxes: an ellipse or ellipsoid was given axis directions that are zero or not orthogonal.
+//    InvalidMesh: a triangle of a mesh was given the index of a vertex that the mesh does not have.
+//    RangeTooLarge: the cells to be returned (or the arithmetic on their coordinates) do not fit in an isize.
+//    Overflow: a computation on rationals overflowed, see OverflowError.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GeometryError {
+    ZeroDenominator,
+    DivisionByZero,
+    NegativeRadius,
+    InvalidExponent,
+    InvalidAxes,
+    InvalidMesh,
+    RangeTooLarge,
+    Overflow,
+}
+
+impl fmt::Display for GeometryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GeometryError::ZeroDenominator => write!(f, "rational with a zero denominator"),
+            GeometryError::DivisionByZero => write!(f, "division by zero"),
+            GeometryError::NegativeRadius => write!(f, "negative radius"),
+            GeometryError::InvalidExponent => write!(f, "exponent of an Lp ball less than 1"),
+            GeometryError::InvalidAxes => write!(f, "axes that are zero or not orthogonal"),
+            GeometryError::InvalidMesh => write!(f, "triangle with a vertex index outside the mesh"),
+            GeometryError::RangeTooLarge => write!(f, "range of cells too large for an isize"),
+            GeometryError::Overflow => write!(f, "{}", OverflowError),
+        }
+    }
+}
+
+impl Error for GeometryError {}
+
+impl From<OverflowError> for GeometryError {
+    fn from(_: OverflowError) -> GeometryError {
+        GeometryError::Overflow
+    }
+}
+
+//check_cell_count
+//Purpose:
+//    Returns GeometryError::RangeTooLarge if count values of type T can not be stored in a Vec,
+//    where a count of None means that computing it overflowed.
+//Pre-conditions:
+//    None.
+pub(crate) fn check_cell_count<T>(count: Option<usize>) -> Result<(), GeometryError>{
+    match count.and_then(|count| count.checked_mul(std::mem::size_of::<T>())) {
+        Some(bytes) if bytes <= isize::MAX as usize => Ok(()),
+        _ => Err(GeometryError::RangeTooLarge),
+    }
+}
@@ -0,0 +1,477 @@
+//Purpose: Contains hex grid coordinates and functions for finding the hexes on line segments, on rings and inside balls.
+//Notes:
+//    Hexes are regular hexagons centered on the points of a hex grid. All computations are done exactly in cube coordinates,
+//    where a hex (q,r) is the point (q,r,s) with s = -q - r. Which way the hexagons point does not change any result.
+
+use std::cmp;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+use crate::error::{check_cell_count, GeometryError};
+use crate::rational::Rational;
+
+//Hex
+//Purpose:
+//    A hex in axial coordinates.
+#[derive(Debug, Copy, Clone,PartialEq,Eq,PartialOrd,Ord)]
+pub struct Hex {
+    pub q: isize,
+    pub r: isize,
+}
+
+//CubeHex
+//Purpose:
+//    A hex in cube coordinates, q + r + s is always 0.
+#[derive(Debug, Copy, Clone,PartialEq,Eq,PartialOrd,Ord)]
+pub struct CubeHex {
+    pub q: isize,
+    pub r: isize,
+    pub s: isize,
+}
+
+//The six neighbours of the hex (0,0), in order around it.
+pub const HEX_DIRECTIONS: [Hex; 6] = [
+    Hex{ q: 1, r: 0,},
+    Hex{ q: 1, r: -1,},
+    Hex{ q: 0, r: -1,},
+    Hex{ q: -1, r: 0,},
+    Hex{ q: -1, r: 1,},
+    Hex{ q: 0, r: 1,},
+];
+
+impl Hex {
+    pub fn s(&self) -> isize{
+        -self.q - self.r
+    }
+}
+
+impl From<Hex> for CubeHex {
+    fn from(hex: Hex) -> CubeHex {
+        CubeHex{ q: hex.q, r: hex.r, s: hex.s(),}
+    }
+}
+
+//Pre-conditions: q + r + s is 0.
+impl From<CubeHex> for Hex {
+    fn from(hex: CubeHex) -> Hex {
+        Hex{ q: hex.q, r: hex.r,}
+    }
+}
+
+//hex_distance
+//Purpose:
+//    Returns the number of steps between neighbouring hexes needed to get from hex1 to hex2.
+//Pre-conditions:
+//    The differences of the coordinates of the hexes and the distance fit in an isize.
+pub fn hex_distance(hex1: Hex, hex2: Hex) -> isize{
+    let dq = hex2.q - hex1.q;
+    let dr = hex2.r - hex1.r;
+    cmp::max(cmp::max(dq.abs(),dr.abs()),(dq + dr).abs())
+}
+
+//The cube coordinates of a hex, or of the offset between two hexes, widened so that the differences and products below do not overflow as soon.
+type CubeOffset = [i128; 3];
+
+//offset
+//Purpose:
+//    Returns the cube coordinates of hex2 - hex1.
+//Pre-conditions:
+//    None.
+fn offset(hex1: Hex, hex2: Hex) -> CubeOffset{
+    let dq = hex2.q as i128 - hex1.q as i128;
+    let dr = hex2.r as i128 - hex1.r as i128;
+    [dq, dr, -dq - dr]
+}
+
+//translate
+//Purpose:
+//    Returns hex moved by the given offset, or GeometryError::RangeTooLarge if its coordinates do not fit in an isize.
+//Pre-conditions:
+//    The coordinates of offset sum to 0.
+fn translate(hex: Hex, offset: CubeOffset) -> Result<Hex, GeometryError>{
+    let coordinate = |start: isize, offset: i128| isize::try_from(start as i128 + offset).map_err(|_| GeometryError::RangeTooLarge);
+    Ok(Hex{ q: coordinate(hex.q,offset[0])?, r: coordinate(hex.r,offset[1])?,})
+}
+
+//contains_scaled
+//Purpose:
+//    Returns the ordering of max(|d_q - d_r|,|d_r - d_s|,|d_s - d_q|) with scale, where d = point - scale*center,
+//    or None if a computation overflows.
+//    The point point/scale is inside the hex if this is Less, on its boundary if Equal and outside it if Greater.
+//Pre-conditions:
+//    scale is positive and the coordinates of point and of center sum to 0.
+fn contains_scaled(point: CubeOffset, scale: i128, center: CubeOffset) -> Option<Ordering>{
+    let mut d = [0; 3];
+    for i in 0 .. 3 {
+        d[i] = point[i].checked_sub(scale.checked_mul(center[i])?)?;
+    }
+    let mut largest = 0;
+    for (i, j) in [(0,1),(1,2),(2,0)].iter() {
+        largest = cmp::max(largest,d[*i].checked_sub(d[*j])?.checked_abs()?);
+    }
+    Some(largest.cmp(&scale))
+}
+
+//round_scaled
+//Purpose:
+//    Returns the cube coordinates of the hex containing the point point/scale, if the point is on the boundary of two hexes
+//    the one with the larger q + 2r - 3s is returned. Returns None if a computation overflows.
+//Pre-conditions:
+//    scale is positive and the coordinates of point sum to 0.
+//Notes:
+//    Choosing the larger q + 2r - 3s is the same as moving the point by a tiny fixed amount in the direction (1,2,-3) first,
+//    which is never parallel to an edge, so the choice does not depend on which direction a line is drawn in.
+//    Moving the point by a hex moves the result by the same hex, so points may be given relative to any hex.
+fn round_scaled(point: CubeOffset, scale: i128) -> Option<CubeOffset>{
+    //round each coordinate, then fix the one furthest from its rounded value so that they sum to 0.
+    let double_scale = scale.checked_mul(2)?;
+    let mut rounded = [0; 3];
+    let mut error = [0; 3];
+    for i in 0 .. 3 {
+        rounded[i] = point[i].checked_mul(2)?.checked_add(scale)?.div_euclid(double_scale);
+        error[i] = point[i].checked_sub(scale.checked_mul(rounded[i])?)?.checked_abs()?;
+    }
+    let guess = if error[0] > error[1] && error[0] > error[2] {
+        [-rounded[1] - rounded[2], rounded[1], rounded[2]]
+    }else if error[1] > error[2] {
+        [rounded[0], -rounded[0] - rounded[2], rounded[2]]
+    }else{
+        [rounded[0], rounded[1], -rounded[0] - rounded[1]]
+    };
+    //the point is inside or on the boundary of guess, on a boundary the only other candidates are its neighbours.
+    let tie_break = |hex: CubeOffset| hex[0] + 2*hex[1] - 3*hex[2];
+    let mut best = guess;
+    for direction in HEX_DIRECTIONS.iter() {
+        let neighbour = [guess[0] + direction.q as i128, guess[1] + direction.r as i128, guess[2] + direction.s() as i128];
+        if contains_scaled(point,scale,neighbour)? != Ordering::Greater && tie_break(neighbour) > tie_break(best) {
+            best = neighbour;
+        }
+    }
+    Some(best)
+}
+
+//hex_line_offsets
+//Purpose:
+//    Returns the offsets from hex1 of the hexes of hex_line, where delta is the offset of hex2 from hex1,
+//    or GeometryError::RangeTooLarge if there are too many of them or a computation overflows.
+//Pre-conditions:
+//    The coordinates of delta sum to 0.
+fn hex_line_offsets(delta: CubeOffset) -> Result<Vec<CubeOffset>, GeometryError>{
+    let steps = cmp::max(cmp::max(delta[0].abs(),delta[1].abs()),delta[2].abs());
+    if steps == 0 {
+        return Ok(vec![[0, 0, 0]]);
+    }
+    let count = usize::try_from(steps).ok().and_then(|steps| steps.checked_add(1));
+    check_cell_count::<Hex>(count)?;
+    let mut offsets = Vec::with_capacity(steps as usize + 1);
+    for step in 0 .. steps + 1 {
+        let point = [step.checked_mul(delta[0]), step.checked_mul(delta[1]), step.checked_mul(delta[2])];
+        let rounded = match point {
+            [Some(q), Some(r), Some(s)] => round_scaled([q, r, s],steps),
+            _ => None,
+        };
+        offsets.push(rounded.ok_or(GeometryError::RangeTooLarge)?);
+    }
+    Ok(offsets)
+}
+
+//hex_line
+//Purpose:
+//    Returns a line of hexes from hex1 to hex2, each hex a neighbour of the one before it.
+//    There are hex_distance(hex1,hex2) + 1 hexes, the i-th being the hex containing the point i/N of the way along the segment
+//    between the centers of hex1 and hex2, where N is the distance.
+//Pre-conditions:
+//    The hexes of the line and their number fit in an isize, see try_hex_line.
+//Notes:
+//    Such a point can lie on an edge between two hexes (for example whenever the segment runs along an edge),
+//    see round_scaled for which one is used. Swapping hex1 and hex2 gives the same hexes in the reverse order.
+pub fn hex_line(hex1: Hex, hex2: Hex) -> Vec<Hex>{
+    match try_hex_line(hex1,hex2) {
+        Ok(hexes) => hexes,
+        Err(_) => panic!("Overflow while finding the hexes on a line segment!"),
+    }
+}
+
+//try_hex_line
+//Purpose:
+//    Returns the same hexes as hex_line, or GeometryError::RangeTooLarge if they (or the arithmetic on their coordinates)
+//    do not fit in an isize or there are too many to fit in a Vec.
+//Pre-conditions:
+//    None.
+//Notes:
+//    The line is found relative to hex1, so hexes far from the origin work as well as hexes near it.
+pub fn try_hex_line(hex1: Hex, hex2: Hex) -> Result<Vec<Hex>, GeometryError>{
+    hex_line_offsets(offset(hex1,hex2))?.into_iter().map(|offset| translate(hex1,offset)).collect()
+}
+
+//crossing_interval
+//Purpose:
+//    Returns the interval of parameters t (0 at the start, 1 at the end) for which the segment from the center of a hex
+//    to the center of the hex at offset delta from it lies in the hex at offset center, boundary included, or None if it never does.
+//    Returns GeometryError::RangeTooLarge if a computation overflows.
+//Pre-conditions:
+//    The coordinates of delta and of center sum to 0.
+fn crossing_interval(delta: CubeOffset, center: CubeOffset) -> Result<Option<(Rational, Rational)>, GeometryError>{
+    let start = [-center[0], -center[1], -center[2]];
+    let mut low = Rational::new_rational_from_integer(0);
+    let mut high = Rational::new_rational_from_integer(1);
+    for (i, j) in [(0,1),(1,2),(2,0)].iter() {
+        //need -1 <= offset + t*slope <= 1
+        let offset = start[*i] - start[*j];
+        let slope = delta[*i] - delta[*j];
+        if slope == 0 {
+            if offset.abs() > 1 {
+                return Ok(None);
+            }
+            continue;
+        }
+        let fraction = |numerator: i128| match (isize::try_from(numerator), isize::try_from(slope)) {
+            (Ok(numerator), Ok(slope)) => Rational::try_new(numerator,slope).map_err(|_| GeometryError::RangeTooLarge),
+            _ => Err(GeometryError::RangeTooLarge),
+        };
+        let bound1 = fraction(-1 - offset)?;
+        let bound2 = fraction(1 - offset)?;
+        low = cmp::max(low,cmp::min(bound1,bound2));
+        high = cmp::min(high,cmp::max(bound1,bound2));
+    }
+    if low <= high {
+        Ok(Some((low, high)))
+    }else{
+        Ok(None)
+    }
+}
+
+//hex_cells_on_line_segment
+//Purpose:
+//    Returns all hexes that the segment between the centers of hex1 and hex2 passes through, ordered from hex1 to hex2.
+//    Mirrors integer_cells_on_line_segment_2d_ordered for hexes.
+//Pre-conditions:
+//    The hexes of the segment and their number fit in an isize, see try_hex_cells_on_line_segment.
+//Notes:
+//    Hexes the segment only touches at a corner are not included.
+//    Where the segment runs along an edge both hexes sharing that edge are included,
+//    they are entered at the same point and are ordered by (q,r).
+pub fn hex_cells_on_line_segment(hex1: Hex, hex2: Hex) -> Vec<Hex>{
+    match try_hex_cells_on_line_segment(hex1,hex2) {
+        Ok(hexes) => hexes,
+        Err(_) => panic!("Overflow while finding the hexes on a line segment!"),
+    }
+}
+
+//try_hex_cells_on_line_segment
+//Purpose:
+//    Returns the same hexes as hex_cells_on_line_segment, or GeometryError::RangeTooLarge if they (or the arithmetic on their coordinates)
+//    do not fit in an isize or there are too many to fit in a Vec.
+//Pre-conditions:
+//    None.
+pub fn try_hex_cells_on_line_segment(hex1: Hex, hex2: Hex) -> Result<Vec<Hex>, GeometryError>{
+    if hex1 == hex2 {
+        return Ok(vec![hex1]);
+    }
+    //every hex the segment passes through is a neighbour of (or on) the hex_line between the two hexes.
+    let delta = offset(hex1,hex2);
+    let line = hex_line_offsets(delta)?;
+    check_cell_count::<Hex>(line.len().checked_mul(7))?;
+    let mut candidates: Vec<CubeOffset> = Vec::new();
+    for hex in line {
+        candidates.push(hex);
+        for direction in HEX_DIRECTIONS.iter() {
+            candidates.push([hex[0] + direction.q as i128, hex[1] + direction.r as i128, hex[2] + direction.s() as i128]);
+        }
+    }
+    candidates.sort();
+    candidates.dedup();
+    let mut crossed: Vec<(Rational, Hex)> = Vec::new();
+    for candidate in candidates {
+        if let Some((entry, exit)) = crossing_interval(delta,candidate)? {
+            if entry < exit {
+                crossed.push((entry, translate(hex1,candidate)?));
+            }
+        }
+    }
+    crossed.sort();
+    Ok(crossed.into_iter().map(|(_, hex)| hex).collect())
+}
+
+//hex_ring
+//Purpose:
+//    Returns all hexes at exactly the given distance from the center, going once around the ring.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    The ring starts at center + radius*HEX_DIRECTIONS[4] and there are 6*radius hexes (1 if radius is 0).
+pub fn hex_ring(center: Hex, radius: isize) -> Vec<Hex>{
+    if radius == 0 {
+        return vec![center];
+    }
+    let mut hexes = Vec::with_capacity(6*radius as usize);
+    let mut hex = Hex{ q: center.q + radius*HEX_DIRECTIONS[4].q, r: center.r + radius*HEX_DIRECTIONS[4].r,};
+    for direction in HEX_DIRECTIONS.iter() {
+        for _ in 0 .. radius {
+            hexes.push(hex);
+            hex = Hex{ q: hex.q + direction.q, r: hex.r + direction.r,};
+        }
+    }
+    hexes
+}
+
+//hex_cells_inside_ball
+//Purpose:
+//    Returns all hexes within the given distance from the center.
+//    Mirrors integer_points_inside_dimond_2d for hexes.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    There are 3*radius*(radius + 1) + 1 hexes.
+pub fn hex_cells_inside_ball(center: Hex, radius: isize) -> Vec<Hex>{
+    let mut hexes: Vec<Hex> = Vec::new();
+    for q_cord in -radius .. radius + 1 {
+        for r_cord in cmp::max(-radius,-q_cord - radius) .. cmp::min(radius,-q_cord + radius) + 1 {
+            hexes.push(Hex{ q: q_cord + center.q, r: r_cord + center.r,});
+        }
+    }
+    hexes
+}
+
+//check_hex_ball
+//Purpose:
+//    Returns a GeometryError if the radius is negative, the coordinates of the hexes within the radius of the center
+//    do not fit in an isize, or count of them does not fit in a Vec.
+//Pre-conditions:
+//    None.
+fn check_hex_ball(center: Hex, radius: isize, count: Option<usize>) -> Result<(), GeometryError>{
+    if radius < 0 {
+        return Err(GeometryError::NegativeRadius);
+    }
+    for coordinate in [center.q, center.r].iter() {
+        if coordinate.checked_add(radius).is_none() || coordinate.checked_sub(radius).is_none() {
+            return Err(GeometryError::RangeTooLarge);
+        }
+    }
+    check_cell_count::<Hex>(count)
+}
+
+//try_hex_ring
+//Purpose:
+//    Returns the same hexes as hex_ring, or a GeometryError if the radius is negative or the hexes do not fit in an isize.
+//Pre-conditions:
+//    None.
+pub fn try_hex_ring(center: Hex, radius: isize) -> Result<Vec<Hex>, GeometryError>{
+    check_hex_ball(center,radius,(radius as usize).checked_mul(6))?;
+    Ok(hex_ring(center,radius))
+}
+
+//try_hex_cells_inside_ball
+//Purpose:
+//    Returns the same hexes as hex_cells_inside_ball, or a GeometryError if the radius is negative or the hexes do not fit in an isize.
+//Pre-conditions:
+//    None.
+pub fn try_hex_cells_inside_ball(center: Hex, radius: isize) -> Result<Vec<Hex>, GeometryError>{
+    let count = (radius as usize).checked_add(1)
+        .and_then(|next| next.checked_mul(radius as usize))
+        .and_then(|product| product.checked_mul(3))
+        .and_then(|count| count.checked_add(1));
+    check_hex_ball(center,radius,count)?;
+    Ok(hex_cells_inside_ball(center,radius))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort;
+
+    #[test]
+    fn hex_coordinates(){
+        let hex = Hex{ q: 2, r: -5,};
+        assert_eq!(CubeHex::from(hex),CubeHex{ q: 2, r: -5, s: 3,});
+        assert_eq!(Hex::from(CubeHex::from(hex)),hex);
+        assert_eq!(hex_distance(hex,hex),0);
+        assert_eq!(hex_distance(Hex{ q: 0, r: 0,},Hex{ q: 3, r: -1,}),3);
+        assert_eq!(hex_distance(Hex{ q: 0, r: 0,},Hex{ q: 2, r: 2,}),4);
+        assert_eq!(hex_distance(Hex{ q: -1, r: 4,},Hex{ q: 2, r: 2,}),3);
+    }
+
+    #[test]
+    fn hex_lines(){
+        let origin = Hex{ q: 0, r: 0,};
+        assert_eq!(hex_line(origin,origin),vec![origin]);
+        assert_eq!(hex_line(origin,Hex{ q: 3, r: 0,}),vec![origin,Hex{ q: 1, r: 0,},Hex{ q: 2, r: 0,},Hex{ q: 3, r: 0,}]);
+        //runs along the edge between (1,0) and (0,1), the tie goes to the larger q + 2r - 3s.
+        assert_eq!(hex_line(origin,Hex{ q: 1, r: 1,}),vec![origin,Hex{ q: 0, r: 1,},Hex{ q: 1, r: 1,}]);
+        assert_eq!(hex_cells_on_line_segment(origin,Hex{ q: 1, r: 1,}),vec![origin,Hex{ q: 0, r: 1,},Hex{ q: 1, r: 0,},Hex{ q: 1, r: 1,}]);
+        assert_eq!(hex_cells_on_line_segment(origin,Hex{ q: 2, r: -1,}),vec![origin,Hex{ q: 1, r: -1,},Hex{ q: 1, r: 0,},Hex{ q: 2, r: -1,}]);
+        assert_eq!(hex_cells_on_line_segment(origin,Hex{ q: 2, r: 0,}),vec![origin,Hex{ q: 1, r: 0,},Hex{ q: 2, r: 0,}]);
+        for q1 in -3 .. 4 {
+            for r1 in -3 .. 4 {
+                for q2 in -3 .. 4 {
+                    for r2 in -3 .. 4 {
+                        let hex1 = Hex{ q: q1, r: r1,};
+                        let hex2 = Hex{ q: q2, r: r2,};
+                        let line = hex_line(hex1,hex2);
+                        assert_eq!(line.len() as isize,hex_distance(hex1,hex2) + 1);
+                        assert_eq!((line[0],line[line.len() - 1]),(hex1,hex2));
+                        for pair in line.windows(2) {
+                            assert_eq!(hex_distance(pair[0],pair[1]),1);
+                        }
+                        let mut reversed = hex_line(hex2,hex1);
+                        reversed.reverse();
+                        assert_eq!(line,reversed);
+                        let cells = hex_cells_on_line_segment(hex1,hex2);
+                        assert_eq!((cells[0],cells[cells.len() - 1]),(hex1,hex2));
+                        for pair in cells.windows(2) {
+                            assert_eq!(hex_distance(pair[0],pair[1]),1);
+                        }
+                        for hex in line {
+                            assert!(cells.contains(&hex));
+                        }
+                        assert_eq!(sort(cells),sort(hex_cells_on_line_segment(hex2,hex1)));
+                    }
+                }
+            }
+        }
+        //far from the origin the hexes are those near it, moved.
+        let far = Hex{ q: 1 << 62, r: -(1 << 61),};
+        let moved = |hexes: Vec<Hex>| -> Vec<Hex> { hexes.into_iter().map(|hex| Hex{ q: hex.q + far.q, r: hex.r + far.r,}).collect() };
+        for target in [Hex{ q: 2, r: -1,},Hex{ q: -3, r: 5,},Hex{ q: 7, r: 2,}].iter() {
+            let end = Hex{ q: far.q + target.q, r: far.r + target.r,};
+            assert_eq!(try_hex_line(far,end),Ok(moved(hex_line(origin,*target))));
+            assert_eq!(try_hex_cells_on_line_segment(far,end),Ok(moved(hex_cells_on_line_segment(origin,*target))));
+        }
+        let low = Hex{ q: isize::MIN, r: 0,};
+        let high = Hex{ q: isize::MAX, r: 0,};
+        assert_eq!(try_hex_line(low,high),Err(GeometryError::RangeTooLarge));
+        assert_eq!(try_hex_cells_on_line_segment(high,low),Err(GeometryError::RangeTooLarge));
+        assert_eq!(try_hex_line(high,Hex{ q: isize::MAX - 1, r: 1,}),Ok(vec![high,Hex{ q: isize::MAX - 1, r: 1,}]));
+    }
+
+    #[test]
+    fn hex_rings_and_balls(){
+        let center = Hex{ q: 1, r: -2,};
+        assert_eq!(hex_ring(center,0),vec![center]);
+        assert_eq!(hex_cells_inside_ball(center,0),vec![center]);
+        for radius in 1 .. 6 {
+            let ring = hex_ring(center,radius);
+            assert_eq!(ring.len() as isize,6*radius);
+            for (index, hex) in ring.iter().enumerate() {
+                assert_eq!(hex_distance(center,*hex),radius);
+                assert_eq!(hex_distance(*hex,ring[(index + 1) % ring.len()]),1);
+            }
+            let mut expected: Vec<Hex> = Vec::new();
+            for q_cord in -radius - 2 .. radius + 3 {
+                for r_cord in -radius - 4 .. radius + 1 {
+                    if hex_distance(center,Hex{ q: q_cord, r: r_cord,}) <= radius {
+                        expected.push(Hex{ q: q_cord, r: r_cord,});
+                    }
+                }
+            }
+            assert_eq!(sort(hex_cells_inside_ball(center,radius)),expected);
+            let rings: Vec<Hex> = (0 .. radius + 1).flat_map(|size| hex_ring(center,size)).collect();
+            assert_eq!(sort(rings),expected);
+        }
+        assert_eq!(try_hex_ring(center,2),Ok(hex_ring(center,2)));
+        assert_eq!(try_hex_cells_inside_ball(center,3),Ok(hex_cells_inside_ball(center,3)));
+        assert_eq!(try_hex_ring(center,-1),Err(GeometryError::NegativeRadius));
+        assert_eq!(try_hex_cells_inside_ball(Hex{ q: isize::MIN + 1, r: 0,},2),Err(GeometryError::RangeTooLarge));
+        assert_eq!(try_hex_cells_inside_ball(center,1 << 40),Err(GeometryError::RangeTooLarge));
+    }
+}
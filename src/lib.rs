@@ -0,0 +1,44 @@
+//Author: Everett Sullivan.
+//Date created: March 14th 2019
+//Purpose: Library of exact algorithms for finding the integer cells and points on lines and inside balls, ellipses, polygons and triangle meshes, on square, hex and triangle grids.
+
+pub mod error;
+pub mod rational;
+pub mod point;
+pub mod line;
+pub mod ball;
+pub mod ellipse;
+pub mod thick_line;
+pub mod polygon;
+pub mod mesh;
+pub mod mesh_file;
+pub mod traversal;
+pub mod hex;
+pub mod triangle;
+#[cfg(feature = "big-rational")]
+pub mod big_rational;
+
+pub use crate::error::GeometryError;
+pub use crate::hex::{CubeHex, Hex};
+pub use crate::point::{Point2D, Point3D, RationalPoint2D, RationalPoint3D};
+pub use crate::rational::{OverflowError, Rational, RationalNumber};
+pub use crate::triangle::Triangle;
+#[cfg(feature = "big-rational")]
+pub use crate::big_rational::BigRational;
+
+#[cfg(test)]
+fn sort<A, T>(mut array: A) -> A
+where
+    A: AsMut<[T]>,
+    T: Ord,
+{
+    let slice = array.as_mut();
+    slice.sort();
+
+    array
+}
+
+#[cfg(test)]
+fn rational(numerator: isize, denominator: isize) -> Rational {
+    Rational::new_rational_from_integers(numerator, denominator)
+}
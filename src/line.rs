@@ -0,0 +1,2156 @@
+//Purpose: Contains functions for finding the integer cells and integer points on line segments in 2D and 3D.
+//         Every function has a corresponding iterator which yields the cells (or points) one at a time,
+//         the functions simply collect their iterator.
+
+use std::cmp;
+use std::cmp::Ordering;
+
+use crate::point::{Point2D, Point3D, RationalPoint2D, RationalPoint3D};
+use crate::rational;
+use crate::error::{check_cell_count, GeometryError};
+use crate::rational::{OverflowError, Rational, RationalNumber};
+
+//check_cells_2d
+//Purpose:
+//    Returns GeometryError::RangeTooLarge if the integer cell walks can not follow the segment between the two 2D points without overflowing.
+//Pre-conditions:
+//    None.
+//Notes:
+//    The walks negate their endpoints and compare products of the run and rise, so no coordinate may be isize::MIN
+//    and 2*(|dx| + 1)*(|dy| + 1) must fit in an isize. The |dx| + |dy| + 1 cells must also fit in a Vec.
+fn check_cells_2d(point1: Point2D, point2: Point2D) -> Result<(), GeometryError>{
+    if [point1.x,point1.y,point2.x,point2.y].contains(&isize::MIN) {
+        return Err(GeometryError::RangeTooLarge);
+    }
+    let run = checked_distance(point1.x,point2.x)?;
+    let rise = checked_distance(point1.y,point2.y)?;
+    if run.checked_add(1).and_then(|run| run.checked_mul(rise.checked_add(1)?)).and_then(|area| area.checked_mul(2)).is_none() {
+        return Err(GeometryError::RangeTooLarge);
+    }
+    check_cell_count::<Point2D>((run as usize).checked_add(rise as usize + 1))
+}
+
+//check_cells_3d
+//Purpose:
+//    Returns GeometryError::RangeTooLarge if the |dx| + |dy| + |dz| + 1 cells of the segment between the two 3D points
+//    do not fit in a Vec.
+//Pre-conditions:
+//    None.
+//Notes:
+//    The walk steps one cell past the last cell in each direction, so every coordinate must have an absolute value less than isize::MAX.
+fn check_cells_3d(point1: Point3D, point2: Point3D) -> Result<(), GeometryError>{
+    if [point1.x,point1.y,point1.z,point2.x,point2.y,point2.z].iter().any(|coordinate| *coordinate <= -isize::MAX || *coordinate == isize::MAX) {
+        return Err(GeometryError::RangeTooLarge);
+    }
+    let dx = checked_distance(point1.x,point2.x)? as usize;
+    let dy = checked_distance(point1.y,point2.y)? as usize;
+    let dz = checked_distance(point1.z,point2.z)? as usize;
+    check_cell_count::<Point3D>(dx.checked_add(dy).and_then(|sum| sum.checked_add(dz)).and_then(|sum| sum.checked_add(1)))
+}
+
+//check_points
+//Purpose:
+//    Returns GeometryError::RangeTooLarge if the differences between the given coordinates do not fit in an isize,
+//    or the gcd(|dx|,|dy|,...) + 1 points of type T on the segment do not fit in a Vec.
+//Pre-conditions:
+//    None.
+fn check_points<T>(coordinates1: &[isize], coordinates2: &[isize]) -> Result<(), GeometryError>{
+    let mut common_factor = 0;
+    for (a, b) in coordinates1.iter().zip(coordinates2.iter()) {
+        common_factor = rational::gcd(common_factor,checked_distance(*a,*b)? as usize);
+    }
+    check_cell_count::<T>(common_factor.checked_add(1))
+}
+
+//checked_distance
+//Purpose:
+//    Returns |b - a|, or GeometryError::RangeTooLarge if it does not fit in an isize.
+//Pre-conditions:
+//    None.
+fn checked_distance(a: isize, b: isize) -> Result<isize, GeometryError>{
+    b.checked_sub(a).and_then(isize::checked_abs).ok_or(GeometryError::RangeTooLarge)
+}
+
+//CellsOnSegment2D
+//Purpose:
+//    Iterator over all integer cells (x,y) from a grid that lie on the line segment given by the two 2D points.
+//    The cells are yielded column by column starting from the left most point (the lower point if the segment is vertical).
+//    When built with new_ordered the cells are yielded from point1 to point2 instead, each cell touching the one before it.
+//Notes:
+//    The number of cells is known in advance, it is |dx| + |dy| + 1 minus the number of grid corners the segment passes through.
+pub struct CellsOnSegment2D {
+    left_point: Point2D,
+    sign: isize, //every cell is negated when sign is -1
+    slope_sign: isize,
+    slope: Rational,
+    run: isize,
+    rise: isize,
+    column: isize, //offset of the current column from left_point.x
+    y_cord: isize, //next (unsigned) height in the current column
+    y_end: isize, //last (unsigned) height in the current column
+    next_height: isize, //first (unsigned) height of the next column
+    remaining: usize,
+}
+
+impl CellsOnSegment2D {
+    pub fn new(point1: Point2D, point2: Point2D) -> CellsOnSegment2D{
+        let left_point = cmp::min(point1,point2);
+        let right_point = cmp::max(point1,point2);
+        let run = right_point.x - left_point.x;
+        let rise = (right_point.y - left_point.y).abs();
+        let slope_sign = if right_point.y < left_point.y { -1 } else { 1 };
+        let slope = if run == 0 {
+            Rational::new_rational_from_integer(0)
+        }else{
+            Rational::new_rational_from_integers(rise,run)
+        };
+        //The segment passes through a grid corner only when the reduced run and rise are both odd,
+        //in which case it does so once per repetition of the reduced step.
+        let common_factor = rational::gcd(run as usize,rise as usize);
+        let mut corners = 0;
+        if (common_factor != 0) && ((run as usize/common_factor) % 2 == 1) && ((rise as usize/common_factor) % 2 == 1) {
+            corners = common_factor;
+        }
+        let mut cells = CellsOnSegment2D{
+            left_point,
+            sign: 1,
+            slope_sign,
+            slope,
+            run,
+            rise,
+            column: 0,
+            y_cord: 0,
+            y_end: 0,
+            next_height: 0,
+            remaining: (run + rise + 1) as usize - corners,
+        };
+        cells.start_column();
+        cells
+    }
+
+    //The cells of a segment are symmetric under negation, so walking the negated segment from its left most point
+    //and negating each cell walks the segment from its right most point.
+    pub fn new_ordered(point1: Point2D, point2: Point2D) -> CellsOnSegment2D{
+        if point1 <= point2 {
+            return CellsOnSegment2D::new(point1,point2);
+        }
+        let mut cells = CellsOnSegment2D::new(-point1,-point2);
+        cells.sign = -1;
+        cells
+    }
+
+    fn start_column(&mut self){
+        self.y_cord = self.next_height;
+        if self.column < self.run {
+            let line_intersection_between_integers = self.slope*Rational::new_rational_from_integers(self.column*2+1,2);
+            let kitty_coner = line_intersection_between_integers.denominator() == 2;
+            self.y_end = line_intersection_between_integers.rational_ceil(2).floor();
+            self.next_height = self.y_end;
+            if kitty_coner {
+                self.next_height += 1;
+            }
+        }else{
+            self.y_end = self.rise;
+        }
+    }
+}
+
+impl Iterator for CellsOnSegment2D {
+    type Item = Point2D;
+
+    fn next(&mut self) -> Option<Point2D> {
+        while self.remaining > 0 {
+            if self.y_cord <= self.y_end {
+                let square = Point2D{ x: self.sign*(self.column + self.left_point.x), y: self.sign*(self.slope_sign*self.y_cord + self.left_point.y),};
+                self.y_cord += 1;
+                self.remaining -= 1;
+                return Some(square);
+            }
+            self.column += 1;
+            self.start_column();
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for CellsOnSegment2D {}
+
+//integer_cells_on_line_segment_2d
+//Purpose:
+//    Returns all integer cells (x,y) from a grid that lie on the line segment given by the two 2D points.
+//    Which is to say it returns all integer points (x,y) that are within .5 (using the L infinity metric) of the line segment given by the two 2D points.
+//Pre-conditions:
+//    None.
+pub fn integer_cells_on_line_segment_2d(point1: Point2D, point2: Point2D) -> Vec<Point2D>{
+    CellsOnSegment2D::new(point1,point2).collect()
+}
+
+//try_integer_cells_on_line_segment_2d
+//Purpose:
+//    Returns the same cells as integer_cells_on_line_segment_2d, or GeometryError::RangeTooLarge if the segment is too long to walk.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_line_segment_2d(point1: Point2D, point2: Point2D) -> Result<Vec<Point2D>, GeometryError>{
+    check_cells_2d(point1,point2)?;
+    Ok(integer_cells_on_line_segment_2d(point1,point2))
+}
+
+//integer_cells_on_line_segment_2d_ordered
+//Purpose:
+//    Returns the same cells as integer_cells_on_line_segment_2d, ordered from point1 to point2.
+//    Every cell shares an edge or a corner with the cell before it.
+//Pre-conditions:
+//    None.
+pub fn integer_cells_on_line_segment_2d_ordered(point1: Point2D, point2: Point2D) -> Vec<Point2D>{
+    CellsOnSegment2D::new_ordered(point1,point2).collect()
+}
+
+//try_integer_cells_on_line_segment_2d_ordered
+//Purpose:
+//    Returns the same cells as integer_cells_on_line_segment_2d_ordered, or GeometryError::RangeTooLarge if the segment is too long to walk.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_line_segment_2d_ordered(point1: Point2D, point2: Point2D) -> Result<Vec<Point2D>, GeometryError>{
+    check_cells_2d(point1,point2)?;
+    Ok(integer_cells_on_line_segment_2d_ordered(point1,point2))
+}
+
+//LineMode
+//Purpose:
+//    Selects which cells of a grid are considered to lie on a line segment.
+//    Supercover: every cell the segment passes through (see integer_cells_on_line_segment_2d).
+//    EightConnected: one cell for each integer along the axis the segment changes the most in, the classic Bresenham line.
+//                    The other coordinate is the integer nearest the segment, ties go to the smaller integer.
+//    FourConnected: every cell the segment passes through, joined by an extra cell wherever the segment passes exactly through
+//                   a grid corner so that consecutive cells always share an edge. Of the two cells touching that corner
+//                   which are not on the segment the lower one (smaller y) is used.
+//Notes:
+//    The tie-breaking rules do not depend on the direction of the segment, so swapping the endpoints gives the same cells.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LineMode {
+    Supercover,
+    EightConnected,
+    FourConnected,
+}
+
+//ConnectedCellsOnSegment2D
+//Purpose:
+//    Iterator over the integer cells (x,y) from a grid that lie on the line segment given by the two 2D points using the given LineMode.
+//    The cells are yielded from point1 to point2, each cell touching the one before it.
+//Notes:
+//    The number of cells is known in advance, it is max(|dx|,|dy|) + 1 for EightConnected and |dx| + |dy| + 1 for FourConnected.
+pub struct ConnectedCellsOnSegment2D {
+    walk: ConnectedWalk,
+}
+
+enum ConnectedWalk {
+    Supercover(CellsOnSegment2D),
+    Steps(ConnectedSteps),
+}
+
+struct ConnectedSteps {
+    start: Point2D,
+    current: Point2D,
+    step_x: isize,
+    step_y: isize,
+    run: isize, //|dx|
+    rise: isize, //|dy|
+    x_steps: isize,
+    y_steps: isize,
+    mode: LineMode,
+    remaining: usize,
+}
+
+//round_half_down
+//Purpose:
+//    Returns the integer nearest to numerator/denominator, if there are two the smaller one is returned.
+//Pre-conditions:
+//    denominator is positive.
+fn round_half_down(numerator: isize, denominator: isize) -> isize{
+    -(-(2*numerator - denominator)).div_euclid(2*denominator) //ceil((2*numerator - denominator)/(2*denominator))
+}
+
+impl ConnectedSteps {
+    //The cell of an EightConnected line after the given number of steps along the major axis.
+    fn eight_connected_cell(&self, steps: isize) -> Point2D{
+        if self.run == 0 && self.rise == 0 {
+            self.start
+        }else if self.run >= self.rise {
+            Point2D{ x: self.start.x + self.step_x*steps, y: self.start.y + round_half_down(self.step_y*self.rise*steps,self.run),}
+        }else{
+            Point2D{ x: self.start.x + round_half_down(self.step_x*self.run*steps,self.rise), y: self.start.y + self.step_y*steps,}
+        }
+    }
+
+    //Moves current to the next cell of a FourConnected line.
+    fn four_connected_step(&mut self){
+        let step_in_x;
+        if self.x_steps == self.run {
+            step_in_x = false;
+        }else if self.y_steps == self.rise {
+            step_in_x = true;
+        }else{
+            //compare where the segment leaves the current cell through a vertical side and a horizontal side.
+            match ((2*self.x_steps + 1)*self.rise).cmp(&((2*self.y_steps + 1)*self.run)){
+                Ordering::Less => step_in_x = true,
+                Ordering::Greater => step_in_x = false,
+                Ordering::Equal => step_in_x = self.step_y > 0, //through a corner, step to the lower of the two cells.
+            }
+        }
+        if step_in_x {
+            self.x_steps += 1;
+            self.current.x += self.step_x;
+        }else{
+            self.y_steps += 1;
+            self.current.y += self.step_y;
+        }
+    }
+}
+
+impl ConnectedCellsOnSegment2D {
+    pub fn new(point1: Point2D, point2: Point2D, mode: LineMode) -> ConnectedCellsOnSegment2D{
+        if mode == LineMode::Supercover {
+            return ConnectedCellsOnSegment2D{ walk: ConnectedWalk::Supercover(CellsOnSegment2D::new_ordered(point1,point2)) };
+        }
+        let run = (point2.x - point1.x).abs();
+        let rise = (point2.y - point1.y).abs();
+        let remaining = if mode == LineMode::EightConnected {
+            cmp::max(run,rise) + 1
+        }else{
+            run + rise + 1
+        };
+        ConnectedCellsOnSegment2D{ walk: ConnectedWalk::Steps(ConnectedSteps{
+            start: point1,
+            current: point1,
+            step_x: (point2.x - point1.x).signum(),
+            step_y: (point2.y - point1.y).signum(),
+            run,
+            rise,
+            x_steps: 0,
+            y_steps: 0,
+            mode,
+            remaining: remaining as usize,
+        })}
+    }
+}
+
+impl Iterator for ConnectedCellsOnSegment2D {
+    type Item = Point2D;
+
+    fn next(&mut self) -> Option<Point2D> {
+        match self.walk {
+            ConnectedWalk::Supercover(ref mut cells) => cells.next(),
+            ConnectedWalk::Steps(ref mut steps) => {
+                if steps.remaining == 0 {
+                    return None;
+                }
+                let square;
+                if steps.mode == LineMode::EightConnected {
+                    square = steps.eight_connected_cell(steps.x_steps);
+                    steps.x_steps += 1;
+                }else{
+                    square = steps.current;
+                    if steps.remaining > 1 {
+                        steps.four_connected_step();
+                    }
+                }
+                steps.remaining -= 1;
+                Some(square)
+            },
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.walk {
+            ConnectedWalk::Supercover(ref cells) => cells.size_hint(),
+            ConnectedWalk::Steps(ref steps) => (steps.remaining, Some(steps.remaining)),
+        }
+    }
+}
+
+impl ExactSizeIterator for ConnectedCellsOnSegment2D {}
+
+//integer_cells_on_line_segment_2d_mode
+//Purpose:
+//    Returns the integer cells (x,y) from a grid that lie on the line segment given by the two 2D points using the given LineMode,
+//    ordered from point1 to point2.
+//Pre-conditions:
+//    None.
+pub fn integer_cells_on_line_segment_2d_mode(point1: Point2D, point2: Point2D, mode: LineMode) -> Vec<Point2D>{
+    ConnectedCellsOnSegment2D::new(point1,point2,mode).collect()
+}
+
+//try_integer_cells_on_line_segment_2d_mode
+//Purpose:
+//    Returns the same cells as integer_cells_on_line_segment_2d_mode, or GeometryError::RangeTooLarge if the segment is too long to walk.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_line_segment_2d_mode(point1: Point2D, point2: Point2D, mode: LineMode) -> Result<Vec<Point2D>, GeometryError>{
+    check_cells_2d(point1,point2)?;
+    Ok(integer_cells_on_line_segment_2d_mode(point1,point2,mode))
+}
+
+//ScaledCellsOnSegment2D
+//Purpose:
+//    Iterator over all integer cells (x,y) from a grid that lie on the line segment given by the two 2D points,
+//    where all cells are scaled by the given radius.
+//Notes:
+//    See integer_cells_on_line_segment_2d_rational_scale for how the radius is treated.
+//    The number of cells is only known in advance when the radius is clamped or the segment is horizontal or vertical.
+//    All arithmetic is checked, if a computation overflows the walk stops early and overflowed returns true.
+pub struct ScaledCellsOnSegment2D<N = Rational> {
+    walk: ScaledWalk<N>,
+}
+
+enum ScaledWalk<N> {
+    Points(PointsOnSegment2D),
+    Cells(CellsOnSegment2D),
+    Columns(ScaledColumns<N>),
+}
+
+struct ScaledColumns<N> {
+    left_point: Point2D,
+    slope_sign: isize,
+    slope: N,
+    radius: N,
+    run: isize,
+    rise: isize,
+    column: isize, //offset of the current column from left_point.x
+    y_cord: isize, //next (unsigned) height in the current column
+    y_end: isize, //last (unsigned) height in the current column
+    overflowed: bool,
+}
+
+impl<N: RationalNumber> ScaledColumns<N> {
+    fn start_column(&mut self) -> Option<()>{
+        let slope = &self.slope;
+        let radius = &self.radius;
+        let column = N::from_integer(self.column);
+        if self.column == 0 {
+            self.y_cord = 0;
+        }else{
+            self.y_cord = slope.checked_mul(&column.checked_sub(radius)?)?.checked_sub(radius)?.checked_floor()? + 1;
+        }
+        if self.column < self.run {
+            self.y_end = slope.checked_mul(&column.checked_add(radius)?)?.checked_add(radius)?.checked_ceil()? - 1;
+        }else{
+            self.y_end = self.rise;
+        }
+        Some(())
+    }
+}
+
+impl<N: RationalNumber> ScaledCellsOnSegment2D<N> {
+    pub fn new(point1: Point2D, point2: Point2D, radius: N) -> ScaledCellsOnSegment2D<N>{
+        if radius <= N::from_integer(0) {
+            return ScaledCellsOnSegment2D{ walk: ScaledWalk::Points(PointsOnSegment2D::new(point1,point2)) };
+        } else if radius >= N::from_integers(1,2) {
+            return ScaledCellsOnSegment2D{ walk: ScaledWalk::Cells(CellsOnSegment2D::new(point1,point2)) };
+        }
+        if (point1.x == point2.x) || (point1.y == point2.y) { //every cell along a horizontal or vertical segment is hit.
+            return ScaledCellsOnSegment2D{ walk: ScaledWalk::Points(PointsOnSegment2D::new(point1,point2)) };
+        }
+        let left_point = cmp::min(point1,point2);
+        let right_point = cmp::max(point1,point2);
+        let run = right_point.x - left_point.x;
+        let rise = (right_point.y - left_point.y).abs();
+        let slope_sign = if right_point.y < left_point.y { -1 } else { 1 };
+        let mut columns = ScaledColumns{
+            left_point,
+            slope_sign,
+            slope: N::from_integers(rise,run),
+            radius,
+            run,
+            rise,
+            column: 0,
+            y_cord: 0,
+            y_end: 0,
+            overflowed: false,
+        };
+        if columns.start_column().is_none() {
+            columns.overflowed = true;
+        }
+        ScaledCellsOnSegment2D{ walk: ScaledWalk::Columns(columns) }
+    }
+
+    //Returns true if the walk was stopped because a computation overflowed.
+    pub fn overflowed(&self) -> bool{
+        match self.walk {
+            ScaledWalk::Columns(ref columns) => columns.overflowed,
+            _ => false,
+        }
+    }
+}
+
+impl<N: RationalNumber> Iterator for ScaledCellsOnSegment2D<N> {
+    type Item = Point2D;
+
+    fn next(&mut self) -> Option<Point2D> {
+        match self.walk {
+            ScaledWalk::Points(ref mut points) => points.next(),
+            ScaledWalk::Cells(ref mut cells) => cells.next(),
+            ScaledWalk::Columns(ref mut columns) => {
+                while columns.column <= columns.run && !columns.overflowed {
+                    if columns.y_cord <= columns.y_end {
+                        let square = Point2D{ x: columns.column + columns.left_point.x, y: columns.slope_sign*columns.y_cord + columns.left_point.y,};
+                        columns.y_cord += 1;
+                        return Some(square);
+                    }
+                    columns.column += 1;
+                    if columns.column <= columns.run && columns.start_column().is_none() {
+                        columns.overflowed = true;
+                    }
+                }
+                None
+            },
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.walk {
+            ScaledWalk::Points(ref points) => points.size_hint(),
+            ScaledWalk::Cells(ref cells) => cells.size_hint(),
+            ScaledWalk::Columns(_) => (0, None),
+        }
+    }
+}
+
+//integer_cells_on_line_segment_2d_rational_scale
+//Purpose:
+//    Returns all integer cells (x,y) from a grid that lie on the line segment given by the two 2D points.
+//    All cells are scaled by the given radius.
+//    Which is to say it returns all integer points (x,y) that are within the given rational radius(using the L infinity metric)
+//    of the line segment given by the two 2D points.
+//Pre-conditions:
+//    None
+//Notes:
+//    The rational radius is expected to be between 0 and .5 inclusive.
+//    If the radius is less than 0, it is treated as 0.
+//    If the radius is greater than .5, it it treated as .5.
+//    For larger radii, or the L1 and L2 metrics, see thick_line::integer_cells_on_thick_line_segment_2d_metric.
+pub fn integer_cells_on_line_segment_2d_rational_scale<N: RationalNumber>(point1: Point2D, point2: Point2D, radius: N) -> Vec<Point2D>{
+    match checked_integer_cells_on_line_segment_2d_rational_scale(point1,point2,radius) {
+        Ok(cells) => cells,
+        Err(_) => panic!("Overflow while finding the cells on a line segment (Rationals)!"),
+    }
+}
+
+//checked_integer_cells_on_line_segment_2d_rational_scale
+//Purpose:
+//    Returns the same cells as integer_cells_on_line_segment_2d_rational_scale, or an OverflowError if a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn checked_integer_cells_on_line_segment_2d_rational_scale<N: RationalNumber>(point1: Point2D, point2: Point2D, radius: N) -> Result<Vec<Point2D>, OverflowError>{
+    let mut walk = ScaledCellsOnSegment2D::new(point1,point2,radius);
+    let cells: Vec<Point2D> = walk.by_ref().collect();
+    if walk.overflowed() {
+        Err(OverflowError)
+    }else{
+        Ok(cells)
+    }
+}
+
+//try_integer_cells_on_line_segment_2d_rational_scale
+//Purpose:
+//    Returns the same cells as integer_cells_on_line_segment_2d_rational_scale, or a GeometryError if the radius is negative,
+//    the segment is too long to walk or a computation overflows.
+//Pre-conditions:
+//    None.
+//Notes:
+//    Unlike integer_cells_on_line_segment_2d_rational_scale a negative radius is rejected rather than treated as 0.
+pub fn try_integer_cells_on_line_segment_2d_rational_scale<N: RationalNumber>(point1: Point2D, point2: Point2D, radius: N) -> Result<Vec<Point2D>, GeometryError>{
+    if radius < N::from_integer(0) {
+        return Err(GeometryError::NegativeRadius);
+    }
+    check_cells_2d(point1,point2)?;
+    Ok(checked_integer_cells_on_line_segment_2d_rational_scale(point1,point2,radius)?)
+}
+
+//PointsOnSegment2D
+//Purpose:
+//    Iterator over all integer points (x,y) that are on the line segment given by the two 2D points.
+//    The points are yielded starting from the left most point (the lower point if the segment is vertical).
+//    When built with new_ordered the points are yielded from point1 to point2 instead.
+pub struct PointsOnSegment2D {
+    current: Point2D,
+    step: Point2D,
+    remaining: usize,
+}
+
+impl PointsOnSegment2D {
+    pub fn new(point1: Point2D, point2: Point2D) -> PointsOnSegment2D{
+        PointsOnSegment2D::new_ordered(cmp::min(point1,point2),cmp::max(point1,point2))
+    }
+
+    pub fn new_ordered(point1: Point2D, point2: Point2D) -> PointsOnSegment2D{
+        let run = point2.x - point1.x;
+        let rise = point2.y - point1.y;
+        let common_factor = rational::gcd(run.unsigned_abs(),rise.unsigned_abs());
+        let step = if common_factor == 0 {
+            Point2D{ x: 0, y: 0,}
+        }else{
+            Point2D{ x: run/(common_factor as isize), y: rise/(common_factor as isize),}
+        };
+        PointsOnSegment2D{
+            current: point1,
+            step,
+            remaining: common_factor + 1,
+        }
+    }
+}
+
+impl Iterator for PointsOnSegment2D {
+    type Item = Point2D;
+
+    fn next(&mut self) -> Option<Point2D> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let square = self.current;
+        self.remaining -= 1;
+        if self.remaining > 0 { //stepping past the last point could overflow.
+            self.current = Point2D{ x: self.current.x + self.step.x, y: self.current.y + self.step.y,};
+        }
+        Some(square)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for PointsOnSegment2D {}
+
+//integer_points_on_line_segment_2d
+//Purpose:
+//    Returns all integer points (x,y) that are  on the line segment given by the two 2D points.
+//Pre-conditions:
+//    None.
+pub fn integer_points_on_line_segment_2d(point1: Point2D, point2: Point2D) -> Vec<Point2D>{
+    PointsOnSegment2D::new(point1,point2).collect()
+}
+
+//try_integer_points_on_line_segment_2d
+//Purpose:
+//    Returns the same points as integer_points_on_line_segment_2d, or GeometryError::RangeTooLarge if the segment is too long.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_on_line_segment_2d(point1: Point2D, point2: Point2D) -> Result<Vec<Point2D>, GeometryError>{
+    check_points::<Point2D>(&[point1.x,point1.y],&[point2.x,point2.y])?;
+    Ok(integer_points_on_line_segment_2d(point1,point2))
+}
+
+//integer_points_on_line_segment_2d_ordered
+//Purpose:
+//    Returns the same points as integer_points_on_line_segment_2d, ordered from point1 to point2.
+//Pre-conditions:
+//    None.
+pub fn integer_points_on_line_segment_2d_ordered(point1: Point2D, point2: Point2D) -> Vec<Point2D>{
+    PointsOnSegment2D::new_ordered(point1,point2).collect()
+}
+
+//try_integer_points_on_line_segment_2d_ordered
+//Purpose:
+//    Returns the same points as integer_points_on_line_segment_2d_ordered, or GeometryError::RangeTooLarge if the segment is too long.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_on_line_segment_2d_ordered(point1: Point2D, point2: Point2D) -> Result<Vec<Point2D>, GeometryError>{
+    check_points::<Point2D>(&[point1.x,point1.y],&[point2.x,point2.y])?;
+    Ok(integer_points_on_line_segment_2d_ordered(point1,point2))
+}
+
+//RationalCellsOnSegment2D
+//Purpose:
+//    Iterator over all integer cells (x,y) from a grid that lie on the line segment given by the two rational 2D points.
+//    A cell is hit when the segment passes through its interior, so a segment which only touches the boundary of a cell does not hit it.
+//    The cells are yielded column by column starting from the left most point (the lower point if the segment is vertical).
+//    When built with new_ordered the cells are yielded from point1 to point2 instead, each cell touching the one before it.
+//Notes:
+//    If the segment is decreasing the heights are negated, walked upwards and negated back when yielded.
+//    All arithmetic is checked, if a computation overflows the walk stops early and overflowed returns true.
+pub struct RationalCellsOnSegment2D<N = Rational> {
+    left_point: RationalPoint2D<N>,
+    right_point: RationalPoint2D<N>,
+    sign: isize, //every cell is negated when sign is -1
+    y_sign: isize,
+    slope: N,
+    column: isize,
+    last_column: isize,
+    y_cord: isize, //next (unsigned) height in the current column
+    y_end: isize, //last (unsigned) height in the current column
+    overflowed: bool,
+}
+
+//lower_cell
+//Purpose:
+//    Returns the index of the cell containing the given coordinate, a coordinate between two cells belongs to the higher one.
+//Pre-conditions:
+//    None.
+//Notes:
+//    Returns None if the index does not fit in an isize.
+fn lower_cell<N: RationalNumber>(coordinate: &N) -> Option<isize>{
+    coordinate.checked_add(&N::from_integers(1,2))?.checked_floor()
+}
+
+//upper_cell
+//Purpose:
+//    Returns the index of the cell containing the given coordinate, a coordinate between two cells belongs to the lower one.
+//Pre-conditions:
+//    None.
+//Notes:
+//    Returns None if the index does not fit in an isize.
+fn upper_cell<N: RationalNumber>(coordinate: &N) -> Option<isize>{
+    coordinate.checked_sub(&N::from_integers(1,2))?.checked_ceil()
+}
+
+impl<N: RationalNumber> RationalCellsOnSegment2D<N> {
+    pub fn new(point1: RationalPoint2D<N>, point2: RationalPoint2D<N>) -> RationalCellsOnSegment2D<N>{
+        let mut left_point;
+        let mut right_point;
+        if (&point1.x, &point1.y) <= (&point2.x, &point2.y) {
+            left_point = point1;
+            right_point = point2;
+        }else{
+            left_point = point2;
+            right_point = point1;
+        }
+        let mut y_sign = 1;
+        if left_point.y > right_point.y {
+            y_sign = -1;
+            left_point.y = -left_point.y;
+            right_point.y = -right_point.y;
+        }
+        let mut cells = RationalCellsOnSegment2D{
+            left_point,
+            right_point,
+            sign: 1,
+            y_sign,
+            slope: N::from_integer(0),
+            column: 0,
+            last_column: -1,
+            y_cord: 0,
+            y_end: -1,
+            overflowed: false,
+        };
+        if cells.start_walk().is_none() {
+            cells.overflowed = true;
+            cells.column = 0;
+            cells.last_column = -1;
+        }
+        cells
+    }
+
+    //The cells of a segment are symmetric under negation, so walking the negated segment from its left most point
+    //and negating each cell walks the segment from its right most point.
+    pub fn new_ordered(point1: RationalPoint2D<N>, point2: RationalPoint2D<N>) -> RationalCellsOnSegment2D<N>{
+        if (&point1.x, &point1.y) <= (&point2.x, &point2.y) {
+            return RationalCellsOnSegment2D::new(point1,point2);
+        }
+        let mut cells = RationalCellsOnSegment2D::new(-point1,-point2);
+        cells.sign = -1;
+        cells
+    }
+
+    //Returns true if the walk was stopped because a computation overflowed.
+    pub fn overflowed(&self) -> bool{
+        self.overflowed
+    }
+
+    fn start_walk(&mut self) -> Option<()>{
+        if self.left_point.x != self.right_point.x {
+            self.slope = self.right_point.y.checked_sub(&self.left_point.y)?.checked_div(&self.right_point.x.checked_sub(&self.left_point.x)?)?;
+        }
+        self.column = lower_cell(&self.left_point.x)?; // a point between squares belongs to the square on its right.
+        self.last_column = upper_cell(&self.right_point.x)?; // a point between squares belongs to the square on its left.
+        if (self.left_point.y == self.right_point.y) && self.left_point.y.checked_add(&N::from_integers(1,2))?.is_integer() { // if we are between squares, nothing is hit.
+            self.last_column = self.column - 1;
+        }
+        if self.column <= self.last_column {
+            self.start_column()?;
+        }
+        Some(())
+    }
+
+    fn start_column(&mut self) -> Option<()>{
+        let half = N::from_integers(1,2);
+        let column = N::from_integer(self.column);
+        let x_low = cmp::max(&self.left_point.x, &column.checked_sub(&half)?).clone();
+        let x_high = cmp::min(&self.right_point.x, &column.checked_add(&half)?).clone();
+        let y_low = if x_low == self.left_point.x {
+            self.left_point.y.clone()
+        }else{
+            self.left_point.y.checked_add(&self.slope.checked_mul(&x_low.checked_sub(&self.left_point.x)?)?)?
+        };
+        let y_high = if x_high == self.right_point.x {
+            self.right_point.y.clone()
+        }else{
+            self.left_point.y.checked_add(&self.slope.checked_mul(&x_high.checked_sub(&self.left_point.x)?)?)?
+        };
+        self.y_cord = lower_cell(&y_low)?;
+        self.y_end = upper_cell(&y_high)?;
+        Some(())
+    }
+}
+
+impl<N: RationalNumber> Iterator for RationalCellsOnSegment2D<N> {
+    type Item = Point2D;
+
+    fn next(&mut self) -> Option<Point2D> {
+        while self.column <= self.last_column {
+            if self.y_cord <= self.y_end {
+                let square = Point2D{ x: self.sign*self.column, y: self.sign*self.y_sign*self.y_cord,};
+                self.y_cord += 1;
+                return Some(square);
+            }
+            self.column += 1;
+            if self.column <= self.last_column && self.start_column().is_none() {
+                self.overflowed = true;
+                self.last_column = self.column - 1;
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.column > self.last_column {
+            return (0, Some(0));
+        }
+        //every column after the current one has at least one cell.
+        let in_column = cmp::max(self.y_end - self.y_cord + 1, 0);
+        ((self.last_column - self.column) as usize + in_column as usize, None)
+    }
+}
+
+//l_infinity_line_rational_endpoints
+//Purpose:
+//    Returns all integer points that are within .5 (using the L infinity metric) of the line given by the two 2D points.
+//Pre-conditions:
+//    None.
+//Notes:
+//    Points that are exactly .5 away are only included if the segment passes through the interior of their cell.
+pub fn l_infinity_line_rational_endpoints<N: RationalNumber>(point1: RationalPoint2D<N>, point2: RationalPoint2D<N>) -> Vec<Point2D>{
+    match checked_l_infinity_line_rational_endpoints(point1,point2) {
+        Ok(cells) => cells,
+        Err(_) => panic!("Overflow while finding the cells on a line segment (Rationals)!"),
+    }
+}
+
+//checked_l_infinity_line_rational_endpoints
+//Purpose:
+//    Returns the same points as l_infinity_line_rational_endpoints, or an OverflowError if a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn checked_l_infinity_line_rational_endpoints<N: RationalNumber>(point1: RationalPoint2D<N>, point2: RationalPoint2D<N>) -> Result<Vec<Point2D>, OverflowError>{
+    let mut walk = RationalCellsOnSegment2D::new(point1,point2);
+    let cells: Vec<Point2D> = walk.by_ref().collect();
+    if walk.overflowed() {
+        Err(OverflowError)
+    }else{
+        Ok(cells)
+    }
+}
+
+//l_infinity_line_rational_endpoints_ordered
+//Purpose:
+//    Returns the same points as l_infinity_line_rational_endpoints, ordered from point1 to point2.
+//    Every point is within 1 (using the L infinity metric) of the point before it.
+//Pre-conditions:
+//    None.
+pub fn l_infinity_line_rational_endpoints_ordered<N: RationalNumber>(point1: RationalPoint2D<N>, point2: RationalPoint2D<N>) -> Vec<Point2D>{
+    match checked_l_infinity_line_rational_endpoints_ordered(point1,point2) {
+        Ok(cells) => cells,
+        Err(_) => panic!("Overflow while finding the cells on a line segment (Rationals)!"),
+    }
+}
+
+//checked_l_infinity_line_rational_endpoints_ordered
+//Purpose:
+//    Returns the same points as l_infinity_line_rational_endpoints_ordered, or an OverflowError if a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn checked_l_infinity_line_rational_endpoints_ordered<N: RationalNumber>(point1: RationalPoint2D<N>, point2: RationalPoint2D<N>) -> Result<Vec<Point2D>, OverflowError>{
+    let mut walk = RationalCellsOnSegment2D::new_ordered(point1,point2);
+    let cells: Vec<Point2D> = walk.by_ref().collect();
+    if walk.overflowed() {
+        Err(OverflowError)
+    }else{
+        Ok(cells)
+    }
+}
+
+//approximate_point_2d
+//Purpose:
+//    Converts a 2D point given as a pair of f64 to a RationalPoint2D, using Rational::approximate on each coordinate.
+//Pre-conditions:
+//    None.
+fn approximate_point_2d(point: (f64, f64), max_denominator: usize) -> Option<RationalPoint2D>{
+    Some(RationalPoint2D{ x: Rational::approximate(point.0,max_denominator)?, y: Rational::approximate(point.1,max_denominator)?,})
+}
+
+//l_infinity_line_f64_endpoints
+//Purpose:
+//    Returns the same points as l_infinity_line_rational_endpoints for a line segment whose endpoints are given as pairs of f64.
+//    Each coordinate is first replaced by the closest rational whose denominator is at most max_denominator (see Rational::approximate),
+//    so the result only depends on the f64 values given and not on how they were rounded.
+//Pre-conditions:
+//    None.
+//Notes:
+//    Returns None if max_denominator is zero, if a coordinate is infinite, NaN or too large to approximate, or if the rasterizer overflows.
+//    Coordinates that are multiples of 1/max_denominator are used exactly, for example any f64 that is a multiple of 1/1024 when max_denominator is 1024.
+//    The rasterizer multiplies denominators together, so max_denominator should be kept small enough that their products fit in a usize.
+pub fn l_infinity_line_f64_endpoints(point1: (f64, f64), point2: (f64, f64), max_denominator: usize) -> Option<Vec<Point2D>>{
+    checked_l_infinity_line_rational_endpoints(approximate_point_2d(point1,max_denominator)?,approximate_point_2d(point2,max_denominator)?).ok()
+}
+
+//l_infinity_line_f64_endpoints_ordered
+//Purpose:
+//    Returns the same points as l_infinity_line_f64_endpoints, ordered from point1 to point2.
+//Pre-conditions:
+//    None.
+pub fn l_infinity_line_f64_endpoints_ordered(point1: (f64, f64), point2: (f64, f64), max_denominator: usize) -> Option<Vec<Point2D>>{
+    checked_l_infinity_line_rational_endpoints_ordered(approximate_point_2d(point1,max_denominator)?,approximate_point_2d(point2,max_denominator)?).ok()
+}
+
+//RationalCellsOnSegment3D
+//Purpose:
+//    Iterator over all integer cells (x,y,z) from a grid that lie on the line segment given by the two rational 3D points.
+//    A cell is hit when the segment passes through its interior, so a segment which only touches the boundary of a cell does not hit it.
+//    The segment is cut into slabs, one for each z-plane it passes through, and the cells of each slab
+//    are found from the projection of that piece of the segment onto the xy-plane.
+//    When built with new_ordered the cells are yielded from point1 to point2, each cell touching the one before it.
+//Notes:
+//    All arithmetic is checked, if a computation overflows the walk stops early and overflowed returns true.
+pub struct RationalCellsOnSegment3D<N = Rational> {
+    left_point: RationalPoint3D<N>, //z is negated when z_sign is -1
+    right_point: RationalPoint3D<N>, //z is negated when z_sign is -1
+    z_sign: isize,
+    dxdz: N,
+    dydz: N,
+    slab: isize, //(signed) height of the current slab
+    last_slab: isize,
+    ordered: bool,
+    squares: RationalCellsOnSegment2D<N>,
+    overflowed: bool,
+}
+
+impl<N: RationalNumber> RationalCellsOnSegment3D<N> {
+    pub fn new(point1: RationalPoint3D<N>, point2: RationalPoint3D<N>) -> RationalCellsOnSegment3D<N>{
+        if point1.x < point2.x {
+            RationalCellsOnSegment3D::from_slabs(point1,point2,false)
+        } else{
+            RationalCellsOnSegment3D::from_slabs(point2,point1,false)
+        }
+    }
+
+    pub fn new_ordered(point1: RationalPoint3D<N>, point2: RationalPoint3D<N>) -> RationalCellsOnSegment3D<N>{
+        RationalCellsOnSegment3D::from_slabs(point1,point2,true)
+    }
+
+    //Returns true if the walk was stopped because a computation overflowed.
+    pub fn overflowed(&self) -> bool{
+        self.overflowed || self.squares.overflowed()
+    }
+
+    //Walks the slabs from left_point to right_point, if ordered is true the cells within each slab
+    //are also walked from left_point to right_point.
+    fn from_slabs(mut left_point: RationalPoint3D<N>, mut right_point: RationalPoint3D<N>, ordered: bool) -> RationalCellsOnSegment3D<N>{
+        let mut z_sign = 1;
+        if left_point.z > right_point.z {
+            z_sign = -1;
+            left_point.z = -left_point.z;
+            right_point.z = -right_point.z;
+        }
+        let flat_point = RationalPoint2D{x: left_point.x.clone(), y: left_point.y.clone(),};
+        let mut cubes = RationalCellsOnSegment3D{
+            left_point,
+            right_point,
+            z_sign,
+            dxdz: N::from_integer(0),
+            dydz: N::from_integer(0),
+            slab: 0,
+            last_slab: -1,
+            ordered,
+            squares: RationalCellsOnSegment2D::new(flat_point.clone(),flat_point),
+            overflowed: false,
+        };
+        if cubes.start_walk().is_none() {
+            cubes.overflowed = true;
+            cubes.slab = 0;
+            cubes.last_slab = -1;
+        }
+        cubes
+    }
+
+    fn start_walk(&mut self) -> Option<()>{
+        if self.left_point.z != self.right_point.z {
+            let height = self.right_point.z.checked_sub(&self.left_point.z)?;
+            self.dxdz = self.right_point.x.checked_sub(&self.left_point.x)?.checked_div(&height)?;
+            self.dydz = self.right_point.y.checked_sub(&self.left_point.y)?.checked_div(&height)?;
+        }
+        self.slab = lower_cell(&self.left_point.z)?; // a point between slabs belongs to the slab above it.
+        self.last_slab = upper_cell(&self.right_point.z)?; // a point between slabs belongs to the slab below it.
+        if self.slab <= self.last_slab {
+            self.start_slab()?;
+        }
+        Some(())
+    }
+
+    //The point on the segment (projected onto the xy-plane) at the given (signed) height.
+    fn point_at_height(&self, height: &N) -> Option<RationalPoint2D<N>>{
+        let rise = height.checked_sub(&self.left_point.z)?;
+        Some(RationalPoint2D{
+            x: rise.checked_mul(&self.dxdz)?.checked_add(&self.left_point.x)?,
+            y: rise.checked_mul(&self.dydz)?.checked_add(&self.left_point.y)?,
+        })
+    }
+
+    fn start_slab(&mut self) -> Option<()>{
+        let half = N::from_integers(1,2);
+        let slab = N::from_integer(self.slab);
+        let bottom = slab.checked_sub(&half)?;
+        let top = slab.checked_add(&half)?;
+        let start = if bottom <= self.left_point.z {
+            RationalPoint2D{x: self.left_point.x.clone(), y: self.left_point.y.clone(),}
+        }else{
+            self.point_at_height(&bottom)?
+        };
+        let end = if top >= self.right_point.z {
+            RationalPoint2D{x: self.right_point.x.clone(), y: self.right_point.y.clone(),}
+        }else{
+            self.point_at_height(&top)?
+        };
+        if self.ordered {
+            self.squares = RationalCellsOnSegment2D::new_ordered(start,end);
+        }else{
+            self.squares = RationalCellsOnSegment2D::new(start,end);
+        }
+        Some(())
+    }
+}
+
+impl<N: RationalNumber> Iterator for RationalCellsOnSegment3D<N> {
+    type Item = Point3D;
+
+    fn next(&mut self) -> Option<Point3D> {
+        while self.slab <= self.last_slab {
+            if let Some(square) = self.squares.next() {
+                return Some(Point3D{ x: square.x, y: square.y, z: self.z_sign*self.slab});
+            }
+            if self.squares.overflowed() {
+                return None;
+            }
+            self.slab += 1;
+            if self.slab <= self.last_slab && self.start_slab().is_none() {
+                self.overflowed = true;
+                self.last_slab = self.slab - 1;
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.slab > self.last_slab {
+            return (0, Some(0));
+        }
+        let (in_slab, _) = self.squares.size_hint();
+        (in_slab, None)
+    }
+}
+
+//l_infinity_line_rational_endpoints_3d
+//Purpose:
+//    Returns all integer points (x,y,z) that are within .5 (using the L infinity metric) of the line segment given by the two rational 3D points.
+//Pre-conditions:
+//    None.
+//Notes:
+//    Points that are exactly .5 away are only included if the segment passes through the interior of their cell.
+pub fn l_infinity_line_rational_endpoints_3d<N: RationalNumber>(point1: RationalPoint3D<N>, point2: RationalPoint3D<N>) -> Vec<Point3D>{
+    match checked_l_infinity_line_rational_endpoints_3d(point1,point2) {
+        Ok(cells) => cells,
+        Err(_) => panic!("Overflow while finding the cells on a line segment (Rationals)!"),
+    }
+}
+
+//checked_l_infinity_line_rational_endpoints_3d
+//Purpose:
+//    Returns the same points as l_infinity_line_rational_endpoints_3d, or an OverflowError if a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn checked_l_infinity_line_rational_endpoints_3d<N: RationalNumber>(point1: RationalPoint3D<N>, point2: RationalPoint3D<N>) -> Result<Vec<Point3D>, OverflowError>{
+    let mut walk = RationalCellsOnSegment3D::new(point1,point2);
+    let cells: Vec<Point3D> = walk.by_ref().collect();
+    if walk.overflowed() {
+        Err(OverflowError)
+    }else{
+        Ok(cells)
+    }
+}
+
+//l_infinity_line_rational_endpoints_3d_ordered
+//Purpose:
+//    Returns the same points as l_infinity_line_rational_endpoints_3d, ordered from point1 to point2.
+//    Every point is within 1 (using the L infinity metric) of the point before it.
+//Pre-conditions:
+//    None.
+pub fn l_infinity_line_rational_endpoints_3d_ordered<N: RationalNumber>(point1: RationalPoint3D<N>, point2: RationalPoint3D<N>) -> Vec<Point3D>{
+    match checked_l_infinity_line_rational_endpoints_3d_ordered(point1,point2) {
+        Ok(cells) => cells,
+        Err(_) => panic!("Overflow while finding the cells on a line segment (Rationals)!"),
+    }
+}
+
+//checked_l_infinity_line_rational_endpoints_3d_ordered
+//Purpose:
+//    Returns the same points as l_infinity_line_rational_endpoints_3d_ordered, or an OverflowError if a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn checked_l_infinity_line_rational_endpoints_3d_ordered<N: RationalNumber>(point1: RationalPoint3D<N>, point2: RationalPoint3D<N>) -> Result<Vec<Point3D>, OverflowError>{
+    let mut walk = RationalCellsOnSegment3D::new_ordered(point1,point2);
+    let cells: Vec<Point3D> = walk.by_ref().collect();
+    if walk.overflowed() {
+        Err(OverflowError)
+    }else{
+        Ok(cells)
+    }
+}
+
+//approximate_point_3d
+//Purpose:
+//    Converts a 3D point given as a triple of f64 to a RationalPoint3D, using Rational::approximate on each coordinate.
+//Pre-conditions:
+//    None.
+fn approximate_point_3d(point: (f64, f64, f64), max_denominator: usize) -> Option<RationalPoint3D>{
+    Some(RationalPoint3D{
+        x: Rational::approximate(point.0,max_denominator)?,
+        y: Rational::approximate(point.1,max_denominator)?,
+        z: Rational::approximate(point.2,max_denominator)?,
+    })
+}
+
+//l_infinity_line_f64_endpoints_3d
+//Purpose:
+//    Returns the same points as l_infinity_line_rational_endpoints_3d for a line segment whose endpoints are given as triples of f64.
+//    Coordinates are converted as in l_infinity_line_f64_endpoints.
+//Pre-conditions:
+//    None.
+//Notes:
+//    Returns None if max_denominator is zero, if a coordinate is infinite, NaN or too large to approximate, or if the rasterizer overflows.
+pub fn l_infinity_line_f64_endpoints_3d(point1: (f64, f64, f64), point2: (f64, f64, f64), max_denominator: usize) -> Option<Vec<Point3D>>{
+    checked_l_infinity_line_rational_endpoints_3d(approximate_point_3d(point1,max_denominator)?,approximate_point_3d(point2,max_denominator)?).ok()
+}
+
+//l_infinity_line_f64_endpoints_3d_ordered
+//Purpose:
+//    Returns the same points as l_infinity_line_f64_endpoints_3d, ordered from point1 to point2.
+//Pre-conditions:
+//    None.
+pub fn l_infinity_line_f64_endpoints_3d_ordered(point1: (f64, f64, f64), point2: (f64, f64, f64), max_denominator: usize) -> Option<Vec<Point3D>>{
+    checked_l_infinity_line_rational_endpoints_3d_ordered(approximate_point_3d(point1,max_denominator)?,approximate_point_3d(point2,max_denominator)?).ok()
+}
+
+//CellsOnSegment3D
+//Purpose:
+//    Iterator over all integer cells (x,y,z) from a grid that lie on the line segment given by the two 3D points.
+//    This is RationalCellsOnSegment3D for integer endpoints.
+//    When built with new_ordered the cells are yielded from point1 to point2 instead, each cell touching the one before it.
+pub struct CellsOnSegment3D {
+    cubes: RationalCellsOnSegment3D,
+}
+
+impl CellsOnSegment3D {
+    pub fn new(point1: Point3D, point2: Point3D) -> CellsOnSegment3D{
+        CellsOnSegment3D{ cubes: RationalCellsOnSegment3D::new(RationalPoint3D::from(point1),RationalPoint3D::from(point2)) }
+    }
+
+    pub fn new_ordered(point1: Point3D, point2: Point3D) -> CellsOnSegment3D{
+        CellsOnSegment3D{ cubes: RationalCellsOnSegment3D::new_ordered(RationalPoint3D::from(point1),RationalPoint3D::from(point2)) }
+    }
+
+    //Returns true if the walk was stopped because a computation overflowed.
+    pub fn overflowed(&self) -> bool{
+        self.cubes.overflowed()
+    }
+}
+
+impl Iterator for CellsOnSegment3D {
+    type Item = Point3D;
+
+    fn next(&mut self) -> Option<Point3D> {
+        self.cubes.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cubes.size_hint()
+    }
+}
+
+//integer_cells_on_line_segment_3d
+//Purpose:
+//    Returns all integer cells (x,y,z) from a grid that lie on the line segment given by the two 3D points.
+//    Which is to say it returns all integer points (x,y,z) that are within .5 (using the L infinity metric) of the line segment given by the two 3D points.
+//Pre-conditions:
+//    None.
+pub fn integer_cells_on_line_segment_3d(point1: Point3D, point2: Point3D) -> Vec<Point3D>{
+    match checked_integer_cells_on_line_segment_3d(point1,point2) {
+        Ok(cells) => cells,
+        Err(_) => panic!("Overflow while finding the cells on a line segment (Rationals)!"),
+    }
+}
+
+//checked_integer_cells_on_line_segment_3d
+//Purpose:
+//    Returns the same cells as integer_cells_on_line_segment_3d, or an OverflowError if a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn checked_integer_cells_on_line_segment_3d(point1: Point3D, point2: Point3D) -> Result<Vec<Point3D>, OverflowError>{
+    let mut walk = CellsOnSegment3D::new(point1,point2);
+    let cells: Vec<Point3D> = walk.by_ref().collect();
+    if walk.overflowed() {
+        Err(OverflowError)
+    }else{
+        Ok(cells)
+    }
+}
+
+//try_integer_cells_on_line_segment_3d
+//Purpose:
+//    Returns the same cells as integer_cells_on_line_segment_3d, or a GeometryError if the segment is too long
+//    or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_line_segment_3d(point1: Point3D, point2: Point3D) -> Result<Vec<Point3D>, GeometryError>{
+    check_cells_3d(point1,point2)?;
+    Ok(checked_integer_cells_on_line_segment_3d(point1,point2)?)
+}
+
+//integer_cells_on_line_segment_3d_ordered
+//Purpose:
+//    Returns the same cells as integer_cells_on_line_segment_3d, ordered from point1 to point2.
+//    Every cell shares a face, an edge or a corner with the cell before it.
+//Pre-conditions:
+//    None.
+pub fn integer_cells_on_line_segment_3d_ordered(point1: Point3D, point2: Point3D) -> Vec<Point3D>{
+    match checked_integer_cells_on_line_segment_3d_ordered(point1,point2) {
+        Ok(cells) => cells,
+        Err(_) => panic!("Overflow while finding the cells on a line segment (Rationals)!"),
+    }
+}
+
+//checked_integer_cells_on_line_segment_3d_ordered
+//Purpose:
+//    Returns the same cells as integer_cells_on_line_segment_3d_ordered, or an OverflowError if a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn checked_integer_cells_on_line_segment_3d_ordered(point1: Point3D, point2: Point3D) -> Result<Vec<Point3D>, OverflowError>{
+    let mut walk = CellsOnSegment3D::new_ordered(point1,point2);
+    let cells: Vec<Point3D> = walk.by_ref().collect();
+    if walk.overflowed() {
+        Err(OverflowError)
+    }else{
+        Ok(cells)
+    }
+}
+
+//try_integer_cells_on_line_segment_3d_ordered
+//Purpose:
+//    Returns the same cells as integer_cells_on_line_segment_3d_ordered, or a GeometryError if the segment is too long
+//    or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_line_segment_3d_ordered(point1: Point3D, point2: Point3D) -> Result<Vec<Point3D>, GeometryError>{
+    check_cells_3d(point1,point2)?;
+    Ok(checked_integer_cells_on_line_segment_3d_ordered(point1,point2)?)
+}
+
+//PointsOnSegment3D
+//Purpose:
+//    Iterator over all integer points (x,y,z) that are on the line segment given by the two 3D points.
+//    The points are yielded starting from the smaller point (comparing x, then y, then z).
+//    When built with new_ordered the points are yielded from point1 to point2 instead.
+pub struct PointsOnSegment3D {
+    current: Point3D,
+    step: Point3D,
+    remaining: usize,
+}
+
+impl PointsOnSegment3D {
+    pub fn new(point1: Point3D, point2: Point3D) -> PointsOnSegment3D{
+        PointsOnSegment3D::new_ordered(cmp::min(point1,point2),cmp::max(point1,point2))
+    }
+
+    pub fn new_ordered(point1: Point3D, point2: Point3D) -> PointsOnSegment3D{
+        let dx = point2.x - point1.x;
+        let dy = point2.y - point1.y;
+        let dz = point2.z - point1.z;
+        let common_factor = rational::gcd(rational::gcd(dx.unsigned_abs(),dy.unsigned_abs()),dz.unsigned_abs());
+        let step = if common_factor == 0 {
+            Point3D{ x: 0, y: 0, z: 0,}
+        }else{
+            let common_factor = common_factor as isize;
+            Point3D{ x: dx/common_factor, y: dy/common_factor, z: dz/common_factor,}
+        };
+        PointsOnSegment3D{
+            current: point1,
+            step,
+            remaining: common_factor + 1,
+        }
+    }
+}
+
+impl Iterator for PointsOnSegment3D {
+    type Item = Point3D;
+
+    fn next(&mut self) -> Option<Point3D> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let cube = self.current;
+        self.remaining -= 1;
+        if self.remaining > 0 { //stepping past the last point could overflow.
+            self.current = Point3D{ x: self.current.x + self.step.x, y: self.current.y + self.step.y, z: self.current.z + self.step.z,};
+        }
+        Some(cube)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for PointsOnSegment3D {}
+
+//integer_points_on_line_segment_3d
+//Purpose:
+//    Returns all integer points (x,y,z) that are  on the line segment given by the two 3D points.
+//Pre-conditions:
+//    None.
+pub fn integer_points_on_line_segment_3d(point1: Point3D, point2: Point3D) -> Vec<Point3D>{
+    PointsOnSegment3D::new(point1,point2).collect()
+}
+
+//try_integer_points_on_line_segment_3d
+//Purpose:
+//    Returns the same points as integer_points_on_line_segment_3d, or GeometryError::RangeTooLarge if the segment is too long.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_on_line_segment_3d(point1: Point3D, point2: Point3D) -> Result<Vec<Point3D>, GeometryError>{
+    check_points::<Point3D>(&[point1.x,point1.y,point1.z],&[point2.x,point2.y,point2.z])?;
+    Ok(integer_points_on_line_segment_3d(point1,point2))
+}
+
+//integer_points_on_line_segment_3d_ordered
+//Purpose:
+//    Returns the same points as integer_points_on_line_segment_3d, ordered from point1 to point2.
+//Pre-conditions:
+//    None.
+pub fn integer_points_on_line_segment_3d_ordered(point1: Point3D, point2: Point3D) -> Vec<Point3D>{
+    PointsOnSegment3D::new_ordered(point1,point2).collect()
+}
+
+//try_integer_points_on_line_segment_3d_ordered
+//Purpose:
+//    Returns the same points as integer_points_on_line_segment_3d_ordered, or GeometryError::RangeTooLarge if the segment is too long.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_on_line_segment_3d_ordered(point1: Point3D, point2: Point3D) -> Result<Vec<Point3D>, GeometryError>{
+    check_points::<Point3D>(&[point1.x,point1.y,point1.z],&[point2.x,point2.y,point2.z])?;
+    Ok(integer_points_on_line_segment_3d_ordered(point1,point2))
+}
+
+//polyline_vertices
+//Purpose:
+//    Returns the vertices to walk for a polyline through the given vertices, and whether the last cell of the walk should be left out.
+//    A closed polyline walks back to its first vertex, whose cell was already yielded (a repeated first vertex at the end is ignored).
+//Pre-conditions:
+//    None.
+fn polyline_vertices<P: Copy + PartialEq>(vertices: &[P], closed: bool) -> (Vec<P>, bool){
+    let mut walked: Vec<P> = vertices.to_vec();
+    if closed && walked.len() > 1 && walked[0] == walked[walked.len() - 1] {
+        walked.pop();
+    }
+    let skip_last = closed && walked.len() > 1;
+    //a single vertex is walked as a segment from it to itself.
+    if skip_last || walked.len() == 1 {
+        walked.push(walked[0]);
+    }
+    (walked, skip_last)
+}
+
+//CellsOnPolyline2D
+//Purpose:
+//    Iterator over the integer cells (x,y) from a grid that lie on the segments between consecutive vertices using the given LineMode,
+//    ordered along the polyline. Every segment starts with the last cell of the segment before it, which is only yielded once,
+//    so each cell touches the one before it just as on a single segment, including across corners.
+//    When built with new_closed the polyline also returns from the last vertex to the first, without yielding the first cell again.
+//Notes:
+//    Only the cells at the joints are left out, a polyline that crosses or retraces itself yields the cells it passes again.
+pub struct CellsOnPolyline2D {
+    vertices: Vec<Point2D>,
+    mode: LineMode,
+    next_vertex: usize,
+    segment: Option<ConnectedCellsOnSegment2D>,
+    skip_last: bool,
+}
+
+impl CellsOnPolyline2D {
+    pub fn new(vertices: &[Point2D], mode: LineMode) -> CellsOnPolyline2D{
+        CellsOnPolyline2D::with_vertices(polyline_vertices(vertices,false),mode)
+    }
+
+    pub fn new_closed(vertices: &[Point2D], mode: LineMode) -> CellsOnPolyline2D{
+        CellsOnPolyline2D::with_vertices(polyline_vertices(vertices,true),mode)
+    }
+
+    fn with_vertices((vertices, skip_last): (Vec<Point2D>, bool), mode: LineMode) -> CellsOnPolyline2D{
+        CellsOnPolyline2D{ vertices, mode, next_vertex: 1, segment: None, skip_last,}
+    }
+}
+
+impl Iterator for CellsOnPolyline2D {
+    type Item = Point2D;
+
+    fn next(&mut self) -> Option<Point2D> {
+        loop {
+            if let Some(ref mut segment) = self.segment {
+                if let Some(square) = segment.next() {
+                    if self.skip_last && self.next_vertex == self.vertices.len() && segment.len() == 0 {
+                        return None;
+                    }
+                    return Some(square);
+                }
+            }
+            if self.next_vertex >= self.vertices.len() {
+                return None;
+            }
+            let mut segment = ConnectedCellsOnSegment2D::new(self.vertices[self.next_vertex - 1],self.vertices[self.next_vertex],self.mode);
+            if self.next_vertex > 1 {
+                segment.next(); //the joint, already yielded by the segment before.
+            }
+            self.next_vertex += 1;
+            self.segment = Some(segment);
+        }
+    }
+}
+
+//check_polyline_2d
+//Purpose:
+//    Returns GeometryError::RangeTooLarge if a segment of the polyline can not be walked, or all of its cells do not fit in a Vec.
+//Pre-conditions:
+//    None.
+fn check_polyline_2d(vertices: &[Point2D]) -> Result<(), GeometryError>{
+    let mut count: Option<usize> = Some(1);
+    for pair in vertices.windows(2) {
+        check_cells_2d(pair[0],pair[1])?;
+        let cells = checked_distance(pair[0].x,pair[1].x)? as usize + checked_distance(pair[0].y,pair[1].y)? as usize;
+        count = count.and_then(|count| count.checked_add(cells));
+    }
+    check_cell_count::<Point2D>(count)
+}
+
+//integer_cells_on_polyline_2d
+//Purpose:
+//    Returns the integer cells (x,y) from a grid that lie on the polyline through the given vertices using the given LineMode,
+//    ordered from the first vertex to the last. The cell of each joint between two segments is only returned once,
+//    and consecutive cells touch as they do on a single segment with the same LineMode.
+//Pre-conditions:
+//    None.
+//Notes:
+//    No vertices give no cells, and a single vertex gives its cell.
+pub fn integer_cells_on_polyline_2d(vertices: &[Point2D], mode: LineMode) -> Vec<Point2D>{
+    CellsOnPolyline2D::new(vertices,mode).collect()
+}
+
+//try_integer_cells_on_polyline_2d
+//Purpose:
+//    Returns the same cells as integer_cells_on_polyline_2d, or GeometryError::RangeTooLarge if a segment is too long to walk.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_polyline_2d(vertices: &[Point2D], mode: LineMode) -> Result<Vec<Point2D>, GeometryError>{
+    check_polyline_2d(&polyline_vertices(vertices,false).0)?;
+    Ok(integer_cells_on_polyline_2d(vertices,mode))
+}
+
+//integer_cells_on_polygon_outline_2d
+//Purpose:
+//    Returns the integer cells (x,y) from a grid that lie on the outline of the closed polygon with the given vertices
+//    using the given LineMode, ordered from the first vertex around the polygon and back.
+//    No cell at a joint is returned twice, and the last cell touches the first as consecutive cells do.
+//Pre-conditions:
+//    None.
+//Notes:
+//    The first vertex does not need to be repeated at the end, if it is the repeat is ignored.
+pub fn integer_cells_on_polygon_outline_2d(vertices: &[Point2D], mode: LineMode) -> Vec<Point2D>{
+    CellsOnPolyline2D::new_closed(vertices,mode).collect()
+}
+
+//try_integer_cells_on_polygon_outline_2d
+//Purpose:
+//    Returns the same cells as integer_cells_on_polygon_outline_2d, or GeometryError::RangeTooLarge if a segment is too long to walk.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_polygon_outline_2d(vertices: &[Point2D], mode: LineMode) -> Result<Vec<Point2D>, GeometryError>{
+    check_polyline_2d(&polyline_vertices(vertices,true).0)?;
+    Ok(integer_cells_on_polygon_outline_2d(vertices,mode))
+}
+
+//CellsOnPolyline3D
+//Purpose:
+//    Iterator over the integer cells (x,y,z) from a grid that lie on the segments between consecutive vertices,
+//    ordered along the polyline as integer_cells_on_line_segment_3d_ordered orders a single segment.
+//    Every segment starts with the last cell of the segment before it, which is only yielded once,
+//    so each cell shares a face, an edge or a corner with the one before it, including across corners.
+//    When built with new_closed the polyline also returns from the last vertex to the first, without yielding the first cell again.
+//Notes:
+//    Only the cells at the joints are left out, a polyline that crosses or retraces itself yields the cells it passes again.
+pub struct CellsOnPolyline3D {
+    vertices: Vec<Point3D>,
+    next_vertex: usize,
+    segment: Option<CellsOnSegment3D>,
+    skip_last: bool,
+    overflowed: bool,
+}
+
+impl CellsOnPolyline3D {
+    pub fn new(vertices: &[Point3D]) -> CellsOnPolyline3D{
+        CellsOnPolyline3D::with_vertices(polyline_vertices(vertices,false))
+    }
+
+    pub fn new_closed(vertices: &[Point3D]) -> CellsOnPolyline3D{
+        CellsOnPolyline3D::with_vertices(polyline_vertices(vertices,true))
+    }
+
+    fn with_vertices((vertices, skip_last): (Vec<Point3D>, bool)) -> CellsOnPolyline3D{
+        CellsOnPolyline3D{ vertices, next_vertex: 1, segment: None, skip_last, overflowed: false,}
+    }
+
+    //Returns true if the walk was stopped because a computation overflowed.
+    pub fn overflowed(&self) -> bool{
+        self.overflowed
+    }
+}
+
+impl Iterator for CellsOnPolyline3D {
+    type Item = Point3D;
+
+    fn next(&mut self) -> Option<Point3D> {
+        loop {
+            if self.overflowed {
+                return None;
+            }
+            if let Some(ref mut segment) = self.segment {
+                if let Some(cube) = segment.next() {
+                    //the closing segment ends at the first cell, which no other cell of that segment is.
+                    if self.skip_last && self.next_vertex == self.vertices.len() && cube == self.vertices[0] {
+                        return None;
+                    }
+                    return Some(cube);
+                }
+                if segment.overflowed() {
+                    self.overflowed = true;
+                    return None;
+                }
+            }
+            if self.next_vertex >= self.vertices.len() {
+                return None;
+            }
+            let mut segment = CellsOnSegment3D::new_ordered(self.vertices[self.next_vertex - 1],self.vertices[self.next_vertex]);
+            if self.next_vertex > 1 {
+                segment.next(); //the joint, already yielded by the segment before.
+            }
+            self.next_vertex += 1;
+            self.segment = Some(segment);
+        }
+    }
+}
+
+//check_polyline_3d
+//Purpose:
+//    Returns GeometryError::RangeTooLarge if a segment of the polyline can not be walked, or all of its cells do not fit in a Vec.
+//Pre-conditions:
+//    None.
+fn check_polyline_3d(vertices: &[Point3D]) -> Result<(), GeometryError>{
+    let mut count: Option<usize> = Some(1);
+    for pair in vertices.windows(2) {
+        check_cells_3d(pair[0],pair[1])?;
+        let cells = checked_distance(pair[0].x,pair[1].x)? as usize + checked_distance(pair[0].y,pair[1].y)? as usize + checked_distance(pair[0].z,pair[1].z)? as usize;
+        count = count.and_then(|count| count.checked_add(cells));
+    }
+    check_cell_count::<Point3D>(count)
+}
+
+//collect_polyline_3d
+//Purpose:
+//    Collects the cells of a 3D polyline walk, or returns an OverflowError if the walk overflowed.
+//Pre-conditions:
+//    None.
+fn collect_polyline_3d(mut walk: CellsOnPolyline3D) -> Result<Vec<Point3D>, OverflowError>{
+    let cells: Vec<Point3D> = walk.by_ref().collect();
+    if walk.overflowed() {
+        Err(OverflowError)
+    }else{
+        Ok(cells)
+    }
+}
+
+//integer_cells_on_polyline_3d
+//Purpose:
+//    Returns the integer cells (x,y,z) from a grid that lie on the polyline through the given vertices, ordered from the first vertex to the last.
+//    The cell of each joint between two segments is only returned once, and consecutive cells share a face, an edge or a corner.
+//Pre-conditions:
+//    None.
+//Notes:
+//    No vertices give no cells, and a single vertex gives its cell.
+pub fn integer_cells_on_polyline_3d(vertices: &[Point3D]) -> Vec<Point3D>{
+    match checked_integer_cells_on_polyline_3d(vertices) {
+        Ok(cells) => cells,
+        Err(_) => panic!("Overflow while finding the cells on a line segment (Rationals)!"),
+    }
+}
+
+//checked_integer_cells_on_polyline_3d
+//Purpose:
+//    Returns the same cells as integer_cells_on_polyline_3d, or an OverflowError if a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn checked_integer_cells_on_polyline_3d(vertices: &[Point3D]) -> Result<Vec<Point3D>, OverflowError>{
+    collect_polyline_3d(CellsOnPolyline3D::new(vertices))
+}
+
+//try_integer_cells_on_polyline_3d
+//Purpose:
+//    Returns the same cells as integer_cells_on_polyline_3d, or a GeometryError if a segment is too long or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_polyline_3d(vertices: &[Point3D]) -> Result<Vec<Point3D>, GeometryError>{
+    check_polyline_3d(&polyline_vertices(vertices,false).0)?;
+    Ok(checked_integer_cells_on_polyline_3d(vertices)?)
+}
+
+//integer_cells_on_polygon_outline_3d
+//Purpose:
+//    Returns the integer cells (x,y,z) from a grid that lie on the closed outline through the given vertices,
+//    ordered from the first vertex around the outline and back.
+//    No cell at a joint is returned twice, and the last cell touches the first as consecutive cells do.
+//Pre-conditions:
+//    None.
+//Notes:
+//    The vertices do not need to lie in a plane.
+//    The first vertex does not need to be repeated at the end, if it is the repeat is ignored.
+pub fn integer_cells_on_polygon_outline_3d(vertices: &[Point3D]) -> Vec<Point3D>{
+    match checked_integer_cells_on_polygon_outline_3d(vertices) {
+        Ok(cells) => cells,
+        Err(_) => panic!("Overflow while finding the cells on a line segment (Rationals)!"),
+    }
+}
+
+//checked_integer_cells_on_polygon_outline_3d
+//Purpose:
+//    Returns the same cells as integer_cells_on_polygon_outline_3d, or an OverflowError if a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn checked_integer_cells_on_polygon_outline_3d(vertices: &[Point3D]) -> Result<Vec<Point3D>, OverflowError>{
+    collect_polyline_3d(CellsOnPolyline3D::new_closed(vertices))
+}
+
+//try_integer_cells_on_polygon_outline_3d
+//Purpose:
+//    Returns the same cells as integer_cells_on_polygon_outline_3d, or a GeometryError if a segment is too long or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_polygon_outline_3d(vertices: &[Point3D]) -> Result<Vec<Point3D>, GeometryError>{
+    check_polyline_3d(&polyline_vertices(vertices,true).0)?;
+    Ok(checked_integer_cells_on_polygon_outline_3d(vertices)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort;
+
+    #[test]
+    fn line_2d_endpoints_order(){
+        assert_eq!(sort(integer_points_on_line_segment_2d(Point2D{ x: -1, y: 0,},Point2D{ x: 1, y: 0,})),
+                   sort(integer_points_on_line_segment_2d(Point2D{ x: 1, y: 0,},Point2D{ x: -1, y: 0,})));
+        assert_eq!(sort(integer_points_on_line_segment_2d(Point2D{ x: 0, y: -1,},Point2D{ x: 0, y: 1,})),
+                   sort(integer_points_on_line_segment_2d(Point2D{ x: 0, y: 1,},Point2D{ x: 0, y: -1,})));
+        assert_eq!(sort(integer_points_on_line_segment_2d(Point2D{ x: 1, y: 2,},Point2D{ x: 5, y: 10,})),
+                   sort(integer_points_on_line_segment_2d(Point2D{ x: 5, y: 10,},Point2D{ x: 1, y: 2,})));
+        assert_eq!(sort(integer_points_on_line_segment_2d(Point2D{ x: 1, y: 2,},Point2D{ x: 5, y: -6,})),
+                   sort(integer_points_on_line_segment_2d(Point2D{ x: 5, y: -6,},Point2D{ x: 1, y: 2,})));
+    }
+
+    #[test]
+    fn line_2d_correct_points(){
+        assert_eq!(sort(integer_points_on_line_segment_2d(Point2D{ x: -1, y: 0,},Point2D{ x: 1, y: 0,})),
+                   sort([Point2D{ x: 1, y: 0,},Point2D{ x: 0, y: 0,},Point2D{ x: -1, y: 0,}]));
+        assert_eq!(sort(integer_points_on_line_segment_2d(Point2D{ x: -1, y: 1,},Point2D{ x: 1, y: 1,})),
+                   sort([Point2D{ x: 1, y: 1,},Point2D{ x: 0, y: 1,},Point2D{ x: -1, y: 1,}]));
+        assert_eq!(sort(integer_points_on_line_segment_2d(Point2D{ x: 1, y: 2,},Point2D{ x: 5, y: 10,})),
+                   sort([Point2D{ x: 1, y: 2,},Point2D{ x: 2, y: 4,},
+                        Point2D{ x: 3, y: 6,},Point2D{ x: 4, y: 8,},Point2D{ x: 5, y: 10,}]));
+        assert_eq!(sort(integer_points_on_line_segment_2d(Point2D{ x: 1, y: 2,},Point2D{ x: 5, y: -6,})),
+                   sort([Point2D{ x: 1, y: 2,},Point2D{ x: 2, y: 0,},
+                        Point2D{ x: 3, y: -2,},Point2D{ x: 4, y: -4,},Point2D{ x: 5, y: -6,}]));
+        assert_eq!(sort(integer_points_on_line_segment_2d(Point2D{ x: 0, y: 0,},Point2D{ x: 3, y: 5,})),
+                   sort([Point2D{ x: 0, y: 0,},Point2D{ x: 3, y: 5,}]));
+        assert_eq!(sort(integer_points_on_line_segment_2d(Point2D{ x: 0, y: 0,},Point2D{ x: 4, y: 2,})),
+                   sort([Point2D{ x: 0, y: 0,},Point2D{ x: 2, y: 1,},Point2D{ x: 4, y: 2,}]));
+        assert_eq!(sort(integer_points_on_line_segment_2d(Point2D{ x: -3, y: 4,},Point2D{ x: -1, y: 6,})),
+                   sort([Point2D{ x: -3, y: 4,},Point2D{ x: -2, y: 5,},Point2D{ x: -1, y: 6,}]));
+    }
+
+    #[test]
+    fn l_infinity_line_2d_order(){
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: -1, y: 0,},Point2D{ x: 1, y: 0,})),
+                   sort(integer_cells_on_line_segment_2d(Point2D{ x: 1, y: 0,},Point2D{ x: -1, y: 0,})));
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: 0, y: -1,},Point2D{ x: 0, y: 1,})),
+                   sort(integer_cells_on_line_segment_2d(Point2D{ x: 0, y: 1,},Point2D{ x: 0, y: -1,})));
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: 0, y: 0,},Point2D{ x: 7, y: 7,})),
+                   sort(integer_cells_on_line_segment_2d(Point2D{ x: 7, y: 7,},Point2D{ x: 0, y: 0,})));
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: -1, y: -2,},Point2D{ x: 4, y: 3,})),
+                   sort(integer_cells_on_line_segment_2d(Point2D{ x: 4, y: 3,},Point2D{ x: -1, y: -2,})));
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: 1, y: 2,},Point2D{ x: 5, y: 10,})),
+                   sort(integer_cells_on_line_segment_2d(Point2D{ x: 5, y: 10,},Point2D{ x: 1, y: 2,})));
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: 1, y: 2,},Point2D{ x: 5, y: -6,})),
+                   sort(integer_cells_on_line_segment_2d(Point2D{ x: 5, y: -6,},Point2D{ x: 1, y: 2,})));
+    }
+
+    #[test]
+    fn l_infinity_line_2d_points(){
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: -1, y: 0,},Point2D{ x: 1, y: 0,})),
+                   sort([Point2D{ x: -1, y: 0,},Point2D{ x: 0, y: 0,},Point2D{ x: 1, y: 0,}]));
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: 0, y: 1,},Point2D{ x: 1, y: 0,})),
+                   sort(vec![Point2D{ x: 0, y: 1,},Point2D{ x: 1, y: 0,}]));
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: 0, y: 0,},Point2D{ x: 2, y: 1,})),
+                   sort([Point2D{ x: 0, y: 0,},Point2D{ x: 1, y: 0,},
+                        Point2D{ x: 1, y: 1,},Point2D{ x: 2, y: 1,}]));
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: -2, y: 0,},Point2D{ x: 1, y: 4,})),
+                   sort([Point2D{ x: -2, y: 0,},Point2D{ x: -2, y: 1,},
+                        Point2D{ x: -1, y: 1,},Point2D{ x: -1, y: 2,},
+                        Point2D{ x: 0, y: 2,},Point2D{ x: 0, y: 3,},
+                        Point2D{ x: 1, y: 3,},Point2D{ x: 1, y: 4,}]));
+    }
+
+    #[test]
+    fn l_infinity_line_rational_2d_order(){
+        assert_eq!(sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: -1, y: 0,},Point2D{ x: 1, y: 0,},Rational::new_rational(1,4))),
+                   sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 1, y: 0,},Point2D{ x: -1, y: 0,},Rational::new_rational(1,4))));
+        assert_eq!(sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 0, y: -1,},Point2D{ x: 0, y: 1,},Rational::new_rational(1,4))),
+                   sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 0, y: 1,},Point2D{ x: 0, y: -1,},Rational::new_rational(1,4))));
+        assert_eq!(sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 0, y: 0,},Point2D{ x: 7, y: 7,},Rational::new_rational(1,4))),
+                   sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 7, y: 7,},Point2D{ x: 0, y: 0,},Rational::new_rational(1,4))));
+        assert_eq!(sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: -1, y: -2,},Point2D{ x: 4, y: 3,},Rational::new_rational(1,4))),
+                   sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 4, y: 3,},Point2D{ x: -1, y: -2,},Rational::new_rational(1,4))));
+        assert_eq!(sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 1, y: 2,},Point2D{ x: 5, y: 10,},Rational::new_rational(1,4))),
+                   sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 5, y: 10,},Point2D{ x: 1, y: 2,},Rational::new_rational(1,4))));
+        assert_eq!(sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 1, y: 2,},Point2D{ x: 5, y: -6,},Rational::new_rational(1,4))),
+                   sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 5, y: -6,},Point2D{ x: 1, y: 2,},Rational::new_rational(1,4))));
+        assert_eq!(sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 1, y: 2,},Point2D{ x: 5, y: 10,},Rational::new_rational(1,6))),
+                   sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 5, y: 10,},Point2D{ x: 1, y: 2,},Rational::new_rational(1,6))));
+        assert_eq!(sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 1, y: 2,},Point2D{ x: 5, y: -6,},Rational::new_rational(1,6))),
+                   sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 5, y: -6,},Point2D{ x: 1, y: 2,},Rational::new_rational(1,6))));
+        assert_eq!(sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 1, y: 2,},Point2D{ x: 5, y: 10,},Rational::new_rational(2,6))),
+                   sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 5, y: 10,},Point2D{ x: 1, y: 2,},Rational::new_rational(2,6))));
+        assert_eq!(sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 1, y: 2,},Point2D{ x: 5, y: -6,},Rational::new_rational(2,6))),
+                   sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 5, y: -6,},Point2D{ x: 1, y: 2,},Rational::new_rational(2,6))));
+    }
+
+    #[test]
+    fn l_infinity_line_rational_2d_points(){
+        assert_ne!(sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 0, y: 0,},Point2D{ x: 2, y: 1,},Rational::new_rational(1,4))),
+                   sort(vec![Point2D{ x: 0, y: 0,},Point2D{ x: 1, y: 0,},Point2D{ x: 1, y: 1,},Point2D{ x: 2, y: 1,}]));
+
+        assert_eq!(sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: -1, y: 0,},Point2D{ x: 1, y: 0,},Rational::new_rational(1,4))),
+                   sort(vec![Point2D{ x: -1, y: 0,},Point2D{ x: 0, y: 0,},Point2D{ x: 1, y: 0,}]));
+        assert_eq!(sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 0, y: 0,},Point2D{ x: 2, y: 1,},Rational::new_rational(1,4))),
+                   sort(vec![Point2D{ x: 0, y: 0,},Point2D{ x: 2, y: 1,}]));
+        assert_eq!(sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 0, y: 0,},Point2D{ x: 2, y: 1,},Rational::new_rational(1,3))),
+                   sort(vec![Point2D{ x: 0, y: 0,},Point2D{ x: 2, y: 1,}]));
+        assert_eq!(sort(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 0, y: 0,},Point2D{ x: 2, y: 1,},Rational::new_rational(13,33))),
+                   sort(vec![Point2D{ x: 0, y: 0,},Point2D{ x: 1, y: 0,},Point2D{ x: 1, y: 1,},Point2D{ x: 2, y: 1,}]));
+    }
+
+    #[test]
+    fn l_infinity_line_rational_endpoints_integer_check(){
+        let zero = Rational::new_rational(0,1);
+        let one = Rational::new_rational(1,1);
+        let two = one + one;
+        let three = one + two;
+        let four = one + three;
+        let five = one + four;
+        let six = one + five;
+        let seven = one + six;
+        let ten = five + five;
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: -1, y: 0,},Point2D{ x: 1, y: 0,})),
+                   sort(l_infinity_line_rational_endpoints(RationalPoint2D{ x: -one, y: zero,},RationalPoint2D{ x: one, y: zero,})));
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: 0, y: -1,},Point2D{ x: 0, y: 1,})),
+                   sort(l_infinity_line_rational_endpoints(RationalPoint2D{ x: zero, y: -one,},RationalPoint2D{ x: zero, y: one,})));
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: 0, y: 0,},Point2D{ x: 7, y: 7,})),
+                   sort(l_infinity_line_rational_endpoints(RationalPoint2D{ x: zero, y: zero,},RationalPoint2D{ x: seven, y: seven,})));
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: -1, y: -2,},Point2D{ x: 4, y: 3,})),
+                   sort(l_infinity_line_rational_endpoints(RationalPoint2D{ x: -one, y: -two,},RationalPoint2D{ x: four, y: three,})));
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: 1, y: 2,},Point2D{ x: 5, y: 10,})),
+                   sort(l_infinity_line_rational_endpoints(RationalPoint2D{ x: one, y: two,},RationalPoint2D{ x: five, y: ten,})));
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: 1, y: 2,},Point2D{ x: 5, y: -6,})),
+                   sort(l_infinity_line_rational_endpoints(RationalPoint2D{ x: one, y: two,},RationalPoint2D{ x: five, y: -six,})));
+    }
+
+    #[test]
+    fn line_3d_endpoints_order(){
+        assert_eq!(sort(integer_points_on_line_segment_3d(Point3D{ x: -1, y: 0, z: 0,},Point3D{ x: 1, y: 0, z: 0,})),
+                   sort(integer_points_on_line_segment_3d(Point3D{ x: 1, y: 0, z: 0,},Point3D{ x: -1, y: 0, z: 0,})));
+        assert_eq!(sort(integer_points_on_line_segment_3d(Point3D{ x: 0, y: 1, z: 0,},Point3D{ x: 0, y: -1, z: 0,})),
+                   sort(integer_points_on_line_segment_3d(Point3D{ x: 0, y: -1, z: 0,},Point3D{ x: 0, y: 1, z: 0,})));
+        assert_eq!(sort(integer_points_on_line_segment_3d(Point3D{ x: 0, y: 0, z: 1,},Point3D{ x: 0, y: 0, z: -1,})),
+                   sort(integer_points_on_line_segment_3d(Point3D{ x: 0, y: 0, z: -1,},Point3D{ x: 0, y: 0, z: 1,})));
+        assert_eq!(sort(integer_points_on_line_segment_3d(Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 3, y: 6, z: 9,})),
+                   sort(integer_points_on_line_segment_3d(Point3D{ x: 3, y: 6, z: 9,},Point3D{ x: 0, y: 0, z: 0,})));
+        assert_eq!(sort(integer_points_on_line_segment_3d(Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 3, y: 6, z: 9,})),
+                   sort(integer_points_on_line_segment_3d(Point3D{ x: 3, y: 6, z: 9,},Point3D{ x: 0, y: 0, z: 0,})));
+        assert_eq!(sort(integer_points_on_line_segment_3d(Point3D{ x: -1, y: -2, z: -3,},Point3D{ x: 4, y: 23, z: 2,})),
+                   sort(integer_points_on_line_segment_3d(Point3D{ x: 4, y: 23, z: 2,},Point3D{ x: -1, y: -2, z: -3,})));
+        assert_eq!(sort(integer_points_on_line_segment_3d(Point3D{ x: 3, y: 6, z: 0,},Point3D{ x: 5, y: -8, z: 14,})),
+                   sort(integer_points_on_line_segment_3d(Point3D{ x: 5, y: -8, z: 14,},Point3D{ x: 3, y: 6, z: 0,})));
+    }
+
+    #[test]
+    fn line_3d_correct_points(){
+        assert_eq!(sort(integer_points_on_line_segment_3d(Point3D{ x: -1, y: 0, z: 0,},Point3D{ x: 1, y: 0, z: 0,})),
+                   sort(vec![Point3D{ x: -1, y: 0, z: 0,},Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 1, y: 0, z: 0,}]));
+        assert_eq!(sort(integer_points_on_line_segment_3d(Point3D{ x: 3, y: 6, z: 9,},Point3D{ x: 0, y: 0, z: 0,})),
+                   sort(vec![Point3D{ x: 3, y: 6, z: 9,},Point3D{ x: 2, y: 4, z: 6,},Point3D{ x: 1, y: 2, z: 3,},Point3D{ x: 0, y: 0, z: 0,}]));
+        assert_eq!(sort(integer_points_on_line_segment_3d(Point3D{ x: -1, y: -2, z: -3,},Point3D{ x: 4, y: 23, z: 2,})),
+                   sort(vec![Point3D{ x: -1, y: -2, z: -3,},Point3D{ x: 0, y: 3, z: -2,},Point3D{ x: 1, y: 8, z: -1,},Point3D{ x: 2, y: 13, z: 0,},
+                        Point3D{ x: 3, y: 18, z: 1,},Point3D{ x: 4, y: 23, z: 2,}]));
+    }
+
+    #[test]
+    fn line_iterators_exact_size(){
+        for x in -3 .. 4 {
+            for y in -3 .. 4 {
+                let point1 = Point2D{ x: 0, y: 0,};
+                let point2 = Point2D{ x, y,};
+                assert_eq!(CellsOnSegment2D::new(point1,point2).len(),CellsOnSegment2D::new(point1,point2).count());
+                assert_eq!(PointsOnSegment2D::new(point1,point2).len(),PointsOnSegment2D::new(point1,point2).count());
+                for z in -3 .. 4 {
+                    let point1 = Point3D{ x: 0, y: 0, z: 0,};
+                    let point2 = Point3D{ x, y, z,};
+                    assert_eq!(PointsOnSegment3D::new(point1,point2).len(),PointsOnSegment3D::new(point1,point2).count());
+                }
+            }
+        }
+        let mut cells = CellsOnSegment2D::new(Point2D{ x: 0, y: 0,},Point2D{ x: 2, y: 1,});
+        assert_eq!(cells.len(),4);
+        cells.next();
+        assert_eq!(cells.len(),3);
+    }
+
+    #[test]
+    fn l_infinity_line_rational_endpoints_fractional(){
+        let zero = Rational::new_rational(0,1);
+        let one = Rational::new_rational(1,1);
+        let two = one + one;
+        let three = one + two;
+        assert_eq!(sort(integer_cells_on_line_segment_2d(Point2D{ x: 0, y: 2,},Point2D{ x: 3, y: 1,})),
+                   sort(l_infinity_line_rational_endpoints(RationalPoint2D{ x: zero, y: two,},RationalPoint2D{ x: three, y: one,})));
+        assert_eq!(sort(l_infinity_line_rational_endpoints(RationalPoint2D{ x: zero, y: Rational::new_rational(3,10),},RationalPoint2D{ x: two, y: Rational::new_rational(13,10),})),
+                   sort(vec![Point2D{ x: 0, y: 0,},Point2D{ x: 0, y: 1,},Point2D{ x: 1, y: 1,},Point2D{ x: 2, y: 1,}]));
+        assert_eq!(l_infinity_line_rational_endpoints(RationalPoint2D{ x: Rational::new_rational(1,2), y: one,},RationalPoint2D{ x: Rational::new_rational(1,2), y: three,}),
+                   vec![]);
+    }
+
+    #[test]
+    fn l_infinity_line_3d_points(){
+        assert_eq!(sort(integer_cells_on_line_segment_3d(Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 1, y: 1, z: 1,})),
+                   sort(vec![Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 1, y: 1, z: 1,}]));
+        assert_eq!(sort(integer_cells_on_line_segment_3d(Point3D{ x: 0, y: 0, z: 5,},Point3D{ x: 2, y: 1, z: 5,})),
+                   sort(vec![Point3D{ x: 0, y: 0, z: 5,},Point3D{ x: 1, y: 0, z: 5,},Point3D{ x: 1, y: 1, z: 5,},Point3D{ x: 2, y: 1, z: 5,}]));
+        assert_eq!(sort(integer_cells_on_line_segment_3d(Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 0, y: 1, z: 2,})),
+                   sort(vec![Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 0, y: 0, z: 1,},Point3D{ x: 0, y: 1, z: 1,},Point3D{ x: 0, y: 1, z: 2,}]));
+        assert_eq!(sort(integer_cells_on_line_segment_3d(Point3D{ x: -1, y: -2, z: 3,},Point3D{ x: 4, y: 3, z: -2,})),
+                   sort(integer_cells_on_line_segment_3d(Point3D{ x: 4, y: 3, z: -2,},Point3D{ x: -1, y: -2, z: 3,})));
+    }
+
+    #[test]
+    fn ordered_lines_2d(){
+        for x in -4 .. 5 {
+            for y in -4 .. 5 {
+                let point1 = Point2D{ x: 1, y: -1,};
+                let point2 = Point2D{ x, y,};
+                let cells = integer_cells_on_line_segment_2d_ordered(point2,point1);
+                assert_eq!(cells[0],point2);
+                assert_eq!(cells[cells.len()-1],point1);
+                for pair in cells.windows(2) {
+                    assert!(cmp::max((pair[0].x - pair[1].x).abs(),(pair[0].y - pair[1].y).abs()) == 1);
+                }
+                assert_eq!(sort(cells),sort(integer_cells_on_line_segment_2d(point1,point2)));
+                let points = integer_points_on_line_segment_2d_ordered(point2,point1);
+                assert_eq!(points[0],point2);
+                assert_eq!(points[points.len()-1],point1);
+                assert_eq!(sort(points),sort(integer_points_on_line_segment_2d(point1,point2)));
+            }
+        }
+        let fractions = [Rational::new_rational_from_integers(-3,2),Rational::new_rational_from_integers(-1,3),Rational::new_rational(0,1),
+                         Rational::new_rational(1,2),Rational::new_rational(7,4),Rational::new_rational(5,2)];
+        for &x1 in fractions.iter() {
+            for &y1 in fractions.iter() {
+                for &x2 in fractions.iter() {
+                    for &y2 in fractions.iter() {
+                        let point1 = RationalPoint2D{ x: x1, y: y1,};
+                        let point2 = RationalPoint2D{ x: x2, y: y2,};
+                        let cells = l_infinity_line_rational_endpoints_ordered(point2,point1);
+                        for pair in cells.windows(2) {
+                            assert!(cmp::max((pair[0].x - pair[1].x).abs(),(pair[0].y - pair[1].y).abs()) == 1);
+                        }
+                        assert_eq!(sort(cells),sort(l_infinity_line_rational_endpoints(point1,point2)));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ordered_lines_3d(){
+        for x in -3 .. 4 {
+            for y in -3 .. 4 {
+                for z in -3 .. 4 {
+                    let point1 = Point3D{ x: 1, y: -1, z: 2,};
+                    let point2 = Point3D{ x, y, z,};
+                    let cells = integer_cells_on_line_segment_3d_ordered(point2,point1);
+                    assert_eq!(cells[0],point2);
+                    assert_eq!(cells[cells.len()-1],point1);
+                    for pair in cells.windows(2) {
+                        assert!(cmp::max(cmp::max((pair[0].x - pair[1].x).abs(),(pair[0].y - pair[1].y).abs()),(pair[0].z - pair[1].z).abs()) == 1);
+                    }
+                    assert_eq!(sort(cells),sort(integer_cells_on_line_segment_3d(point1,point2)));
+                    let points = integer_points_on_line_segment_3d_ordered(point2,point1);
+                    assert_eq!(points[0],point2);
+                    assert_eq!(points[points.len()-1],point1);
+                    assert_eq!(sort(points),sort(integer_points_on_line_segment_3d(point1,point2)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn connected_lines_2d(){
+        assert_eq!(integer_cells_on_line_segment_2d_mode(Point2D{ x: 0, y: 0,},Point2D{ x: 4, y: 2,},LineMode::EightConnected),
+                   vec![Point2D{ x: 0, y: 0,},Point2D{ x: 1, y: 0,},Point2D{ x: 2, y: 1,},Point2D{ x: 3, y: 1,},Point2D{ x: 4, y: 2,}]);
+        assert_eq!(integer_cells_on_line_segment_2d_mode(Point2D{ x: 2, y: 4,},Point2D{ x: 0, y: 0,},LineMode::EightConnected),
+                   vec![Point2D{ x: 2, y: 4,},Point2D{ x: 1, y: 3,},Point2D{ x: 1, y: 2,},Point2D{ x: 0, y: 1,},Point2D{ x: 0, y: 0,}]);
+        assert_eq!(integer_cells_on_line_segment_2d_mode(Point2D{ x: 0, y: 0,},Point2D{ x: 1, y: 1,},LineMode::FourConnected),
+                   vec![Point2D{ x: 0, y: 0,},Point2D{ x: 1, y: 0,},Point2D{ x: 1, y: 1,}]);
+        assert_eq!(integer_cells_on_line_segment_2d_mode(Point2D{ x: 0, y: 1,},Point2D{ x: 1, y: 0,},LineMode::FourConnected),
+                   vec![Point2D{ x: 0, y: 1,},Point2D{ x: 0, y: 0,},Point2D{ x: 1, y: 0,}]);
+        for x in -4 .. 5 {
+            for y in -4 .. 5 {
+                let point1 = Point2D{ x: 1, y: -1,};
+                let point2 = Point2D{ x, y,};
+                let eight = integer_cells_on_line_segment_2d_mode(point1,point2,LineMode::EightConnected);
+                assert_eq!(eight.len(),ConnectedCellsOnSegment2D::new(point1,point2,LineMode::EightConnected).len());
+                assert_eq!(eight[eight.len()-1],point2);
+                for pair in eight.windows(2) {
+                    assert!(cmp::max((pair[0].x - pair[1].x).abs(),(pair[0].y - pair[1].y).abs()) == 1);
+                }
+                assert_eq!(sort(eight),sort(integer_cells_on_line_segment_2d_mode(point2,point1,LineMode::EightConnected)));
+                let four = integer_cells_on_line_segment_2d_mode(point1,point2,LineMode::FourConnected);
+                assert_eq!(four.len(),ConnectedCellsOnSegment2D::new(point1,point2,LineMode::FourConnected).len());
+                assert_eq!(four[four.len()-1],point2);
+                for pair in four.windows(2) {
+                    assert!((pair[0].x - pair[1].x).abs() + (pair[0].y - pair[1].y).abs() == 1);
+                }
+                for cell in integer_cells_on_line_segment_2d(point1,point2) {
+                    assert!(four.contains(&cell));
+                }
+                assert_eq!(sort(four),sort(integer_cells_on_line_segment_2d_mode(point2,point1,LineMode::FourConnected)));
+                assert_eq!(integer_cells_on_line_segment_2d_mode(point1,point2,LineMode::Supercover),integer_cells_on_line_segment_2d_ordered(point1,point2));
+            }
+        }
+    }
+
+    #[test]
+    fn l_infinity_line_rational_endpoints_3d_points(){
+        for x in -3 .. 4 {
+            for y in -3 .. 4 {
+                for z in -3 .. 4 {
+                    let point1 = Point3D{ x: 1, y: -1, z: 2,};
+                    let point2 = Point3D{ x, y, z,};
+                    assert_eq!(sort(l_infinity_line_rational_endpoints_3d(RationalPoint3D::from(point1),RationalPoint3D::from(point2))),
+                               sort(integer_cells_on_line_segment_3d(point1,point2)));
+                }
+            }
+        }
+        let zero = Rational::new_rational(0,1);
+        let half = Rational::new_rational(1,2);
+        let one = Rational::new_rational(1,1);
+        assert_eq!(l_infinity_line_rational_endpoints_3d_ordered(RationalPoint3D{ x: zero, y: zero, z: Rational::new_rational(1,4),},RationalPoint3D{ x: one, y: zero, z: Rational::new_rational(3,4),}),
+                   vec![Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 1, y: 0, z: 1,}]);
+        assert_eq!(l_infinity_line_rational_endpoints_3d_ordered(RationalPoint3D{ x: zero, y: zero, z: zero,},RationalPoint3D{ x: one, y: zero, z: Rational::new_rational(1,3),}),
+                   vec![Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 1, y: 0, z: 0,}]);
+        assert_eq!(l_infinity_line_rational_endpoints_3d(RationalPoint3D{ x: zero, y: zero, z: half,},RationalPoint3D{ x: one, y: one, z: half,}),
+                   vec![]);
+        assert_eq!(l_infinity_line_rational_endpoints_3d(RationalPoint3D{ x: half, y: zero, z: zero,},RationalPoint3D{ x: half, y: one, z: one,}),
+                   vec![]);
+    }
+
+    #[test]
+    fn l_infinity_line_f64_points(){
+        let quarter = Rational::new_rational(1,4);
+        assert_eq!(l_infinity_line_f64_endpoints((0.25,-1.5),(3.0,2.75),4),
+                   Some(l_infinity_line_rational_endpoints(RationalPoint2D{ x: quarter, y: quarter*(-6_isize),},RationalPoint2D{ x: quarter*12_isize, y: quarter*11_isize,})));
+        assert_eq!(l_infinity_line_f64_endpoints_ordered((0.1,0.0),(1.9,0.0),10),
+                   Some(vec![Point2D{ x: 0, y: 0,},Point2D{ x: 1, y: 0,},Point2D{ x: 2, y: 0,}]));
+        assert_eq!(l_infinity_line_f64_endpoints_3d((0.0,0.0,0.0),(2.0,1.0,-1.0),16),
+                   Some(integer_cells_on_line_segment_3d(Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 2, y: 1, z: -1,})));
+        assert_eq!(l_infinity_line_f64_endpoints_3d_ordered((0.5,0.0,0.0),(0.5,1.0,1.0),2),Some(vec![]));
+        assert_eq!(l_infinity_line_f64_endpoints((0.0,f64::NAN),(1.0,1.0),4),None);
+        assert_eq!(l_infinity_line_f64_endpoints((0.0,0.0),(1.0,1.0),0),None);
+        assert_eq!(l_infinity_line_f64_endpoints_3d_ordered((0.0,0.0,0.0),(1.0,1.0,1.0),0),None);
+    }
+
+    #[test]
+    fn overflowing_rational_lines(){
+        let zero = Rational::new_rational(0,1);
+        let one = Rational::new_rational(1,1);
+        let point1 = RationalPoint2D{ x: Rational::new_rational(1,(1 << 33) + 1), y: zero,};
+        let point2 = RationalPoint2D{ x: Rational::new_rational(1,(1 << 33) - 1), y: one,};
+        assert_eq!(checked_l_infinity_line_rational_endpoints(point1,point2),Err(OverflowError));
+        assert_eq!(checked_l_infinity_line_rational_endpoints_ordered(point2,point1),Err(OverflowError));
+        assert_eq!(checked_l_infinity_line_rational_endpoints(point1,RationalPoint2D{ x: one, y: one,}),
+                   Ok(vec![Point2D{ x: 0, y: 0,},Point2D{ x: 1, y: 0,},Point2D{ x: 1, y: 1,}]));
+        let point1 = RationalPoint3D{ x: zero, y: zero, z: Rational::new_rational(1,(1 << 33) + 1),};
+        let point2 = RationalPoint3D{ x: one, y: zero, z: Rational::new_rational(1,(1 << 33) - 1),};
+        assert_eq!(checked_l_infinity_line_rational_endpoints_3d(point1,point2),Err(OverflowError));
+        assert_eq!(checked_l_infinity_line_rational_endpoints_3d_ordered(point1,point2),Err(OverflowError));
+        assert_eq!(checked_integer_cells_on_line_segment_3d(Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 2, y: 1, z: 0,}),
+                   Ok(integer_cells_on_line_segment_3d(Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 2, y: 1, z: 0,})));
+        assert_eq!(checked_integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 0, y: 0,},Point2D{ x: 3, y: 1,},Rational::new_rational(1,4)),
+                   Ok(integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 0, y: 0,},Point2D{ x: 3, y: 1,},Rational::new_rational(1,4))));
+        assert_eq!(checked_integer_cells_on_line_segment_2d_rational_scale(Point2D{ x: 0, y: 0,},Point2D{ x: 3, y: 1,},Rational::new_rational(1,usize::MAX)),
+                   Err(OverflowError));
+        assert_eq!(l_infinity_line_f64_endpoints((0.1,0.2),(7.3,-2.9),usize::MAX),None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Overflow while finding the cells on a line segment (Rationals)!")]
+    fn overflowing_rational_line_panics(){
+        let point1 = RationalPoint2D{ x: Rational::new_rational(1,(1 << 33) + 1), y: Rational::new_rational(0,1),};
+        let point2 = RationalPoint2D{ x: Rational::new_rational(1,(1 << 33) - 1), y: Rational::new_rational(1,1),};
+        l_infinity_line_rational_endpoints(point1,point2);
+    }
+
+    #[test]
+    fn fallible_lines(){
+        let origin = Point2D{ x: 0, y: 0,};
+        let point = Point2D{ x: 5, y: -3,};
+        assert_eq!(try_integer_cells_on_line_segment_2d(origin,point),Ok(integer_cells_on_line_segment_2d(origin,point)));
+        assert_eq!(try_integer_cells_on_line_segment_2d_ordered(point,origin),Ok(integer_cells_on_line_segment_2d_ordered(point,origin)));
+        assert_eq!(try_integer_cells_on_line_segment_2d_mode(origin,point,LineMode::FourConnected),
+                   Ok(integer_cells_on_line_segment_2d_mode(origin,point,LineMode::FourConnected)));
+        assert_eq!(try_integer_points_on_line_segment_2d(origin,point),Ok(integer_points_on_line_segment_2d(origin,point)));
+        assert_eq!(try_integer_cells_on_line_segment_2d_rational_scale(origin,point,Rational::new_rational(1,3)),
+                   Ok(integer_cells_on_line_segment_2d_rational_scale(origin,point,Rational::new_rational(1,3))));
+        assert_eq!(try_integer_cells_on_line_segment_2d_rational_scale(origin,point,Rational::new_rational_from_integers(-1,3)),
+                   Err(GeometryError::NegativeRadius));
+        let far = Point2D{ x: isize::MAX, y: 1 << 40,};
+        assert_eq!(try_integer_cells_on_line_segment_2d(origin,far),Err(GeometryError::RangeTooLarge));
+        assert_eq!(try_integer_cells_on_line_segment_2d_mode(origin,far,LineMode::EightConnected),Err(GeometryError::RangeTooLarge));
+        assert_eq!(try_integer_cells_on_line_segment_2d(origin,Point2D{ x: isize::MIN, y: 0,}),Err(GeometryError::RangeTooLarge));
+        assert_eq!(try_integer_points_on_line_segment_2d(origin,far),Ok(vec![origin,far]));
+        assert_eq!(try_integer_points_on_line_segment_2d(Point2D{ x: -1, y: 0,},far),Err(GeometryError::RangeTooLarge));
+        let far = Point3D{ x: isize::MAX, y: 0, z: isize::MAX - 1,};
+        assert_eq!(try_integer_points_on_line_segment_3d_ordered(far,Point3D{ x: 0, y: 0, z: 0,}),
+                   Ok(vec![far,Point3D{ x: 0, y: 0, z: 0,}]));
+        assert_eq!(try_integer_points_on_line_segment_3d(Point3D{ x: -1, y: 0, z: 0,},far),Err(GeometryError::RangeTooLarge));
+        assert_eq!(try_integer_cells_on_line_segment_3d(Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 2, y: 1, z: -1,}),
+                   Ok(integer_cells_on_line_segment_3d(Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 2, y: 1, z: -1,})));
+        assert_eq!(try_integer_cells_on_line_segment_3d_ordered(Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: isize::MAX, y: 1, z: 2,}),
+                   Err(GeometryError::RangeTooLarge));
+        assert_eq!(try_integer_cells_on_line_segment_3d(Point3D{ x: isize::MAX - 2, y: 0, z: 0,},Point3D{ x: isize::MAX, y: 1, z: 2,}),
+                   Err(GeometryError::RangeTooLarge));
+        let shift = isize::MAX - 3;
+        assert_eq!(try_integer_cells_on_line_segment_3d_ordered(Point3D{ x: shift + 2, y: 0, z: 0,},Point3D{ x: shift, y: 1, z: 2,}),
+                   Ok(integer_cells_on_line_segment_3d_ordered(Point3D{ x: 2, y: 0, z: 0,},Point3D{ x: 0, y: 1, z: 2,}).into_iter()
+                                                                  .map(|cell| Point3D{ x: cell.x + shift, y: cell.y, z: cell.z,}).collect()));
+    }
+
+    //Returns true if the cells are all different and each touches the next, sharing an edge if four_connected.
+    fn touching_once<T: Copy + Ord>(cells: &[T], touching: &dyn Fn(T, T) -> bool) -> bool{
+        let mut sorted = cells.to_vec();
+        sorted.sort();
+        sorted.dedup();
+        sorted.len() == cells.len() && cells.windows(2).all(|pair| touching(pair[0],pair[1]))
+    }
+
+    #[test]
+    fn polylines_2d(){
+        let vertices = [Point2D{ x: 0, y: 0,},Point2D{ x: 5, y: 2,},Point2D{ x: 3, y: 7,},Point2D{ x: -4, y: 6,},Point2D{ x: -2, y: 1,}];
+        let eight = |a: Point2D, b: Point2D| a != b && (a.x - b.x).abs() <= 1 && (a.y - b.y).abs() <= 1;
+        let four = |a: Point2D, b: Point2D| (a.x - b.x).abs() + (a.y - b.y).abs() == 1;
+        for mode in [LineMode::Supercover,LineMode::EightConnected,LineMode::FourConnected].iter() {
+            let touching: &dyn Fn(Point2D, Point2D) -> bool = if *mode == LineMode::FourConnected { &four } else { &eight };
+            let mut expected: Vec<Point2D> = Vec::new();
+            for pair in vertices.windows(2) {
+                for square in integer_cells_on_line_segment_2d_mode(pair[0],pair[1],*mode) {
+                    if expected.last() != Some(&square) {
+                        expected.push(square);
+                    }
+                }
+            }
+            let polyline = integer_cells_on_polyline_2d(&vertices,*mode);
+            assert_eq!(polyline,expected);
+            assert!(touching_once(&polyline,touching));
+            assert_eq!(polyline[0],vertices[0]);
+            assert_eq!(polyline[polyline.len() - 1],vertices[4]);
+
+            let outline = integer_cells_on_polygon_outline_2d(&vertices,*mode);
+            let mut closing = integer_cells_on_line_segment_2d_mode(vertices[4],vertices[0],*mode);
+            closing.pop();
+            expected.extend(closing.into_iter().skip(1));
+            assert_eq!(outline,expected);
+            assert!(touching_once(&outline,touching));
+            assert!(touching(outline[outline.len() - 1],outline[0]));
+            let mut repeated = vertices.to_vec();
+            repeated.push(vertices[0]);
+            assert_eq!(integer_cells_on_polygon_outline_2d(&repeated,*mode),outline);
+            assert_eq!(try_integer_cells_on_polygon_outline_2d(&vertices,*mode),Ok(outline));
+        }
+        let point = Point2D{ x: 3, y: -3,};
+        assert_eq!(integer_cells_on_polyline_2d(&[],LineMode::Supercover),Vec::new());
+        assert_eq!(integer_cells_on_polyline_2d(&[point],LineMode::Supercover),vec![point]);
+        assert_eq!(integer_cells_on_polygon_outline_2d(&[point],LineMode::FourConnected),vec![point]);
+        assert_eq!(integer_cells_on_polyline_2d(&[point,point,Point2D{ x: 5, y: -3,}],LineMode::EightConnected),vec![point,Point2D{ x: 4, y: -3,},Point2D{ x: 5, y: -3,}]);
+        assert_eq!(try_integer_cells_on_polyline_2d(&[point,Point2D{ x: isize::MIN, y: 0,}],LineMode::Supercover),Err(GeometryError::RangeTooLarge));
+    }
+
+    #[test]
+    fn polylines_3d(){
+        let vertices = [Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 4, y: 2, z: -1,},Point3D{ x: 1, y: 5, z: 3,},Point3D{ x: -3, y: 1, z: 2,}];
+        let touching = |a: Point3D, b: Point3D| a != b && (a.x - b.x).abs() <= 1 && (a.y - b.y).abs() <= 1 && (a.z - b.z).abs() <= 1;
+        let mut expected: Vec<Point3D> = Vec::new();
+        for pair in vertices.windows(2) {
+            for cube in integer_cells_on_line_segment_3d_ordered(pair[0],pair[1]) {
+                if expected.last() != Some(&cube) {
+                    expected.push(cube);
+                }
+            }
+        }
+        let polyline = integer_cells_on_polyline_3d(&vertices);
+        assert_eq!(polyline,expected);
+        assert!(touching_once(&polyline,&touching));
+        let outline = integer_cells_on_polygon_outline_3d(&vertices);
+        let mut closing = integer_cells_on_line_segment_3d_ordered(vertices[3],vertices[0]);
+        closing.pop();
+        expected.extend(closing.into_iter().skip(1));
+        assert_eq!(outline,expected);
+        assert!(touching_once(&outline,&touching));
+        assert!(touching(outline[outline.len() - 1],outline[0]));
+        assert_eq!(try_integer_cells_on_polygon_outline_3d(&vertices),Ok(outline));
+        assert_eq!(integer_cells_on_polygon_outline_3d(&vertices[.. 1]),vec![vertices[0]]);
+        assert_eq!(try_integer_cells_on_polyline_3d(&[vertices[0],Point3D{ x: isize::MAX, y: 0, z: 0,}]),Err(GeometryError::RangeTooLarge));
+    }
+}
@@ -0,0 +1,580 @@
+//Purpose: Contains functions for finding the integer cells (x,y,z) on triangles and triangle meshes in 3D,
+//         on their surface or inside a closed mesh.
+//Notes:
+//    As for the 3D line segments, the cell (x,y,z) is the unit cube centered on the integer point (x,y,z).
+//    Every test is done exactly on the integer vertices, in i128 so that the products of coordinates do not overflow as soon.
+
+use std::convert::TryFrom;
+
+use crate::error::{check_cell_count, GeometryError};
+use crate::line::try_integer_cells_on_line_segment_3d;
+use crate::point::{Point2D, Point3D};
+use crate::polygon::{try_integer_points_inside_triangle, FillRule};
+
+//SurfaceVoxelization
+//Purpose:
+//    Decides which cells are on the surface of a triangle.
+//    Conservative: every cell that the triangle touches, even if only at a corner of the cell.
+//    SixSeparating: the thin surface, in which every 6-connected path of cells from one side to the other has a cell on the surface.
+//        A cell is on it if its center is within max(|n_x|,|n_y|,|n_z|)/(2|n|) of the plane of the triangle and projects into the triangle,
+//        where n is the normal of the triangle, or within 1/2 of an edge of the triangle.
+//    TwentySixSeparating: the thick surface, in which even every 26-connected path of cells from one side to the other has a cell on the surface.
+//        A cell is on it if its center is within (|n_x| + |n_y| + |n_z|)/(2|n|) of the plane of the triangle and projects into the triangle,
+//        or within sqrt(3)/2 of an edge of the triangle.
+//Notes:
+//    The separating surfaces are those described by Huang, Yagel, Filippov and Kurzion, with the edges rounded by cylinders and spheres.
+//    Conservative cells are also on the TwentySixSeparating surface, so it separates 26-connected paths as well.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SurfaceVoxelization {
+    Conservative,
+    SixSeparating,
+    TwentySixSeparating,
+}
+
+type Vector = [i128; 3];
+
+fn vector(point: Point3D) -> Vector{
+    [point.x as i128,point.y as i128,point.z as i128]
+}
+
+fn difference(vector1: Vector, vector2: Vector) -> Option<Vector>{
+    Some([vector1[0].checked_sub(vector2[0])?,vector1[1].checked_sub(vector2[1])?,vector1[2].checked_sub(vector2[2])?])
+}
+
+fn dot(vector1: Vector, vector2: Vector) -> Option<i128>{
+    vector1[0].checked_mul(vector2[0])?.checked_add(vector1[1].checked_mul(vector2[1])?)?.checked_add(vector1[2].checked_mul(vector2[2])?)
+}
+
+fn cross(vector1: Vector, vector2: Vector) -> Option<Vector>{
+    let component = |i: usize, j: usize| vector1[i].checked_mul(vector2[j])?.checked_sub(vector1[j].checked_mul(vector2[i])?);
+    Some([component(1,2)?,component(2,0)?,component(0,1)?])
+}
+
+fn norm_1(vector: Vector) -> Option<i128>{
+    vector[0].checked_abs()?.checked_add(vector[1].checked_abs()?)?.checked_add(vector[2].checked_abs()?)
+}
+
+//Returns floor(numerator/denominator) for a positive denominator.
+fn floor_div(numerator: i128, denominator: i128) -> i128{
+    numerator.div_euclid(denominator)
+}
+
+//A triangle with integer vertices and the normal (vertex2 - vertex1) x (vertex3 - vertex1).
+struct Triangle3D {
+    vertices: [Vector; 3],
+    normal: Vector,
+}
+
+impl Triangle3D {
+    fn new(point1: Point3D, point2: Point3D, point3: Point3D) -> Result<Triangle3D, GeometryError>{
+        let vertices = [vector(point1),vector(point2),vector(point3)];
+        let normal = difference(vertices[1],vertices[0])
+            .and_then(|edge1| difference(vertices[2],vertices[0]).and_then(|edge2| cross(edge1,edge2)))
+            .ok_or(GeometryError::Overflow)?;
+        Ok(Triangle3D{ vertices, normal,})
+    }
+
+    //touches_cell
+    //Purpose:
+    //    Returns true if the triangle touches the closed unit cube centered on the cell, or None if a computation overflows.
+    //Pre-conditions:
+    //    None.
+    //Notes:
+    //    The triangle and the cube are convex, so they are apart exactly when one of the 13 separating axis candidates parts them:
+    //    the axes of the cube, the normal of the triangle, and the cross products of the edges with the axes of the cube.
+    //    Everything is doubled so that the cube has half-width 1.
+    fn touches_cell(&self, cell: Vector) -> Option<bool>{
+        let mut doubled = [[0; 3]; 3];
+        for (point, vertex) in doubled.iter_mut().zip(self.vertices.iter()) {
+            let offset = difference(*vertex,cell)?;
+            *point = [offset[0].checked_mul(2)?,offset[1].checked_mul(2)?,offset[2].checked_mul(2)?];
+        }
+        let units: [Vector; 3] = [[1,0,0],[0,1,0],[0,0,1]];
+        let mut axes: Vec<Vector> = units.to_vec();
+        axes.push(self.normal);
+        for index in 0 .. 3 {
+            let edge = difference(doubled[(index + 1) % 3],doubled[index])?;
+            for unit in units.iter() {
+                axes.push(cross(edge,*unit)?);
+            }
+        }
+        for axis in axes {
+            let radius = norm_1(axis)?;
+            let mut lowest = i128::MAX;
+            let mut highest = i128::MIN;
+            for point in doubled.iter() {
+                let projection = dot(axis,*point)?;
+                lowest = lowest.min(projection);
+                highest = highest.max(projection);
+            }
+            if lowest > radius || highest < -radius {
+                return Some(false);
+            }
+        }
+        Some(true)
+    }
+
+    //near_cell
+    //Purpose:
+    //    Returns true if the center of the cell is on the separating surface of the triangle with the given plane thickness and edge radius,
+    //    or None if a computation overflows.
+    //Pre-conditions:
+    //    thickness is twice the half-width of the slab around the plane times |n|, so that the center is in the slab when 2|n.(cell - vertex)| <= thickness.
+    //    radius_squared is four times the square of the radius around the edges.
+    fn near_cell(&self, cell: Vector, thickness: i128, radius_squared: i128) -> Option<bool>{
+        if self.normal != [0,0,0] {
+            let offset = dot(self.normal,difference(cell,self.vertices[0])?)?;
+            if offset.checked_abs()?.checked_mul(2)? <= thickness {
+                let mut inside = true;
+                for index in 0 .. 3 {
+                    let edge = difference(self.vertices[(index + 1) % 3],self.vertices[index])?;
+                    let side = dot(cross(edge,difference(cell,self.vertices[index])?)?,self.normal)?;
+                    inside = inside && side >= 0;
+                }
+                if inside {
+                    return Some(true);
+                }
+            }
+        }
+        for index in 0 .. 3 {
+            let start = self.vertices[index];
+            let end = self.vertices[(index + 1) % 3];
+            let edge = difference(end,start)?;
+            let offset = difference(cell,start)?;
+            let length = dot(edge,edge)?;
+            let along = dot(offset,edge)?;
+            let near = if along <= 0 {
+                dot(offset,offset)?.checked_mul(4)? <= radius_squared
+            }else if along >= length {
+                let offset = difference(cell,end)?;
+                dot(offset,offset)?.checked_mul(4)? <= radius_squared
+            }else{
+                dot(offset,offset)?.checked_mul(length)?.checked_sub(along.checked_mul(along)?)?.checked_mul(4)? <= radius_squared.checked_mul(length)?
+            };
+            if near {
+                return Some(true);
+            }
+        }
+        Some(false)
+    }
+
+    fn contains_cell(&self, cell: Vector, mode: SurfaceVoxelization) -> Option<bool>{
+        match mode {
+            SurfaceVoxelization::Conservative => self.touches_cell(cell),
+            SurfaceVoxelization::SixSeparating => {
+                let thickness = self.normal.iter().map(|component| component.checked_abs()).max()??;
+                self.near_cell(cell,thickness,1)
+            },
+            SurfaceVoxelization::TwentySixSeparating => self.near_cell(cell,norm_1(self.normal)?,3),
+        }
+    }
+
+    //candidate_cells
+    //Purpose:
+    //    Returns the cells that could be on the surface of the triangle, or a GeometryError if there are too many or they do not fit in an isize.
+    //Pre-conditions:
+    //    None.
+    //Notes:
+    //    Every cell on the surface has its center within sqrt(3)/2 of the triangle.
+    //    Across the axis w along which the normal is largest the triangle is a graph over the other two axes u and v,
+    //    and such a cell is within 3 along w of where the plane crosses its column.
+    //    A triangle with no area is a segment, and such a cell is next to a cell on the segment.
+    fn candidate_cells(&self, points: [Point3D; 3]) -> Result<Vec<Vector>, GeometryError>{
+        let mut candidates: Vec<Vector> = Vec::new();
+        if self.normal == [0,0,0] {
+            let mut longest = (points[0],points[0]);
+            let mut length = 0;
+            for index in 0 .. 3 {
+                let edge = difference(self.vertices[(index + 1) % 3],self.vertices[index]).ok_or(GeometryError::Overflow)?;
+                let edge_length = norm_1(edge).ok_or(GeometryError::Overflow)?;
+                if edge_length >= length {
+                    length = edge_length;
+                    longest = (points[index],points[(index + 1) % 3]);
+                }
+            }
+            let cells = try_integer_cells_on_line_segment_3d(longest.0,longest.1)?;
+            check_cell_count::<Vector>(cells.len().checked_mul(27))?;
+            for cell in cells {
+                let cell = vector(cell);
+                for dx in -1 .. 2 {
+                    for dy in -1 .. 2 {
+                        for dz in -1 .. 2 {
+                            candidates.push([cell[0] + dx,cell[1] + dy,cell[2] + dz]);
+                        }
+                    }
+                }
+            }
+            return Ok(candidates);
+        }
+
+        let magnitudes: Vec<i128> = self.normal.iter().map(|component| component.abs()).collect();
+        let w = (0 .. 3).max_by_key(|&axis| (magnitudes[axis],axis)).unwrap_or(2);
+        let (u, v) = ((w + 1) % 3,(w + 2) % 3);
+        let low = |axis: usize| self.vertices.iter().map(|vertex| vertex[axis]).min().unwrap_or(0) - 1;
+        let high = |axis: usize| self.vertices.iter().map(|vertex| vertex[axis]).max().unwrap_or(0) + 1;
+        let columns = (high(u) - low(u) + 1).checked_mul(high(v) - low(v) + 1);
+        check_cell_count::<Vector>(columns.and_then(|columns| usize::try_from(columns).ok()).and_then(|columns| columns.checked_mul(8)))?;
+        let (normal_w, sign) = if self.normal[w] > 0 { (self.normal[w],1) } else { (-self.normal[w],-1) };
+        let origin = self.vertices[0];
+        for column_u in low(u) ..= high(u) {
+            for column_v in low(v) ..= high(v) {
+                //the plane crosses the column at w = origin[w] - (n_u*(u - origin[u]) + n_v*(v - origin[v]))/n_w.
+                let rise = self.normal[u].checked_mul(column_u - origin[u])
+                    .and_then(|rise| rise.checked_add(self.normal[v].checked_mul(column_v - origin[v])?))
+                    .and_then(|rise| rise.checked_mul(-sign))
+                    .ok_or(GeometryError::Overflow)?;
+                let crossing = origin[w] + floor_div(rise,normal_w);
+                for column_w in crossing - 3 ..= crossing + 4 {
+                    let mut cell = [0; 3];
+                    cell[u] = column_u;
+                    cell[v] = column_v;
+                    cell[w] = column_w;
+                    candidates.push(cell);
+                }
+            }
+        }
+        Ok(candidates)
+    }
+
+    //Pushes the cells on the surface of the triangle to cells.
+    fn surface_cells(&self, points: [Point3D; 3], mode: SurfaceVoxelization, cells: &mut Vec<Point3D>) -> Result<(), GeometryError>{
+        for candidate in self.candidate_cells(points)? {
+            if self.contains_cell(candidate,mode).ok_or(GeometryError::Overflow)? {
+                let coordinate = |index: usize| isize::try_from(candidate[index]).map_err(|_| GeometryError::RangeTooLarge);
+                cells.push(Point3D{ x: coordinate(0)?, y: coordinate(1)?, z: coordinate(2)?,});
+            }
+        }
+        Ok(())
+    }
+}
+
+//mesh_triangles
+//Purpose:
+//    Returns the vertices of every triangle of the mesh, or GeometryError::InvalidMesh if a triangle refers to a vertex the mesh does not have.
+//Pre-conditions:
+//    None.
+fn mesh_triangles(vertices: &[Point3D], triangles: &[[usize; 3]]) -> Result<Vec<[Point3D; 3]>, GeometryError>{
+    let mut points = Vec::new();
+    for triangle in triangles {
+        let vertex = |index: usize| vertices.get(triangle[index]).copied().ok_or(GeometryError::InvalidMesh);
+        points.push([vertex(0)?,vertex(1)?,vertex(2)?]);
+    }
+    Ok(points)
+}
+
+//mesh_or_panic
+//Purpose:
+//    Unwraps the cells of a triangle or mesh, panicking if they could not be found.
+//Pre-conditions:
+//    None.
+fn mesh_or_panic(cells: Result<Vec<Point3D>, GeometryError>) -> Vec<Point3D>{
+    match cells {
+        Ok(cells) => cells,
+        Err(GeometryError::InvalidMesh) => panic!("A triangle of the mesh refers to a vertex the mesh does not have!"),
+        Err(_) => panic!("Overflow while finding the cells of a mesh!"),
+    }
+}
+
+//integer_cells_on_triangle_3d
+//Purpose:
+//    Returns all integer cells (x,y,z) from a grid on the surface of the triangle with the given vertices under the given SurfaceVoxelization, sorted.
+//Pre-conditions:
+//    None.
+//Notes:
+//    A triangle with no area gives the cells of its longest edge in the same way.
+//    Conservative cells include those the triangle touches only at an edge or a corner of the cube,
+//    which integer_cells_on_line_segment_3d leaves out for a segment.
+pub fn integer_cells_on_triangle_3d(point1: Point3D, point2: Point3D, point3: Point3D, mode: SurfaceVoxelization) -> Vec<Point3D>{
+    mesh_or_panic(try_integer_cells_on_triangle_3d(point1,point2,point3,mode))
+}
+
+//try_integer_cells_on_triangle_3d
+//Purpose:
+//    Returns the same cells as integer_cells_on_triangle_3d, or a GeometryError if a computation overflows or the cells do not fit in a Vec.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_triangle_3d(point1: Point3D, point2: Point3D, point3: Point3D, mode: SurfaceVoxelization) -> Result<Vec<Point3D>, GeometryError>{
+    try_integer_cells_on_mesh_surface(&[point1,point2,point3],&[[0,1,2]],mode)
+}
+
+//integer_cells_on_mesh_surface
+//Purpose:
+//    Returns all integer cells (x,y,z) from a grid on the surface of any triangle of the mesh under the given SurfaceVoxelization, sorted and without repeats.
+//    Each triangle gives the indices of its three vertices in vertices.
+//Pre-conditions:
+//    Every index of a triangle is less than vertices.len().
+//Notes:
+//    The mesh does not need to be closed. When it is, the separating surfaces part the cells inside it from those outside.
+pub fn integer_cells_on_mesh_surface(vertices: &[Point3D], triangles: &[[usize; 3]], mode: SurfaceVoxelization) -> Vec<Point3D>{
+    mesh_or_panic(try_integer_cells_on_mesh_surface(vertices,triangles,mode))
+}
+
+//try_integer_cells_on_mesh_surface
+//Purpose:
+//    Returns the same cells as integer_cells_on_mesh_surface, or a GeometryError if a triangle refers to a vertex the mesh does not have,
+//    a computation overflows or the cells do not fit in a Vec.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_mesh_surface(vertices: &[Point3D], triangles: &[[usize; 3]], mode: SurfaceVoxelization) -> Result<Vec<Point3D>, GeometryError>{
+    let mut cells: Vec<Point3D> = Vec::new();
+    for points in mesh_triangles(vertices,triangles)? {
+        Triangle3D::new(points[0],points[1],points[2])?.surface_cells(points,mode,&mut cells)?;
+    }
+    cells.sort();
+    cells.dedup();
+    Ok(cells)
+}
+
+//integer_cells_inside_mesh
+//Purpose:
+//    Returns all integer cells (x,y,z) from a grid whose centers are inside the closed mesh under the given FillRule, sorted.
+//    Each triangle gives the indices of its three vertices in vertices.
+//    A center on the surface is inside when the points just past it towards larger x are inside (and on ties towards larger y, then larger z),
+//    so meshes that share a face never share a cell, as for integer_points_inside_polygon.
+//Pre-conditions:
+//    Every index of a triangle is less than vertices.len().
+//    The mesh is closed, every edge is shared by triangles that wind around it in opposite directions.
+//Notes:
+//    The orientation of the triangles only matters to which side of the mesh counts for FillRule::NonZero,
+//    either all facing out or all facing in give the same cells.
+pub fn integer_cells_inside_mesh(vertices: &[Point3D], triangles: &[[usize; 3]], rule: FillRule) -> Vec<Point3D>{
+    mesh_or_panic(try_integer_cells_inside_mesh(vertices,triangles,rule))
+}
+
+//try_integer_cells_inside_mesh
+//Purpose:
+//    Returns the same cells as integer_cells_inside_mesh, or a GeometryError if a triangle refers to a vertex the mesh does not have,
+//    a computation overflows, a triangle spans more than MAX_POLYGON_ROWS values of z (RangeTooLarge) or the cells do not fit in a Vec.
+//Pre-conditions:
+//    None.
+//Notes:
+//    Each triangle is projected along x and filled with integer_points_inside_triangle, whose shared-edge rule makes every column
+//    of cells along x cross each face of the mesh it passes through exactly once.
+//    The column enters or leaves the mesh at the first cell at or after the exact crossing with the plane of the triangle,
+//    and the cells between sorted crossings are filled by the winding number as in integer_points_inside_polygon.
+pub fn try_integer_cells_inside_mesh(vertices: &[Point3D], triangles: &[[usize; 3]], rule: FillRule) -> Result<Vec<Point3D>, GeometryError>{
+    let mut crossings: Vec<(isize, isize, i128, isize)> = Vec::new();
+    for points in mesh_triangles(vertices,triangles)? {
+        let triangle = Triangle3D::new(points[0],points[1],points[2])?;
+        let normal = triangle.normal;
+        if normal[0] == 0 {
+            continue;
+        }
+        //a column along +x leaves a mesh whose triangles face out where the normal points along +x.
+        let (normal_x, sign, winding) = if normal[0] > 0 { (normal[0],1,-1) } else { (-normal[0],-1,1) };
+        let origin = triangle.vertices[0];
+        let project = |point: Point3D| Point2D{ x: point.y, y: point.z,};
+        for column in try_integer_points_inside_triangle(project(points[0]),project(points[1]),project(points[2]))? {
+            //the plane crosses the column at x = origin.x - (n_y*(y - origin.y) + n_z*(z - origin.z))/n_x.
+            let rise = normal[1].checked_mul(column.x as i128 - origin[1])
+                .and_then(|rise| rise.checked_add(normal[2].checked_mul(column.y as i128 - origin[2])?))
+                .and_then(|rise| rise.checked_mul(sign))
+                .ok_or(GeometryError::Overflow)?;
+            let first_cell = origin[0] - floor_div(rise,normal_x);
+            crossings.push((column.x,column.y,first_cell,winding));
+        }
+    }
+    crossings.sort();
+
+    let mut cells: Vec<Point3D> = Vec::new();
+    let mut count: usize = 0;
+    let mut winding = 0;
+    for pair in crossings.windows(2) {
+        let ((y, z, start, step), (next_y, next_z, end, _)) = (pair[0],pair[1]);
+        if (y, z) != (next_y, next_z) {
+            winding = 0;
+            continue;
+        }
+        winding += step;
+        if rule.is_inside(winding) && start < end {
+            let start = isize::try_from(start).map_err(|_| GeometryError::RangeTooLarge)?;
+            let end = isize::try_from(end).map_err(|_| GeometryError::RangeTooLarge)?;
+            count = count.checked_add((end as usize).wrapping_sub(start as usize)).ok_or(GeometryError::RangeTooLarge)?;
+            check_cell_count::<Point3D>(Some(count))?;
+            cells.extend((start .. end).map(|x| Point3D{ x, y, z,}));
+        }
+    }
+    cells.sort();
+    Ok(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: isize, y: isize, z: isize) -> Point3D{
+        Point3D{ x, y, z,}
+    }
+
+    //Returns the triangles of the convex hull faces given, each turned to face away from the inner point.
+    fn facing_out(vertices: &[Point3D], faces: &[[usize; 3]], inner: Point3D) -> Vec<[usize; 3]>{
+        faces.iter().map(|&[a, b, c]| {
+            let triangle = Triangle3D::new(vertices[a],vertices[b],vertices[c]).unwrap();
+            let towards = dot(triangle.normal,difference(vector(inner),vector(vertices[a])).unwrap()).unwrap();
+            if towards > 0 { [a,c,b] } else { [a,b,c] }
+        }).collect()
+    }
+
+    //Returns the faces of the octahedron with the given vertices, the first two opposite along x, then along y, then along z.
+    fn octahedron(vertices: &[Point3D]) -> Vec<[usize; 3]>{
+        let mut faces = Vec::new();
+        for &a in [0,1].iter() {
+            for &b in [2,3].iter() {
+                for &c in [4,5].iter() {
+                    faces.push([a,b,c]);
+                }
+            }
+        }
+        facing_out(vertices,&faces,point(0,0,0))
+    }
+
+    #[test]
+    fn triangle_surfaces(){
+        let triangles = [
+            [point(0,0,0),point(7,0,0),point(0,5,0)],
+            [point(-3,2,1),point(6,-4,5),point(2,7,-3)],
+            [point(1,1,1),point(9,4,2),point(-2,6,8)],
+            [point(0,0,0),point(3,3,3),point(6,6,6)],
+            [point(2,-1,4),point(2,-1,4),point(2,-1,4)],
+            [point(0,0,0),point(10,1,0),point(0,1,1)],
+        ];
+        for points in triangles.iter() {
+            let triangle = Triangle3D::new(points[0],points[1],points[2]).unwrap();
+            let mut surfaces = Vec::new();
+            for mode in [SurfaceVoxelization::Conservative,SurfaceVoxelization::SixSeparating,SurfaceVoxelization::TwentySixSeparating].iter() {
+                let cells = integer_cells_on_triangle_3d(points[0],points[1],points[2],*mode);
+                //every cell near the triangle is found among the candidates.
+                let mut slow = Vec::new();
+                for x in -6 .. 13 {
+                    for y in -8 .. 11 {
+                        for z in -6 .. 12 {
+                            if triangle.contains_cell([x,y,z],*mode).unwrap() {
+                                slow.push(point(x as isize,y as isize,z as isize));
+                            }
+                        }
+                    }
+                }
+                assert_eq!(cells,slow,"{:?} {:?}",points,mode);
+                surfaces.push(cells);
+            }
+            let [conservative, six, twenty_six] = [&surfaces[0],&surfaces[1],&surfaces[2]];
+            assert!(six.iter().all(|cell| twenty_six.contains(cell)));
+            assert!(conservative.iter().all(|cell| twenty_six.contains(cell)));
+            //the cells of points spread over the triangle, in eighths.
+            for i in 0 .. 9 {
+                for j in 0 .. 9 - i {
+                    let k = 8 - i - j;
+                    let coordinate = |a: isize, b: isize, c: isize| i as isize*a + j as isize*b + k as isize*c;
+                    let sample = [coordinate(points[0].x,points[1].x,points[2].x),coordinate(points[0].y,points[1].y,points[2].y),coordinate(points[0].z,points[1].z,points[2].z)];
+                    let nearest = point((sample[0] + 4).div_euclid(8),(sample[1] + 4).div_euclid(8),(sample[2] + 4).div_euclid(8));
+                    assert!(conservative.contains(&nearest));
+                }
+            }
+        }
+        let flat = integer_cells_on_triangle_3d(point(0,0,0),point(7,0,0),point(0,5,0),SurfaceVoxelization::TwentySixSeparating);
+        assert!(flat.iter().all(|cell| cell.z == 0));
+        //the segment also touches cells such as (1,0,0) at a corner, which integer_cells_on_line_segment_3d leaves out.
+        let segment = integer_cells_on_triangle_3d(point(0,0,0),point(3,3,3),point(6,6,6),SurfaceVoxelization::Conservative);
+        assert!(crate::line::integer_cells_on_line_segment_3d(point(0,0,0),point(6,6,6)).iter().all(|cell| segment.contains(cell)));
+        assert!(segment.contains(&point(1,0,0)));
+    }
+
+    #[test]
+    fn separating_surfaces(){
+        let vertices = [point(-7,1,0),point(8,-1,1),point(0,-6,-1),point(1,7,2),point(1,0,-8),point(-1,2,6)];
+        let faces = octahedron(&vertices);
+        let inside = integer_cells_inside_mesh(&vertices,&faces,FillRule::NonZero);
+        for (mode, steps) in [(SurfaceVoxelization::Conservative,26),(SurfaceVoxelization::TwentySixSeparating,26),(SurfaceVoxelization::SixSeparating,6)].iter() {
+            let surface = integer_cells_on_mesh_surface(&vertices,&faces,*mode);
+            let mut neighbours = Vec::new();
+            for dx in -1 .. 2isize {
+                for dy in -1 .. 2isize {
+                    for dz in -1 .. 2isize {
+                        if dx.abs() + dy.abs() + dz.abs() == 1 || (*steps == 26 && (dx, dy, dz) != (0, 0, 0)) {
+                            neighbours.push((dx,dy,dz));
+                        }
+                    }
+                }
+            }
+            //walk the cells off the surface from a corner of a box around the mesh, never reaching a cell inside it.
+            let index = |cell: Point3D| ((cell.x + 11)*23*23 + (cell.y + 11)*23 + cell.z + 11) as usize;
+            let mut seen = vec![false; 23*23*23];
+            let mut reached = vec![point(-11,-11,-11)];
+            let mut frontier = reached.clone();
+            seen[index(reached[0])] = true;
+            while let Some(cell) = frontier.pop() {
+                for &(dx, dy, dz) in neighbours.iter() {
+                    let next = point(cell.x + dx,cell.y + dy,cell.z + dz);
+                    if next.x.abs() <= 11 && next.y.abs() <= 11 && next.z.abs() <= 11 && !seen[index(next)] && surface.binary_search(&next).is_err() {
+                        seen[index(next)] = true;
+                        reached.push(next);
+                        frontier.push(next);
+                    }
+                }
+            }
+            assert!(inside.iter().any(|cell| surface.binary_search(cell).is_err()),"{:?}",mode);
+            assert!(reached.iter().all(|cell| inside.binary_search(cell).is_err()),"{:?}",mode);
+        }
+    }
+
+    #[test]
+    fn solid_meshes(){
+        //a cell is inside a convex mesh with faces facing out when its center, nudged a little towards larger x,
+        //far less towards larger y and even less towards larger z, is behind every face.
+        let scale: i128 = 1_000_000_000_000;
+        let convex_slow = |vertices: &[Point3D], faces: &[[usize; 3]]| {
+            let mut cells = Vec::new();
+            for x in -10 .. 11 {
+                for y in -10 .. 11 {
+                    for z in -10 .. 11 {
+                        let nudged = [x*scale + 100_000_000,y*scale + 10_000,z*scale + 1];
+                        let behind = faces.iter().all(|&[a, b, c]| {
+                            let triangle = Triangle3D::new(vertices[a],vertices[b],vertices[c]).unwrap();
+                            let corner = triangle.vertices[0];
+                            dot(triangle.normal,[nudged[0] - corner[0]*scale,nudged[1] - corner[1]*scale,nudged[2] - corner[2]*scale]).unwrap() < 0
+                        });
+                        if behind {
+                            cells.push(point(x as isize,y as isize,z as isize));
+                        }
+                    }
+                }
+            }
+            cells
+        };
+        let octahedra = [
+            vec![point(-7,1,0),point(8,-1,1),point(0,-6,-1),point(1,7,2),point(1,0,-8),point(-1,2,6)],
+            vec![point(-4,0,0),point(4,0,0),point(0,-4,0),point(0,4,0),point(0,0,-4),point(0,0,4)],
+        ];
+        for vertices in octahedra.iter() {
+            let faces = octahedron(vertices);
+            let slow = convex_slow(vertices,&faces);
+            for rule in [FillRule::EvenOdd,FillRule::NonZero].iter() {
+                assert_eq!(integer_cells_inside_mesh(vertices,&faces,*rule),slow);
+                let inward: Vec<[usize; 3]> = faces.iter().map(|&[a, b, c]| [a,c,b]).collect();
+                assert_eq!(integer_cells_inside_mesh(vertices,&inward,*rule),slow);
+            }
+        }
+
+        //two tetrahedra sharing a face split the cells of the double pyramid between them.
+        let vertices = [point(-3,-2,-1),point(5,-1,1),point(0,6,-2),point(1,1,7),point(-1,0,-8)];
+        let top = facing_out(&vertices,&[[0,1,2],[0,1,3],[1,2,3],[0,2,3]],point(0,1,2));
+        let bottom = facing_out(&vertices,&[[0,1,2],[0,1,4],[1,2,4],[0,2,4]],point(0,1,-3));
+        let both = facing_out(&vertices,&[[0,1,3],[1,2,3],[0,2,3],[0,1,4],[1,2,4],[0,2,4]],point(0,1,0));
+        let mut split = integer_cells_inside_mesh(&vertices,&top,FillRule::EvenOdd);
+        split.extend(integer_cells_inside_mesh(&vertices,&bottom,FillRule::EvenOdd));
+        split.sort();
+        assert_eq!(split,integer_cells_inside_mesh(&vertices,&both,FillRule::EvenOdd));
+        assert_eq!(split,convex_slow(&vertices,&both));
+    }
+
+    #[test]
+    fn invalid_meshes(){
+        let vertices = [point(0,0,0),point(1,0,0),point(0,1,0)];
+        assert_eq!(try_integer_cells_on_mesh_surface(&vertices,&[[0,1,3]],SurfaceVoxelization::Conservative),Err(GeometryError::InvalidMesh));
+        assert_eq!(try_integer_cells_inside_mesh(&vertices,&[[0,1,2],[2,1,5]],FillRule::NonZero),Err(GeometryError::InvalidMesh));
+        assert_eq!(try_integer_cells_on_mesh_surface(&vertices,&[],SurfaceVoxelization::SixSeparating),Ok(Vec::new()));
+        let huge = try_integer_cells_on_triangle_3d(point(isize::MIN,0,0),point(isize::MAX,0,0),point(0,isize::MAX,isize::MIN),SurfaceVoxelization::SixSeparating);
+        assert!(huge == Err(GeometryError::Overflow) || huge == Err(GeometryError::RangeTooLarge));
+        assert_eq!(try_integer_cells_on_triangle_3d(point(0,0,0),point(1 << 40,0,0),point(0,1 << 40,0),SurfaceVoxelization::Conservative),Err(GeometryError::RangeTooLarge));
+    }
+}
@@ -0,0 +1,613 @@
+//Purpose: Contains readers for triangle meshes in Wavefront OBJ and ASCII or binary STL files,
+//         giving vertices and triangles that can be passed to the mesh and 3D line functions.
+//Notes:
+//    Decimal coordinates are read exactly as Rationals, never through a float, and binary STL floats are converted exactly.
+//    Every vertex is scaled and then offset by a MeshTransform as it is read, so that a file in any unit can be fit to the grid.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use crate::error::GeometryError;
+use crate::point::{Point3D, RationalPoint3D};
+use crate::rational::Rational;
+
+//ParseErrorKind
+//Purpose:
+//    The reasons a mesh file can be rejected.
+//    InvalidNumber: a coordinate is not a decimal number, or is too precise or too large for a Rational.
+//    MissingValue: a vertex has fewer than three coordinates or a face fewer than three vertices.
+//    InvalidIndex: a face refers to a vertex index of 0, or to a vertex that has not been read yet.
+//    UnexpectedToken: a keyword or value appears where the format does not allow it.
+//    UnexpectedEnd: the file ends in the middle of a solid, a facet or a binary triangle.
+//    Overflow: scaling or offsetting a vertex overflowed a Rational.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    InvalidNumber,
+    MissingValue,
+    InvalidIndex,
+    UnexpectedToken,
+    UnexpectedEnd,
+    Overflow,
+}
+
+//ParseError
+//Purpose:
+//    The reason a mesh file could not be read, and the line it was found on, counted from 1.
+//Notes:
+//    For a binary STL file the line is the number of the triangle, counted from 1, or 0 for the header.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self.kind {
+            ParseErrorKind::InvalidNumber => "invalid or unrepresentable number",
+            ParseErrorKind::MissingValue => "missing coordinate or face vertex",
+            ParseErrorKind::InvalidIndex => "vertex index outside the mesh",
+            ParseErrorKind::UnexpectedToken => "unexpected token",
+            ParseErrorKind::UnexpectedEnd => "unexpected end of file",
+            ParseErrorKind::Overflow => "overflow while transforming a vertex",
+        };
+        write!(f, "line {}: {}", self.line, reason)
+    }
+}
+
+impl Error for ParseError {}
+
+//MeshTransform
+//Purpose:
+//    Maps every vertex v read from a file to v*scale + offset.
+#[derive(Debug, Copy, Clone)]
+pub struct MeshTransform {
+    pub scale: Rational,
+    pub offset: RationalPoint3D,
+}
+
+impl MeshTransform {
+    //Returns the transform that leaves every vertex as it is.
+    pub fn identity() -> MeshTransform{
+        MeshTransform::new_integer(1,Point3D{ x: 0, y: 0, z: 0,})
+    }
+
+    pub fn new(scale: Rational, offset: RationalPoint3D) -> MeshTransform{
+        MeshTransform{ scale, offset,}
+    }
+
+    pub fn new_integer(scale: isize, offset: Point3D) -> MeshTransform{
+        MeshTransform{ scale: Rational::new_rational_from_integer(scale), offset: RationalPoint3D::from(offset),}
+    }
+
+    //Returns vertex*scale + offset, or None if a computation overflows.
+    fn apply(&self, vertex: [Rational; 3]) -> Option<RationalPoint3D>{
+        let coordinate = |value: Rational, offset: Rational| value.checked_mul(self.scale)?.checked_add(offset);
+        Some(RationalPoint3D{
+            x: coordinate(vertex[0],self.offset.x)?,
+            y: coordinate(vertex[1],self.offset.y)?,
+            z: coordinate(vertex[2],self.offset.z)?,
+        })
+    }
+}
+
+//Mesh
+//Purpose:
+//    The vertices of a mesh and its triangles, each given by the indices of its three vertices.
+//Notes:
+//    Faces with more than three vertices are split into a fan of triangles around their first vertex.
+#[derive(Debug, Clone)]
+pub struct Mesh {
+    pub vertices: Vec<RationalPoint3D>,
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl Mesh {
+    //rounded_vertices
+    //Purpose:
+    //    Returns every vertex rounded to the nearest integer point, with halves rounded up,
+    //    as integer_cells_on_mesh_surface and integer_cells_inside_mesh take them.
+    //Pre-conditions:
+    //    None.
+    //Notes:
+    //    Returns GeometryError::RangeTooLarge if a rounded coordinate does not fit in an isize.
+    pub fn rounded_vertices(&self) -> Result<Vec<Point3D>, GeometryError>{
+        let half = Rational::new_rational(1,2);
+        let round = |value: Rational| value.checked_add(half).and_then(|value| value.checked_floor()).ok_or(GeometryError::RangeTooLarge);
+        self.vertices.iter().map(|vertex| Ok(Point3D{ x: round(vertex.x)?, y: round(vertex.y)?, z: round(vertex.z)?,})).collect()
+    }
+
+    //edges
+    //Purpose:
+    //    Returns the edges of the triangles as pairs of vertex indices, the smaller index first, sorted and without repeats,
+    //    such as for walking every edge with integer_cells_on_line_segment_3d.
+    //Pre-conditions:
+    //    None.
+    pub fn edges(&self) -> Vec<[usize; 2]>{
+        let mut edges: Vec<[usize; 2]> = Vec::new();
+        for triangle in &self.triangles {
+            for index in 0 .. 3 {
+                let (start, end) = (triangle[index],triangle[(index + 1) % 3]);
+                edges.push([start.min(end),start.max(end)]);
+            }
+        }
+        edges.sort();
+        edges.dedup();
+        edges
+    }
+}
+
+//parse_decimal
+//Purpose:
+//    Returns the exact Rational written as a decimal number, with an optional sign, fraction and exponent, such as -12.5e-3,
+//    or None if it is not such a number or does not fit in a Rational.
+//Pre-conditions:
+//    None.
+fn parse_decimal(token: &str) -> Option<Rational>{
+    let (negative, rest) = match token.as_bytes().first() {
+        Some(b'-') => (true,&token[1 ..]),
+        Some(b'+') => (false,&token[1 ..]),
+        _ => (false,token),
+    };
+    let (mantissa, exponent) = match rest.find(['e','E']) {
+        Some(position) => (&rest[.. position],rest[position + 1 ..].parse::<i32>().ok()?),
+        None => (rest,0),
+    };
+    let (whole, fraction) = match mantissa.find('.') {
+        Some(position) => (&mantissa[.. position],&mantissa[position + 1 ..]),
+        None => (mantissa,""),
+    };
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    if !whole.bytes().chain(fraction.bytes()).all(|digit| digit.is_ascii_digit()) {
+        return None;
+    }
+    //trailing zeros of the fraction and leading zeros of the number do not change it, and would only overflow the numerator.
+    let fraction = fraction.trim_end_matches('0');
+    let digits = whole.to_string() + fraction;
+    let digits = digits.trim_start_matches('0');
+    let mut numerator: usize = 0;
+    for digit in digits.bytes() {
+        numerator = numerator.checked_mul(10)?.checked_add((digit - b'0') as usize)?;
+    }
+    if numerator == 0 {
+        return Some(Rational::new_rational_from_integer(0));
+    }
+    let exponent = exponent.checked_sub(i32::try_from(fraction.len()).ok()?)?;
+    let power = 10usize.checked_pow(exponent.unsigned_abs())?;
+    let value = if exponent >= 0 {
+        Rational::new_signed_rational(numerator.checked_mul(power)?,1,negative)
+    }else{
+        Rational::new_signed_rational(numerator,power,negative)
+    };
+    Some(value)
+}
+
+//Returns the next token of a line, or a ParseError with the given kind if there is none.
+fn next_token<'a>(tokens: &mut dyn Iterator<Item = &'a str>, line: usize, kind: ParseErrorKind) -> Result<&'a str, ParseError>{
+    tokens.next().ok_or(ParseError{ line, kind,})
+}
+
+//parse_vertex
+//Purpose:
+//    Reads the three coordinates of a vertex from the tokens of a line and transforms it.
+//Pre-conditions:
+//    None.
+fn parse_vertex(tokens: &mut dyn Iterator<Item = &str>, line: usize, transform: &MeshTransform) -> Result<RationalPoint3D, ParseError>{
+    let mut coordinates = [Rational::new_rational_from_integer(0); 3];
+    for coordinate in coordinates.iter_mut() {
+        let token = next_token(tokens,line,ParseErrorKind::MissingValue)?;
+        *coordinate = parse_decimal(token).ok_or(ParseError{ line, kind: ParseErrorKind::InvalidNumber,})?;
+    }
+    transform.apply(coordinates).ok_or(ParseError{ line, kind: ParseErrorKind::Overflow,})
+}
+
+//parse_obj
+//Purpose:
+//    Returns the mesh given by the v and f statements of a Wavefront OBJ file, with every vertex transformed,
+//    or a ParseError giving the line it was found on.
+//Pre-conditions:
+//    None.
+//Notes:
+//    A face vertex may be written as v, v/vt, v//vn or v/vt/vn, and only v is used. A negative v counts back from the last vertex read.
+//    A face may only refer to vertices read before it, as in every OBJ file written by common tools.
+//    Any other statement (normals, texture coordinates, groups, materials and so on) is skipped, as are comments after #.
+pub fn parse_obj(text: &str, transform: &MeshTransform) -> Result<Mesh, ParseError>{
+    let mut mesh = Mesh{ vertices: Vec::new(), triangles: Vec::new(),};
+    for (index, content) in text.lines().enumerate() {
+        let line = index + 1;
+        let content = content.split('#').next().unwrap_or("");
+        let mut tokens = content.split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                let vertex = parse_vertex(&mut tokens,line,transform)?;
+                mesh.vertices.push(vertex);
+            },
+            Some("f") => {
+                let mut face: Vec<usize> = Vec::new();
+                for token in tokens {
+                    let position = token.split('/').next().unwrap_or("");
+                    let position = position.parse::<isize>().map_err(|_| ParseError{ line, kind: ParseErrorKind::InvalidNumber,})?;
+                    let vertex = if position > 0 {
+                        position as usize - 1
+                    }else{
+                        mesh.vertices.len().checked_sub(position.unsigned_abs()).ok_or(ParseError{ line, kind: ParseErrorKind::InvalidIndex,})?
+                    };
+                    if vertex >= mesh.vertices.len() {
+                        return Err(ParseError{ line, kind: ParseErrorKind::InvalidIndex,});
+                    }
+                    face.push(vertex);
+                }
+                if face.len() < 3 {
+                    return Err(ParseError{ line, kind: ParseErrorKind::MissingValue,});
+                }
+                for corner in 1 .. face.len() - 1 {
+                    mesh.triangles.push([face[0],face[corner],face[corner + 1]]);
+                }
+            },
+            _ => {},
+        }
+    }
+    Ok(mesh)
+}
+
+//MeshBuilder
+//Purpose:
+//    Collects the triangles of an STL file, giving every distinct vertex one index so that neighbouring triangles share them.
+struct MeshBuilder {
+    mesh: Mesh,
+    indices: BTreeMap<(Rational, Rational, Rational), usize>,
+}
+
+impl MeshBuilder {
+    fn new() -> MeshBuilder{
+        MeshBuilder{ mesh: Mesh{ vertices: Vec::new(), triangles: Vec::new(),}, indices: BTreeMap::new(),}
+    }
+
+    fn push_triangle(&mut self, triangle: [RationalPoint3D; 3]){
+        let mut indices = [0; 3];
+        for (index, vertex) in indices.iter_mut().zip(triangle.iter()) {
+            let next = self.mesh.vertices.len();
+            *index = *self.indices.entry((vertex.x,vertex.y,vertex.z)).or_insert(next);
+            if *index == next {
+                self.mesh.vertices.push(*vertex);
+            }
+        }
+        self.mesh.triangles.push(indices);
+    }
+}
+
+//parse_ascii_stl
+//Purpose:
+//    Returns the mesh given by the facets of an ASCII STL file, or a ParseError giving the line it was found on.
+//Pre-conditions:
+//    None.
+//Notes:
+//    The normals of the facets are checked to be numbers but otherwise ignored, the order of the vertices gives the orientation.
+//    Several solids may follow each other, their facets all go to the one mesh.
+fn parse_ascii_stl(text: &str, transform: &MeshTransform) -> Result<Mesh, ParseError>{
+    //the statements the parser may read next, in the order they appear in a solid.
+    #[derive(PartialEq)]
+    enum Expect { Solid, Facet, OuterLoop, Vertex(usize), EndLoop, EndFacet }
+
+    let mut builder = MeshBuilder::new();
+    let mut expect = Expect::Solid;
+    let mut triangle = [RationalPoint3D::from(Point3D{ x: 0, y: 0, z: 0,}); 3];
+    let mut last_line = 0;
+    for (index, content) in text.lines().enumerate() {
+        let line = index + 1;
+        last_line = line;
+        let mut tokens = content.split_whitespace();
+        let keyword = match tokens.next() {
+            Some(keyword) => keyword,
+            None => continue,
+        };
+        let unexpected = ParseError{ line, kind: ParseErrorKind::UnexpectedToken,};
+        match (keyword, &expect) {
+            ("solid", Expect::Solid) => {
+                expect = Expect::Facet;
+                continue; //the rest of the line is the name of the solid.
+            },
+            ("endsolid", Expect::Facet) => {
+                expect = Expect::Solid;
+                continue;
+            },
+            ("facet", Expect::Facet) => {
+                if next_token(&mut tokens,line,ParseErrorKind::MissingValue)? != "normal" {
+                    return Err(unexpected);
+                }
+                for _ in 0 .. 3 {
+                    parse_decimal(next_token(&mut tokens,line,ParseErrorKind::MissingValue)?).ok_or(ParseError{ line, kind: ParseErrorKind::InvalidNumber,})?;
+                }
+                expect = Expect::OuterLoop;
+            },
+            ("outer", Expect::OuterLoop) => {
+                if next_token(&mut tokens,line,ParseErrorKind::MissingValue)? != "loop" {
+                    return Err(unexpected);
+                }
+                expect = Expect::Vertex(0);
+            },
+            ("vertex", Expect::Vertex(corner)) => {
+                let corner = *corner;
+                triangle[corner] = parse_vertex(&mut tokens,line,transform)?;
+                expect = if corner == 2 { Expect::EndLoop } else { Expect::Vertex(corner + 1) };
+            },
+            ("endloop", Expect::EndLoop) => {
+                expect = Expect::EndFacet;
+            },
+            ("endfacet", Expect::EndFacet) => {
+                builder.push_triangle(triangle);
+                expect = Expect::Facet;
+            },
+            _ => return Err(unexpected),
+        }
+        if tokens.next().is_some() {
+            return Err(unexpected);
+        }
+    }
+    if expect != Expect::Solid {
+        return Err(ParseError{ line: last_line, kind: ParseErrorKind::UnexpectedEnd,});
+    }
+    Ok(builder.mesh)
+}
+
+//parse_binary_stl
+//Purpose:
+//    Returns the mesh given by the triangles of a binary STL file, or a ParseError giving the number of the triangle it was found in.
+//Pre-conditions:
+//    bytes holds at least the 84 bytes of the header and the triangle count.
+//Notes:
+//    Every float is converted exactly with Rational::from_f64_exact. The normals and attribute bytes are ignored.
+fn parse_binary_stl(bytes: &[u8], transform: &MeshTransform) -> Result<Mesh, ParseError>{
+    let count = u32::from_le_bytes([bytes[80],bytes[81],bytes[82],bytes[83]]) as usize;
+    let mut builder = MeshBuilder::new();
+    for number in 0 .. count {
+        let line = number + 1;
+        let record = bytes.get(84 + 50*number .. 84 + 50*line).ok_or(ParseError{ line, kind: ParseErrorKind::UnexpectedEnd,})?;
+        let mut triangle = [RationalPoint3D::from(Point3D{ x: 0, y: 0, z: 0,}); 3];
+        for (corner, vertex) in triangle.iter_mut().enumerate() {
+            let mut coordinates = [Rational::new_rational_from_integer(0); 3];
+            for (axis, coordinate) in coordinates.iter_mut().enumerate() {
+                let start = 12 + 12*corner + 4*axis;
+                let value = f32::from_le_bytes([record[start],record[start + 1],record[start + 2],record[start + 3]]);
+                *coordinate = Rational::from_f64_exact(value as f64).ok_or(ParseError{ line, kind: ParseErrorKind::InvalidNumber,})?;
+            }
+            *vertex = transform.apply(coordinates).ok_or(ParseError{ line, kind: ParseErrorKind::Overflow,})?;
+        }
+        builder.push_triangle(triangle);
+    }
+    if bytes.len() > 84 + 50*count {
+        return Err(ParseError{ line: count + 1, kind: ParseErrorKind::UnexpectedToken,});
+    }
+    Ok(builder.mesh)
+}
+
+//parse_stl
+//Purpose:
+//    Returns the mesh given by an ASCII or binary STL file, with every vertex transformed and every distinct vertex given one index,
+//    or a ParseError giving the line (or for a binary file the triangle) it was found on.
+//Pre-conditions:
+//    None.
+//Notes:
+//    A file is read as binary when its length is exactly that of a binary file with the triangle count in its header,
+//    since the 80 byte header of a binary file may itself start with "solid".
+//    Otherwise it is read as ASCII if it is text starting with "solid" and has no NUL bytes, which binary counts and floats nearly always have,
+//    and as a binary file of the wrong length if not.
+//    Text too short to be a binary file is read as ASCII even when it does not start with "solid",
+//    so the error is an UnexpectedToken on its first non-blank line.
+//    Only an empty or blank input, or a short one that is not text, is an UnexpectedEnd on line 0.
+pub fn parse_stl(bytes: &[u8], transform: &MeshTransform) -> Result<Mesh, ParseError>{
+    if bytes.len() >= 84 {
+        let count = u32::from_le_bytes([bytes[80],bytes[81],bytes[82],bytes[83]]) as u64;
+        if bytes.len() as u64 == 84 + 50*count {
+            return parse_binary_stl(bytes,transform);
+        }
+    }
+    let text = std::str::from_utf8(bytes).ok().filter(|text| !text.contains('\0'));
+    match text {
+        Some(text) if text.trim_start().starts_with("solid") => parse_ascii_stl(text,transform),
+        _ if bytes.len() >= 84 => parse_binary_stl(bytes,transform),
+        Some(text) if !text.trim().is_empty() => parse_ascii_stl(text,transform),
+        _ => Err(ParseError{ line: 0, kind: ParseErrorKind::UnexpectedEnd,}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line::integer_cells_on_line_segment_3d;
+    use crate::mesh::{integer_cells_inside_mesh, integer_cells_on_mesh_surface, SurfaceVoxelization};
+    use crate::polygon::FillRule;
+    use crate::rational;
+
+    fn coordinates(mesh: &Mesh) -> Vec<(Rational, Rational, Rational)>{
+        mesh.vertices.iter().map(|vertex| (vertex.x,vertex.y,vertex.z)).collect()
+    }
+
+    //Returns a binary STL file with the given triangles, each corner given by three floats.
+    fn binary_stl(triangles: &[[[f32; 3]; 3]]) -> Vec<u8>{
+        let mut bytes = b"solid written by a tool that puts the name in the header".to_vec();
+        bytes.resize(80,b' ');
+        bytes.extend_from_slice(&(triangles.len() as u32).to_le_bytes());
+        for triangle in triangles {
+            bytes.extend_from_slice(&[0; 12]);
+            for corner in triangle {
+                for coordinate in corner {
+                    bytes.extend_from_slice(&coordinate.to_le_bytes());
+                }
+            }
+            bytes.extend_from_slice(&[0; 2]);
+        }
+        bytes
+    }
+
+    const CUBE_OBJ: &str = "# a cube from -1.5 to 2.5
+mtllib cube.mtl
+o cube
+v -1.5 -1.5 -1.5
+v 2.5 -1.5 -1.5
+v 2.5 2.5 -1.5
+v -1.5 2.5 -1.5
+v -1.5 -1.5 2.5
+v 2.5 -1.5 2.5
+v 2.5 2.5 2.5
+v -15e-1 25E-1 0.25e1 # the last corner
+vt 0 0
+vn 0 0 -1
+usemtl grey
+f 1/1/1 4/1/1 3/1/1 2/1/1
+f 5//1 6//1 7//1 8//1
+f 1 2 6 5
+f 2 3 7 6
+f -5 -1 -2 -6
+f -8 -4 -1 -5
+";
+
+    #[test]
+    fn decimals(){
+        assert_eq!(parse_decimal("0.1"),Some(rational(1,10)));
+        assert_eq!(parse_decimal("12.5e-3"),Some(rational(1,80)));
+        assert_eq!(parse_decimal("-3E2"),Some(rational(-300,1)));
+        assert_eq!(parse_decimal("+.5"),Some(rational(1,2)));
+        assert_eq!(parse_decimal("7."),Some(rational(7,1)));
+        assert_eq!(parse_decimal("1.000000000000000000000000000"),Some(rational(1,1)));
+        assert_eq!(parse_decimal("0e-40"),Some(rational(0,1)));
+        for token in ["",".","-","1e","1.2.3","0x10","nan","inf","1e-30","123456789012345678901234"].iter() {
+            assert_eq!(parse_decimal(token),None,"{}",token);
+        }
+    }
+
+    #[test]
+    fn obj_files(){
+        let transform = MeshTransform::new_integer(2,Point3D{ x: 1, y: 0, z: -1,});
+        let mesh = parse_obj(CUBE_OBJ,&transform).unwrap();
+        assert_eq!(mesh.vertices.len(),8);
+        assert_eq!(mesh.triangles.len(),12);
+        assert_eq!(mesh.edges().len(),18);
+        assert_eq!(coordinates(&mesh)[7],(rational(-2,1),rational(5,1),rational(4,1)));
+        assert_eq!(mesh.triangles[0..2],[[0,3,2],[0,2,1]]);
+        assert_eq!(mesh.triangles[8],[3,7,6]);
+
+        //the cube now spans [-2, 6) x [-3, 5) x [-4, 4) in cells.
+        let vertices = mesh.rounded_vertices().unwrap();
+        let mut cube = Vec::new();
+        for x in -2 .. 6 {
+            for y in -3 .. 5 {
+                for z in -4 .. 4 {
+                    cube.push(Point3D{ x, y, z,});
+                }
+            }
+        }
+        assert_eq!(integer_cells_inside_mesh(&vertices,&mesh.triangles,FillRule::NonZero),cube);
+        let surface = integer_cells_on_mesh_surface(&vertices,&mesh.triangles,SurfaceVoxelization::Conservative);
+        for [start, end] in mesh.edges() {
+            assert!(integer_cells_on_line_segment_3d(vertices[start],vertices[end]).iter().all(|cell| surface.binary_search(cell).is_ok()));
+        }
+
+        let halved = parse_obj(CUBE_OBJ,&MeshTransform::new(rational(1,2),RationalPoint3D{ x: rational(1,3), y: rational(0,1), z: rational(0,1),})).unwrap();
+        assert_eq!(coordinates(&halved)[0],(rational(-5,12),rational(-3,4),rational(-3,4)));
+        assert_eq!(Mesh{ vertices: halved.vertices.clone(), triangles: Vec::new(),}.rounded_vertices().unwrap()[0],Point3D{ x: 0, y: -1, z: -1,});
+        assert_eq!(parse_obj("",&MeshTransform::identity()).unwrap().vertices.len(),0);
+    }
+
+    #[test]
+    fn obj_errors(){
+        let error = |text: &str| parse_obj(text,&MeshTransform::identity()).unwrap_err();
+        assert_eq!(error("v 0 0 0\nv 1 0 0\nv 1 x 0\n"),ParseError{ line: 3, kind: ParseErrorKind::InvalidNumber,});
+        assert_eq!(error("# vertex\n\nv 1 2\n"),ParseError{ line: 3, kind: ParseErrorKind::MissingValue,});
+        assert_eq!(error("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 4\nv 0 0 1\n"),ParseError{ line: 4, kind: ParseErrorKind::InvalidIndex,});
+        assert_eq!(error("v 0 0 0\nv 1 0 0\nf 0 1 2\n"),ParseError{ line: 3, kind: ParseErrorKind::InvalidIndex,});
+        assert_eq!(error("v 0 0 0\nv 1 0 0\nf -3 1 2\n"),ParseError{ line: 3, kind: ParseErrorKind::InvalidIndex,});
+        assert_eq!(error("v 0 0 0\nv 1 0 0\nf 1 2\n"),ParseError{ line: 3, kind: ParseErrorKind::MissingValue,});
+        assert_eq!(error("v 0 0 0\nf 1 a 1\n"),ParseError{ line: 2, kind: ParseErrorKind::InvalidNumber,});
+        let overflow = MeshTransform::new_integer(isize::MAX,Point3D{ x: 0, y: 0, z: 0,});
+        assert_eq!(parse_obj("v 0 0 0\nv 3 0 0\n",&overflow).unwrap_err(),ParseError{ line: 2, kind: ParseErrorKind::Overflow,});
+        assert_eq!(format!("{}",error("v 1 2\n")),"line 1: missing coordinate or face vertex");
+    }
+
+    #[test]
+    fn stl_files(){
+        let ascii = "solid tetrahedron
+  facet normal 0 0 -1
+    outer loop
+      vertex 0 0 0
+      vertex 0 1.5 0
+      vertex 2 0 0
+    endloop
+  endfacet
+  facet normal 0 -1 0
+    outer loop
+      vertex 0 0 0
+      vertex 2 0 0
+      vertex 0 0 -1.25
+    endloop
+  endfacet
+endsolid tetrahedron
+solid lid
+  facet normal 1 1 1
+    outer loop
+      vertex 2 0 0
+      vertex 0 1.5 0
+      vertex 0 0 -1.25
+    endloop
+  endfacet
+  facet normal -1 0 0
+    outer loop
+      vertex 0 0 0
+      vertex 0 0 -1.25
+      vertex 0 1.5 0
+    endloop
+  endfacet
+endsolid lid
+";
+        let transform = MeshTransform::new(rational(4,1),RationalPoint3D{ x: rational(1,2), y: rational(0,1), z: rational(0,1),});
+        let mesh = parse_stl(ascii.as_bytes(),&transform).unwrap();
+        assert_eq!(coordinates(&mesh),vec![
+            (rational(1,2),rational(0,1),rational(0,1)),
+            (rational(1,2),rational(6,1),rational(0,1)),
+            (rational(17,2),rational(0,1),rational(0,1)),
+            (rational(1,2),rational(0,1),rational(-5,1)),
+        ]);
+        assert_eq!(mesh.triangles,vec![[0,1,2],[0,2,3],[2,1,3],[0,3,1]]);
+
+        let corners = [[0.0,0.0,0.0],[0.0,1.5,0.0],[2.0,0.0,0.0],[0.0,0.0,-1.25]];
+        let binary = binary_stl(&[[corners[0],corners[1],corners[2]],[corners[0],corners[2],corners[3]],[corners[2],corners[1],corners[3]],[corners[0],corners[3],corners[1]]]);
+        let read = parse_stl(&binary,&transform).unwrap();
+        assert_eq!(coordinates(&read),coordinates(&mesh));
+        assert_eq!(read.triangles,mesh.triangles);
+        let tenth = parse_stl(&binary_stl(&[[[0.1,0.0,0.0],corners[1],corners[2]]]),&MeshTransform::identity()).unwrap();
+        assert_eq!(tenth.vertices[0].x,Rational::from_f64_exact(0.1f32 as f64).unwrap());
+
+        let vertices = mesh.rounded_vertices().unwrap();
+        assert!(!integer_cells_inside_mesh(&vertices,&mesh.triangles,FillRule::EvenOdd).is_empty());
+    }
+
+    #[test]
+    fn stl_errors(){
+        let error = |text: &str| parse_stl(text.as_bytes(),&MeshTransform::identity()).unwrap_err();
+        let facet = "solid s\nfacet normal 0 0 1\nouter loop\nvertex 0 0 0\nvertex 1 0 0\nvertex 0 1 0\nendloop\nendfacet\n";
+        assert_eq!(parse_stl((facet.to_string() + "endsolid s\n").as_bytes(),&MeshTransform::identity()).unwrap().triangles,vec![[0,1,2]]);
+        assert_eq!(error(facet),ParseError{ line: 8, kind: ParseErrorKind::UnexpectedEnd,});
+        assert_eq!(error(&facet.replace("endloop\n","")),ParseError{ line: 7, kind: ParseErrorKind::UnexpectedToken,});
+        assert_eq!(error(&facet.replace("vertex 1 0 0","vertex 1 0")),ParseError{ line: 5, kind: ParseErrorKind::MissingValue,});
+        assert_eq!(error(&facet.replace("vertex 1 0 0","vertex 1 0 0 0")),ParseError{ line: 5, kind: ParseErrorKind::UnexpectedToken,});
+        assert_eq!(error(&facet.replace("normal 0 0 1","normal 0 zero 1")),ParseError{ line: 2, kind: ParseErrorKind::InvalidNumber,});
+        assert_eq!(error(&facet.replace("outer loop","outer")),ParseError{ line: 3, kind: ParseErrorKind::MissingValue,});
+        assert_eq!(error("facet normal 0 0 1\n"),ParseError{ line: 1, kind: ParseErrorKind::UnexpectedToken,});
+        assert_eq!(error("\n  \nendsolid s\n"),ParseError{ line: 3, kind: ParseErrorKind::UnexpectedToken,});
+        assert_eq!(error(" \n"),ParseError{ line: 0, kind: ParseErrorKind::UnexpectedEnd,});
+        assert_eq!(parse_stl(&[0, 1, 2],&MeshTransform::identity()).unwrap_err(),ParseError{ line: 0, kind: ParseErrorKind::UnexpectedEnd,});
+
+        let corner = [0.0f32,0.0,0.0];
+        let binary = binary_stl(&[[corner,corner,corner],[corner,corner,[f32::NAN,0.0,0.0]]]);
+        assert_eq!(parse_stl(&binary,&MeshTransform::identity()).unwrap_err(),ParseError{ line: 2, kind: ParseErrorKind::InvalidNumber,});
+        let mut binary = binary_stl(&[[corner,corner,corner],[corner,corner,corner]]);
+        binary.truncate(binary.len() - 1);
+        assert_eq!(parse_stl(&binary,&MeshTransform::identity()).unwrap_err(),ParseError{ line: 2, kind: ParseErrorKind::UnexpectedEnd,});
+        binary.extend_from_slice(&[0; 10]);
+        assert_eq!(parse_stl(&binary,&MeshTransform::identity()).unwrap_err(),ParseError{ line: 3, kind: ParseErrorKind::UnexpectedToken,});
+    }
+}
@@ -0,0 +1,76 @@
+//Purpose: Contains the point structs shared by the line and ball algorithms.
+
+use std::ops::Neg;
+
+use crate::rational::{Rational, RationalNumber};
+
+//The coordinates of the rational points may be any RationalNumber, by default they are Rationals.
+#[derive(Debug, Copy, Clone)]
+pub struct RationalPoint3D<N = Rational> {
+    pub x: N,
+    pub y: N,
+    pub z: N,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct RationalPoint2D<N = Rational> {
+    pub x: N,
+    pub y: N,
+}
+
+#[derive(Debug, Copy, Clone,PartialEq,Eq,PartialOrd,Ord)]
+pub struct Point3D {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+#[derive(Debug, Copy, Clone,PartialEq,Eq,PartialOrd,Ord)]
+pub struct Point2D {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl From<Point3D> for RationalPoint3D {
+    fn from(point: Point3D) -> RationalPoint3D {
+        RationalPoint3D{ x: Rational::new_rational_from_integer(point.x), y: Rational::new_rational_from_integer(point.y), z: Rational::new_rational_from_integer(point.z),}
+    }
+}
+
+impl From<Point2D> for RationalPoint2D {
+    fn from(point: Point2D) -> RationalPoint2D {
+        RationalPoint2D{ x: Rational::new_rational_from_integer(point.x), y: Rational::new_rational_from_integer(point.y),}
+    }
+}
+
+impl<N: RationalNumber> Neg for RationalPoint3D<N> {
+    type Output = RationalPoint3D<N>;
+
+    fn neg(self) -> RationalPoint3D<N> {
+        RationalPoint3D{ x: -self.x, y: -self.y, z: -self.z,}
+    }
+}
+
+impl<N: RationalNumber> Neg for RationalPoint2D<N> {
+    type Output = RationalPoint2D<N>;
+
+    fn neg(self) -> RationalPoint2D<N> {
+        RationalPoint2D{ x: -self.x, y: -self.y,}
+    }
+}
+
+impl Neg for Point3D {
+    type Output = Point3D;
+
+    fn neg(self) -> Point3D {
+        Point3D{ x: -self.x, y: -self.y, z: -self.z,}
+    }
+}
+
+impl Neg for Point2D {
+    type Output = Point2D;
+
+    fn neg(self) -> Point2D {
+        Point2D{ x: -self.x, y: -self.y,}
+    }
+}
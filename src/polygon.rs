@@ -0,0 +1,437 @@
+//Purpose: Contains functions for finding the integer points inside polygons and triangles with integer or rational vertices,
+//         simple or self-intersecting, by filling one row of integer points at a time.
+//Notes:
+//    Every edge is intersected with every row exactly using Rationals, so no point is ever decided by rounding.
+//    A point on an edge is decided by a top-left rule: it is inside when the points just past it towards larger x
+//    (or, on a horizontal edge, towards larger y) are inside. Polygons that share an edge therefore never share a point,
+//    and the polygons of a tiling together cover every integer point exactly once.
+
+use crate::error::{check_cell_count, GeometryError};
+use crate::point::{Point2D, RationalPoint2D};
+use crate::rational::Rational;
+
+//MAX_POLYGON_ROWS
+//Purpose:
+//    The most rows of integer points a polygon may span.
+//    Filling takes time proportional to the number of rows spanned, including rows with no points inside,
+//    so the try_ functions return GeometryError::RangeTooLarge for taller polygons instead of sweeping them.
+pub const MAX_POLYGON_ROWS: usize = 1 << 24;
+
+//FillRule
+//Purpose:
+//    Decides which points of a self-intersecting polygon are inside, from how many times its edges wind around them.
+//    EvenOdd: inside when the edges cross a ray from the point an odd number of times.
+//    NonZero: inside when the edges wind around the point a nonzero number of times.
+//Notes:
+//    Both rules agree on simple polygons.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FillRule {
+    EvenOdd,
+    NonZero,
+}
+
+impl FillRule {
+    pub(crate) fn is_inside(&self, winding: isize) -> bool{
+        match self {
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0,
+        }
+    }
+}
+
+//An edge of a polygon that is not horizontal, with the half-open range of rows it crosses.
+struct Edge {
+    top: RationalPoint2D,
+    slope: Rational,
+    first_row: isize,
+    end_row: isize,
+    winding: isize,
+}
+
+impl Edge {
+    //new
+    //Purpose:
+    //    Returns the edge from start to end, or None if it is horizontal or crosses no row.
+    //Pre-conditions:
+    //    None.
+    //Notes:
+    //    The edge crosses the rows y with top.y <= y < bottom.y, so a row through its lower end is left to the edge after it.
+    fn new(start: RationalPoint2D, end: RationalPoint2D) -> Result<Option<Edge>, GeometryError>{
+        if start.y == end.y {
+            return Ok(None);
+        }
+        let (top, bottom, winding) = if start.y < end.y { (start,end,1) } else { (end,start,-1) };
+        let first_row = top.y.checked_ceil().ok_or(GeometryError::RangeTooLarge)?;
+        let end_row = bottom.y.checked_ceil().ok_or(GeometryError::RangeTooLarge)?;
+        if first_row == end_row {
+            return Ok(None);
+        }
+        let run = bottom.x.checked_sub(top.x).ok_or(GeometryError::Overflow)?;
+        let rise = bottom.y.checked_sub(top.y).ok_or(GeometryError::Overflow)?;
+        let slope = run.checked_div(rise).ok_or(GeometryError::Overflow)?;
+        Ok(Some(Edge{ top, slope, first_row, end_row, winding,}))
+    }
+
+    //Returns the exact x coordinate where the edge crosses the given row.
+    fn crossing(&self, row: isize) -> Result<Rational, GeometryError>{
+        Rational::new_rational_from_integer(row).checked_sub(self.top.y)
+            .and_then(|height| height.checked_mul(self.slope))
+            .and_then(|offset| offset.checked_add(self.top.x))
+            .ok_or(GeometryError::Overflow)
+    }
+}
+
+//try_polygon_fill
+//Purpose:
+//    Returns the integer points inside the polygon with the given vertices under the given FillRule, ordered by y and then by x,
+//    or a GeometryError if a computation overflows, the polygon spans more than MAX_POLYGON_ROWS rows or the points do not fit in a Vec.
+//Pre-conditions:
+//    None.
+//Notes:
+//    The rows are swept from the top down, keeping the edges that cross the current row active.
+//    The crossings of a row are sorted, and between two crossings the points x with left <= x < right are inside
+//    when the winding number after the left crossing is inside under the rule.
+fn try_polygon_fill(vertices: &[RationalPoint2D], rule: FillRule) -> Result<Vec<Point2D>, GeometryError>{
+    let mut edges: Vec<Edge> = Vec::new();
+    for (index, &start) in vertices.iter().enumerate() {
+        if let Some(edge) = Edge::new(start,vertices[(index + 1) % vertices.len()])? {
+            edges.push(edge);
+        }
+    }
+    edges.sort_by_key(|edge| edge.first_row);
+    if let (Some(first), Some(end)) = (edges.first().map(|edge| edge.first_row), edges.iter().map(|edge| edge.end_row).max()) {
+        if end as i128 - first as i128 > MAX_POLYGON_ROWS as i128 {
+            return Err(GeometryError::RangeTooLarge);
+        }
+    }
+
+    let mut points: Vec<Point2D> = Vec::new();
+    let mut count: usize = 0;
+    let mut active: Vec<&Edge> = Vec::new();
+    let mut next_edge = 0;
+    let mut crossings: Vec<(Rational, isize)> = Vec::new();
+    let mut row = match edges.first() {
+        Some(edge) => edge.first_row,
+        None => return Ok(points),
+    };
+    loop {
+        active.retain(|edge| edge.end_row > row);
+        if active.is_empty() {
+            if next_edge == edges.len() {
+                return Ok(points);
+            }
+            row = row.max(edges[next_edge].first_row);
+        }
+        while next_edge < edges.len() && edges[next_edge].first_row == row {
+            active.push(&edges[next_edge]);
+            next_edge += 1;
+        }
+
+        crossings.clear();
+        for edge in &active {
+            crossings.push((edge.crossing(row)?,edge.winding));
+        }
+        crossings.sort();
+        let mut winding = 0;
+        for pair in crossings.windows(2) {
+            winding += pair[0].1;
+            if !rule.is_inside(winding) {
+                continue;
+            }
+            let start = pair[0].0.checked_ceil().ok_or(GeometryError::RangeTooLarge)?;
+            let end = pair[1].0.checked_ceil().ok_or(GeometryError::RangeTooLarge)?;
+            if start < end {
+                count = count.checked_add((end as usize).wrapping_sub(start as usize)).ok_or(GeometryError::RangeTooLarge)?;
+                check_cell_count::<Point2D>(Some(count))?;
+                points.extend((start .. end).map(|x| Point2D{ x, y: row,}));
+            }
+        }
+        row += 1; //every active edge ends by isize::MAX, so this only overflows once none are left.
+    }
+}
+
+//polygon_or_panic
+//Purpose:
+//    Unwraps the points inside a polygon, panicking if they could not be found.
+//Pre-conditions:
+//    None.
+fn polygon_or_panic(points: Result<Vec<Point2D>, GeometryError>) -> Vec<Point2D>{
+    match points {
+        Ok(points) => points,
+        Err(_) => panic!("Overflow while finding the points inside a polygon!"),
+    }
+}
+
+//integer_points_inside_polygon
+//Purpose:
+//    Returns all integer points (x,y) inside the polygon with the given vertices under the given FillRule, ordered by y and then by x.
+//    A point on an edge is inside when the points just past it towards larger x, or for a horizontal edge towards larger y, are inside.
+//Pre-conditions:
+//    None.
+//Notes:
+//    The polygon is closed from the last vertex back to the first, and may cross itself.
+//    Fewer than three vertices (or any polygon with no area) have no points.
+//    Takes time proportional to the number of rows the polygon spans plus the number of points,
+//    and panics if it spans more than MAX_POLYGON_ROWS rows.
+pub fn integer_points_inside_polygon(vertices: &[Point2D], rule: FillRule) -> Vec<Point2D>{
+    polygon_or_panic(try_integer_points_inside_polygon(vertices,rule))
+}
+
+//try_integer_points_inside_polygon
+//Purpose:
+//    Returns the same points as integer_points_inside_polygon, or a GeometryError if a computation overflows,
+//    it spans more than MAX_POLYGON_ROWS rows (RangeTooLarge) or the points do not fit in a Vec.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_polygon(vertices: &[Point2D], rule: FillRule) -> Result<Vec<Point2D>, GeometryError>{
+    let vertices: Vec<RationalPoint2D> = vertices.iter().map(|&vertex| RationalPoint2D::from(vertex)).collect();
+    try_polygon_fill(&vertices,rule)
+}
+
+//integer_points_inside_polygon_rational
+//Purpose:
+//    Returns all integer points (x,y) inside the polygon with the given rational vertices under the given FillRule,
+//    ordered by y and then by x, deciding the points on edges as integer_points_inside_polygon does.
+//Pre-conditions:
+//    None.
+//Notes:
+//    The polygon is closed from the last vertex back to the first, and may cross itself.
+//    Takes time proportional to the number of rows the polygon spans plus the number of points,
+//    and panics if it spans more than MAX_POLYGON_ROWS rows.
+pub fn integer_points_inside_polygon_rational(vertices: &[RationalPoint2D], rule: FillRule) -> Vec<Point2D>{
+    polygon_or_panic(try_integer_points_inside_polygon_rational(vertices,rule))
+}
+
+//try_integer_points_inside_polygon_rational
+//Purpose:
+//    Returns the same points as integer_points_inside_polygon_rational, or a GeometryError if a computation overflows,
+//    it spans more than MAX_POLYGON_ROWS rows (RangeTooLarge) or the points do not fit in a Vec.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_polygon_rational(vertices: &[RationalPoint2D], rule: FillRule) -> Result<Vec<Point2D>, GeometryError>{
+    try_polygon_fill(vertices,rule)
+}
+
+//integer_points_inside_triangle
+//Purpose:
+//    Returns all integer points (x,y) inside the triangle with the given vertices, ordered by y and then by x.
+//    A point on an edge is inside when the points just past it towards larger x, or for a horizontal edge towards larger y, are inside,
+//    so triangles that share an edge never share a point, and the triangles of a mesh cover each point of it exactly once.
+//Pre-conditions:
+//    None.
+//Notes:
+//    The vertices may be given in either orientation. A triangle with no area has no points.
+//    The points are the same as integer_points_inside_polygon finds for the three vertices.
+//    Takes time proportional to the number of rows the triangle spans plus the number of points,
+//    and panics if it spans more than MAX_POLYGON_ROWS rows.
+pub fn integer_points_inside_triangle(point1: Point2D, point2: Point2D, point3: Point2D) -> Vec<Point2D>{
+    polygon_or_panic(try_integer_points_inside_triangle(point1,point2,point3))
+}
+
+//try_integer_points_inside_triangle
+//Purpose:
+//    Returns the same points as integer_points_inside_triangle, or a GeometryError if a computation overflows,
+//    it spans more than MAX_POLYGON_ROWS rows (RangeTooLarge) or the points do not fit in a Vec.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_triangle(point1: Point2D, point2: Point2D, point3: Point2D) -> Result<Vec<Point2D>, GeometryError>{
+    try_integer_points_inside_polygon(&[point1,point2,point3],FillRule::NonZero)
+}
+
+//integer_points_inside_triangle_rational
+//Purpose:
+//    Returns all integer points (x,y) inside the triangle with the given rational vertices, ordered by y and then by x,
+//    deciding the points on edges as integer_points_inside_triangle does.
+//Pre-conditions:
+//    None.
+//Notes:
+//    Takes time proportional to the number of rows the triangle spans plus the number of points,
+//    and panics if it spans more than MAX_POLYGON_ROWS rows.
+pub fn integer_points_inside_triangle_rational(point1: RationalPoint2D, point2: RationalPoint2D, point3: RationalPoint2D) -> Vec<Point2D>{
+    polygon_or_panic(try_integer_points_inside_triangle_rational(point1,point2,point3))
+}
+
+//try_integer_points_inside_triangle_rational
+//Purpose:
+//    Returns the same points as integer_points_inside_triangle_rational, or a GeometryError if a computation overflows,
+//    it spans more than MAX_POLYGON_ROWS rows (RangeTooLarge) or the points do not fit in a Vec.
+//Pre-conditions:
+//    None.
+pub fn try_integer_points_inside_triangle_rational(point1: RationalPoint2D, point2: RationalPoint2D, point3: RationalPoint2D) -> Result<Vec<Point2D>, GeometryError>{
+    try_polygon_fill(&[point1,point2,point3],FillRule::NonZero)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort;
+
+    //Returns the winding number of the polygon around (x, y), where the vertices are scaled by scale and the point is not on an edge.
+    fn winding_number(vertices: &[(i64, i64)], scale: i64, x: i64, y: i64) -> isize{
+        let mut winding = 0;
+        for (index, &(x1, y1)) in vertices.iter().enumerate() {
+            let (x2, y2) = vertices[(index + 1) % vertices.len()];
+            let (x1, y1, x2, y2) = (x1*scale, y1*scale, x2*scale, y2*scale);
+            let cross = (x2 - x1)*(y - y1) - (x - x1)*(y2 - y1);
+            if y1 <= y && y < y2 && cross > 0 {
+                winding += 1;
+            }else if y2 <= y && y < y1 && cross < 0 {
+                winding -= 1;
+            }
+        }
+        winding
+    }
+
+    //Returns the integer points inside the polygon with vertices in units of 1/denominator, found by testing every point in range.
+    //Each point is nudged by a little towards larger x and by far less towards larger y, which is what the top-left rule decides.
+    fn polygon_slow(vertices: &[(i64, i64)], denominator: i64, rule: FillRule) -> Vec<Point2D>{
+        let scale = 100_000;
+        let mut points = Vec::new();
+        for y in -12 .. 13 {
+            for x in -12 .. 13 {
+                let winding = winding_number(vertices,scale,x*denominator*scale + 1000,y*denominator*scale + 1);
+                if rule.is_inside(winding) {
+                    points.push(Point2D{ x: x as isize, y: y as isize,});
+                }
+            }
+        }
+        points
+    }
+
+    fn rational_vertices(vertices: &[(i64, i64)], denominator: i64) -> Vec<RationalPoint2D>{
+        vertices.iter().map(|&(x, y)| RationalPoint2D{
+            x: Rational::new_rational_from_integers(x as isize,denominator as isize),
+            y: Rational::new_rational_from_integers(y as isize,denominator as isize),
+        }).collect()
+    }
+
+    #[test]
+    fn integer_polygons(){
+        let polygons: Vec<Vec<(i64, i64)>> = vec![
+            vec![(0,0),(4,0),(4,3),(0,3)],
+            vec![(0,0),(0,3),(4,3),(4,0)],
+            vec![(-3,-2),(5,1),(-1,6)],
+            vec![(0,-5),(2,-1),(6,0),(2,1),(0,5),(-2,1),(-6,0),(-2,-1)],
+            vec![(-4,-4),(4,-4),(4,4),(0,0),(-4,4)],
+            vec![(0,-6),(4,6),(-6,-2),(6,-2),(-4,6)],
+            vec![(-5,0),(5,0),(5,2),(-5,2),(-5,4),(0,-3),(2,5)],
+            vec![(1,1),(1,1),(3,1)],
+            vec![(2,2)],
+            vec![],
+        ];
+        for polygon in &polygons {
+            let vertices: Vec<Point2D> = polygon.iter().map(|&(x, y)| Point2D{ x: x as isize, y: y as isize,}).collect();
+            for rule in [FillRule::EvenOdd,FillRule::NonZero].iter() {
+                let points = integer_points_inside_polygon(&vertices,*rule);
+                assert_eq!(points,polygon_slow(polygon,1,*rule),"{:?} {:?}",polygon,rule);
+                assert_eq!(try_integer_points_inside_polygon(&vertices,*rule),Ok(points));
+            }
+        }
+        //the center of a pentagram is wound around twice.
+        let star: Vec<Point2D> = polygons[5].iter().map(|&(x, y)| Point2D{ x: x as isize, y: y as isize,}).collect();
+        assert!(integer_points_inside_polygon(&star,FillRule::NonZero).contains(&Point2D{ x: 0, y: 1,}));
+        assert!(!integer_points_inside_polygon(&star,FillRule::EvenOdd).contains(&Point2D{ x: 0, y: 1,}));
+        assert_eq!(integer_points_inside_polygon(&[Point2D{ x: 0, y: 0,},Point2D{ x: 2, y: 0,},Point2D{ x: 2, y: 2,},Point2D{ x: 0, y: 2,}],FillRule::NonZero),
+            vec![Point2D{ x: 0, y: 0,},Point2D{ x: 1, y: 0,},Point2D{ x: 0, y: 1,},Point2D{ x: 1, y: 1,}]);
+    }
+
+    #[test]
+    fn rational_polygons(){
+        let polygons: Vec<Vec<(i64, i64)>> = vec![
+            vec![(-7,-5),(11,-2),(4,13)],
+            vec![(0,0),(9,0),(9,9),(0,9)],
+            vec![(-20,1),(17,-8),(25,22),(-3,14),(2,3)],
+            vec![(0,-18),(12,18),(-18,-6),(18,-6),(-12,18)],
+        ];
+        for polygon in &polygons {
+            let vertices = rational_vertices(polygon,3);
+            for rule in [FillRule::EvenOdd,FillRule::NonZero].iter() {
+                assert_eq!(integer_points_inside_polygon_rational(&vertices,*rule),polygon_slow(polygon,3,*rule),"{:?} {:?}",polygon,rule);
+            }
+        }
+    }
+
+    #[test]
+    fn shared_edges(){
+        //a fan of triangles around an inner point tiles the hexagon, so they cover each of its points once.
+        let hexagon = [(7,0),(4,6),(-3,7),(-8,1),(-4,-6),(3,-7)];
+        let center = (1,1);
+        let mut covered: Vec<Point2D> = Vec::new();
+        for (index, &corner) in hexagon.iter().enumerate() {
+            let triangle = [center,corner,hexagon[(index + 1) % hexagon.len()]];
+            let vertices: Vec<Point2D> = triangle.iter().map(|&(x, y)| Point2D{ x, y,}).collect();
+            covered.extend(integer_points_inside_polygon(&vertices,FillRule::EvenOdd));
+        }
+        let vertices: Vec<Point2D> = hexagon.iter().map(|&(x, y)| Point2D{ x, y,}).collect();
+        assert_eq!(sort(covered),sort(integer_points_inside_polygon(&vertices,FillRule::EvenOdd)));
+    }
+
+    #[test]
+    fn large_polygons(){
+        let huge = [Point2D{ x: isize::MIN, y: isize::MIN,},Point2D{ x: isize::MAX, y: isize::MIN,},Point2D{ x: isize::MAX, y: isize::MAX,}];
+        assert_eq!(try_integer_points_inside_polygon(&huge,FillRule::EvenOdd),Err(GeometryError::RangeTooLarge));
+        let thin = [Point2D{ x: isize::MIN, y: 0,},Point2D{ x: isize::MAX, y: 0,},Point2D{ x: isize::MAX, y: 1,}];
+        assert_eq!(try_integer_points_inside_polygon(&thin,FillRule::NonZero),Err(GeometryError::RangeTooLarge));
+        //a sliver between two columns has no points, but is rejected before sweeping its rows when it is too tall.
+        let sliver = |height: i64| rational_vertices(&[(1,-height),(1,height),(2,height),(2,-height)],3);
+        assert_eq!(try_integer_points_inside_polygon_rational(&sliver(3 << 20),FillRule::NonZero),Ok(vec![]));
+        assert_eq!(try_integer_points_inside_polygon_rational(&sliver(3 << 40),FillRule::NonZero),Err(GeometryError::RangeTooLarge));
+        let (top, bottom) = (Rational::new_rational_from_integer(-((MAX_POLYGON_ROWS/2) as isize)),Rational::new_rational_from_integer((MAX_POLYGON_ROWS/2) as isize + 1));
+        let x = |numerator: isize| Rational::new_rational_from_integers(numerator,3);
+        let tall = [RationalPoint2D{ x: x(1), y: top,},RationalPoint2D{ x: x(2), y: top,},RationalPoint2D{ x: x(2), y: bottom,}];
+        assert_eq!(try_integer_points_inside_triangle_rational(tall[0],tall[1],tall[2]),Err(GeometryError::RangeTooLarge));
+    }
+
+    #[test]
+    fn triangles(){
+        let triangles = [
+            [(0,0),(6,0),(0,6)],
+            [(0,6),(6,0),(0,0)],
+            [(-7,-2),(8,3),(1,9)],
+            [(-5,-5),(5,-5),(5,-5)],
+            [(-4,-4),(0,0),(4,4)],
+            [(3,-9),(-9,4),(10,10)],
+        ];
+        for triangle in triangles.iter() {
+            let [a, b, c] = *triangle;
+            let points = integer_points_inside_triangle(Point2D{ x: a.0, y: a.1,},Point2D{ x: b.0, y: b.1,},Point2D{ x: c.0, y: c.1,});
+            let slow_vertices: Vec<(i64, i64)> = triangle.iter().map(|&(x, y)| (x as i64, y as i64)).collect();
+            assert_eq!(points,polygon_slow(&slow_vertices,1,FillRule::NonZero),"{:?}",triangle);
+            let rational: Vec<(i64, i64)> = slow_vertices.iter().map(|&(x, y)| (x + 1, y - 1)).collect();
+            let vertices = rational_vertices(&rational,2);
+            assert_eq!(try_integer_points_inside_triangle_rational(vertices[0],vertices[1],vertices[2]),Ok(polygon_slow(&rational,2,FillRule::NonZero)));
+        }
+    }
+
+    #[test]
+    fn triangle_meshes(){
+        //a grid of jittered rational vertices split into triangles, with the diagonals of neighbouring quads alternating.
+        let vertex = |i: isize, j: isize| RationalPoint2D{
+            x: Rational::new_rational_from_integers(i*13 + (i*j*7) % 5 - 30,3),
+            y: Rational::new_rational_from_integers(j*11 + (i + 2*j) % 4 - 25,3),
+        };
+        let mut covered: Vec<Point2D> = Vec::new();
+        for i in 0 .. 5 {
+            for j in 0 .. 5 {
+                let (a, b, c, d) = (vertex(i,j),vertex(i + 1,j),vertex(i + 1,j + 1),vertex(i,j + 1));
+                if (i + j) % 2 == 0 {
+                    covered.extend(integer_points_inside_triangle_rational(a,b,c));
+                    covered.extend(integer_points_inside_triangle_rational(a,c,d));
+                }else{
+                    covered.extend(integer_points_inside_triangle_rational(a,b,d));
+                    covered.extend(integer_points_inside_triangle_rational(b,c,d));
+                }
+            }
+        }
+        let mut outline: Vec<RationalPoint2D> = Vec::new();
+        outline.extend((0 .. 5).map(|i| vertex(i,0)));
+        outline.extend((0 .. 5).map(|j| vertex(5,j)));
+        outline.extend((0 .. 5).map(|i| vertex(5 - i,5)));
+        outline.extend((0 .. 5).map(|j| vertex(0,5 - j)));
+        let covered = sort(covered);
+        let mut once = covered.clone();
+        once.dedup();
+        assert_eq!(covered,once);
+        assert_eq!(covered,sort(integer_points_inside_polygon_rational(&outline,FillRule::EvenOdd)));
+    }
+}
@@ -9,10 +9,14 @@ use std::ops::Sub;
 use std::ops::Mul;
 use std::ops::Div;
 use std::ops::Neg;
-use std::cmp::Ordering;
 use std::cmp;
+use std::convert::TryFrom;
+use std::cmp::Ordering;
+use std::error::Error;
 use std::fmt;
 
+use crate::error::GeometryError;
+
 #[derive(Debug)]
 pub struct Rational {
     sign: bool, //is true if a negative number, false if zero or positive
@@ -24,8 +28,20 @@ pub struct Rational {
 //If the numerator is 0, we will have the denominator be 1
 impl Rational {
     pub fn new_signed_rational(numerator: usize, denominator: usize, sign: bool) -> Rational{
+        match Rational::try_new_signed(numerator,denominator,sign) {
+            Ok(rational) => rational,
+            Err(_) => panic!("Attempted creation of a rational with zero denominator!"),
+        }
+    }
+
+    //try_new_signed
+    //Purpose:
+    //    Returns numerator/denominator, negated if sign is true, or GeometryError::ZeroDenominator if the denominator is zero.
+    //Pre-conditions:
+    //    None.
+    pub fn try_new_signed(numerator: usize, denominator: usize, sign: bool) -> Result<Rational, GeometryError>{
         if denominator == 0 {
-            panic!("Attempted creation of a rational with zero denominator!");
+            return Err(GeometryError::ZeroDenominator);
         }
         let my_gcd;
         let mut my_sign = sign;
@@ -36,41 +52,46 @@ impl Rational {
             my_denominator = 1;
         }else{
             my_gcd = gcd(numerator,my_denominator);
-            my_denominator = my_denominator/my_gcd;
-            my_numerator = my_numerator/my_gcd;
+            my_denominator /= my_gcd;
+            my_numerator /= my_gcd;
         }
-        Rational{
+        Ok(Rational{
             sign: my_sign,
             numerator: my_numerator,
             denominator: my_denominator,
-        }
+        })
     }
 
     //if no sign is not given assume it is a non-negative ratinal
     pub fn new_rational(numerator: usize, denominator: usize) -> Rational{
-        if denominator == 0 {
-            panic!("Attempted creation of a rational with zero denominator!");
-        }
-        let my_gcd;
-        let mut my_denominator = denominator;
-        let mut my_numerator = numerator;
-        if my_numerator == 0 {
-            my_denominator = 1;
-        }else{
-            my_gcd = gcd(numerator,my_denominator);
-            my_denominator = my_denominator/my_gcd;
-            my_numerator = my_numerator/my_gcd;
-        }
-        Rational{
-            sign: false,
-            numerator: my_numerator,
-            denominator: my_denominator,
-        }
+        Rational::new_signed_rational(numerator,denominator,false)
+    }
+
+    //try_new_unsigned
+    //Purpose:
+    //    Returns the non-negative rational numerator/denominator, or GeometryError::ZeroDenominator if the denominator is zero.
+    //    The fallible counterpart of new_rational and new_rational_from_unsigned_integers.
+    //Pre-conditions:
+    //    None.
+    pub fn try_new_unsigned(numerator: usize, denominator: usize) -> Result<Rational, GeometryError>{
+        Rational::try_new_signed(numerator,denominator,false)
     }
 
     pub fn new_rational_from_integers(numerator: isize, denominator: isize) -> Rational{
+        match Rational::try_new(numerator,denominator) {
+            Ok(rational) => rational,
+            Err(_) => panic!("Attempted creation of a rational with zero denominator!"),
+        }
+    }
+
+    //try_new
+    //Purpose:
+    //    Returns numerator/denominator, or GeometryError::ZeroDenominator if the denominator is zero.
+    //Pre-conditions:
+    //    None.
+    pub fn try_new(numerator: isize, denominator: isize) -> Result<Rational, GeometryError>{
         if denominator == 0 {
-            panic!("Attempted creation of a rational with zero denominator!");
+            return Err(GeometryError::ZeroDenominator);
         }
         let my_gcd;
         let my_sign;
@@ -83,72 +104,31 @@ impl Rational {
             my_numerator = 0;
             my_denominator = 1;
         }else{
-            if numerator < 0 {
-                neg_numerator = true;
-                my_numerator = (-numerator) as usize;
-            }else{
-                neg_numerator = false;
-                my_numerator = numerator as usize;
-            }
-            if denominator < 0{
-                neg_denominator = true;
-                my_denominator = (-denominator) as usize;
-            }else{
-                neg_denominator = false;
-                my_denominator = denominator as usize;
-            }
+            neg_numerator = numerator < 0;
+            my_numerator = numerator.unsigned_abs();
+            neg_denominator = denominator < 0;
+            my_denominator = denominator.unsigned_abs();
 
-            if (neg_numerator & !neg_denominator) | (!neg_numerator & neg_denominator) {
-                my_sign = true;
-            }else{
-                my_sign = false;
-            }
+            my_sign = neg_numerator ^ neg_denominator;
             my_gcd = gcd(my_numerator,my_denominator);
-            my_denominator = my_denominator/my_gcd;
-            my_numerator = my_numerator/my_gcd;
+            my_denominator /= my_gcd;
+            my_numerator /= my_gcd;
         }
-        Rational{
+        Ok(Rational{
             sign: my_sign,
             numerator: my_numerator,
             denominator: my_denominator,
-        }
+        })
     }
 
     pub fn new_rational_from_unsigned_integers(numerator: usize, denominator: usize) -> Rational{
-        if denominator == 0 {
-            panic!("Attempted creation of a rational with zero denominator!");
-        }
-        let my_gcd;
-        let my_denominator:usize;
-        let my_numerator:usize;
-        if numerator == 0 {
-            my_numerator = 0;
-            my_denominator = 1;
-        }else{
-            my_gcd = gcd(numerator,denominator);
-            my_denominator = denominator/my_gcd;
-            my_numerator = numerator/my_gcd;
-        }
-        Rational{
-            sign: false,
-            numerator: my_numerator,
-            denominator: my_denominator,
-        }
+        Rational::new_signed_rational(numerator,denominator,false)
     }
 
     pub fn new_rational_from_integer(integer: isize) -> Rational{
-        let my_sign;
-        let my_numerator;
-        if integer < 0 {
-            my_sign = true;
-            my_numerator = (-integer) as usize;
-        }else{
-            my_sign = false;
-            my_numerator =  integer as usize;
-        }
         Rational{
-            sign: my_sign,
-            numerator: my_numerator,
+            sign: integer < 0,
+            numerator: integer.unsigned_abs(),
             denominator: 1,
         }
     }
@@ -179,20 +159,11 @@ impl Rational {
     //Purpose:
     //    Returns the largest integer less than or equal to the current rational.
     //Pre-conditions:
-    //    None.
+    //    The result fits in an isize.
     pub fn floor(&self) -> isize{
-	    if self.denominator == 1 {
-            if self.sign {
-                -(self.numerator as isize)
-            }else{
-                self.numerator as isize
-            }
-        }else{
-            if self.sign {
-                -((self.numerator as isize) / (self.denominator as isize)) - 1
-            }else{
-                (self.numerator / self.denominator) as isize
-            }
+        match self.checked_floor() {
+            Some(integer) => integer,
+            None => panic!("Overflow in floor (Rationals)!"),
         }
 	}
 
@@ -200,31 +171,58 @@ impl Rational {
     //Purpose:
     //    Returns the samllest integer greater than or equal to the current rational.
     //Pre-conditions:
-    //    None.
+    //    The result fits in an isize.
     pub fn ceil(&self) -> isize{
-        if self.denominator == 1 {
-            if self.sign {
-                -(self.numerator as isize)
-            }else{
-                self.numerator as isize
-            }
-        }else{
-            if self.sign {
-                -((self.numerator as isize) / (self.denominator as isize))
-            }else{
-                (self.numerator / self.denominator + 1) as isize
-            }
+        match self.checked_ceil() {
+            Some(integer) => integer,
+            None => panic!("Overflow in ceil (Rationals)!"),
         }
 	}
 
+    //checked_floor
+    //Purpose:
+    //    Returns the largest integer less than or equal to the current rational, or None if it does not fit in an isize.
+    //Pre-conditions:
+    //    None.
+    pub fn checked_floor(&self) -> Option<isize>{
+        let whole = (self.numerator / self.denominator) as i128;
+        let floor = if !self.sign {
+            whole
+        }else if self.denominator == 1 {
+            -whole
+        }else{
+            -whole - 1
+        };
+        isize::try_from(floor).ok()
+    }
+
+    //checked_ceil
+    //Purpose:
+    //    Returns the smallest integer greater than or equal to the current rational, or None if it does not fit in an isize.
+    //Pre-conditions:
+    //    None.
+    pub fn checked_ceil(&self) -> Option<isize>{
+        let whole = (self.numerator / self.denominator) as i128;
+        let ceil = if self.sign {
+            -whole
+        }else if self.denominator == 1 {
+            whole
+        }else{
+            whole + 1
+        };
+        isize::try_from(ceil).ok()
+    }
+
     //rational_floor
     //Purpose:
     //    Returns the largest rational number less than or equal to the current rational which has the given denominator.
     //Pre-conditions:
     //    denom must not be zero. (If zero is used, return the zero rational)
     pub fn rational_floor(&self,denom: usize) -> Rational{
-        let a = Rational::new_rational_from_unsigned_integer(denom);
-	    Rational::new_rational_from_integers((a*(*self)).floor() as isize,denom as isize)
+        match self.checked_rational_floor(denom) {
+            Some(rational) => rational,
+            None => panic!("Overflow in floor (Rationals)!"),
+        }
 	}
 
     //rational_ceil
@@ -233,21 +231,354 @@ impl Rational {
     //Pre-conditions:
     //    denom must not be zero. (If zero is used, return the zero rational)
     pub fn rational_ceil(&self,denom: usize) -> Rational{
-        let a = Rational::new_rational_from_unsigned_integer(denom);
-	    Rational::new_rational_from_integers((a*(*self)).ceil() as isize,denom as isize)
+        match self.checked_rational_ceil(denom) {
+            Some(rational) => rational,
+            None => panic!("Overflow in ceil (Rationals)!"),
+        }
 	}
 
+    //checked_rational_floor
+    //Purpose:
+    //    Returns the same rational as rational_floor, or None if it can not be represented.
+    //Pre-conditions:
+    //    denom must not be zero. (If zero is used, return the zero rational)
+    pub fn checked_rational_floor(&self,denom: usize) -> Option<Rational>{
+        let a = Rational::new_rational_from_unsigned_integer(denom);
+        let floor = a.checked_mul(*self)?.checked_floor()?;
+        Some(Rational::new_rational_from_integers(floor,isize::try_from(denom).ok()?))
+    }
+
+    //checked_rational_ceil
+    //Purpose:
+    //    Returns the same rational as rational_ceil, or None if it can not be represented.
+    //Pre-conditions:
+    //    denom must not be zero. (If zero is used, return the zero rational)
+    pub fn checked_rational_ceil(&self,denom: usize) -> Option<Rational>{
+        let a = Rational::new_rational_from_unsigned_integer(denom);
+        let ceil = a.checked_mul(*self)?.checked_ceil()?;
+        Some(Rational::new_rational_from_integers(ceil,isize::try_from(denom).ok()?))
+    }
+
     //abs
     //Purpose:
     //    Returns the absolute value of the given rational.
     //Pre-conditions:
     //    None
     pub fn abs(&self) -> Rational{
-        let a = Rational::new_rational_from_unsigned_integers(self.numerator,self.denominator);
-        a
+        Rational::new_rational_from_unsigned_integers(self.numerator,self.denominator)
 	}
+
+    //checked_add
+    //Purpose:
+    //    Returns the sum of the current rational and rhs, or None if it can not be represented.
+    //Pre-conditions:
+    //    None.
+    //Notes:
+    //    The cross-multiplication is done in a u128, so None is only returned when the reduced sum does not fit.
+    pub fn checked_add(&self, rhs: Rational) -> Option<Rational>{
+        if self.numerator == 0 {
+            return Some(rhs);
+        }else if rhs.numerator == 0 {
+            return Some(*self);
+        }
+        let common = gcd(self.denominator,rhs.denominator);
+        let left = self.numerator as u128 * (rhs.denominator/common) as u128;
+        let right = rhs.numerator as u128 * (self.denominator/common) as u128;
+        let denominator = (self.denominator/common) as u128 * rhs.denominator as u128;
+        if self.sign == rhs.sign {
+            //if the sum does not fit in a u128 then even after dividing by common it does not fit in a usize.
+            from_wide(left.checked_add(right)?,denominator,self.sign)
+        }else if left >= right {
+            from_wide(left - right,denominator,self.sign)
+        }else{
+            from_wide(right - left,denominator,rhs.sign)
+        }
+    }
+
+    //checked_sub
+    //Purpose:
+    //    Returns the current rational minus rhs, or None if it can not be represented.
+    //Pre-conditions:
+    //    None.
+    pub fn checked_sub(&self, rhs: Rational) -> Option<Rational>{
+        self.checked_add(-rhs)
+    }
+
+    //checked_mul
+    //Purpose:
+    //    Returns the product of the current rational and rhs, or None if it can not be represented.
+    //Pre-conditions:
+    //    None.
+    pub fn checked_mul(&self, rhs: Rational) -> Option<Rational>{
+        if (self.numerator == 0) || (rhs.numerator == 0) {
+            return Some(Rational::new_rational_from_integer(0));
+        }
+        //cancelling first leaves the product in lowest form, so it only fails to fit when the product can not be represented.
+        let first_gcd = gcd(self.numerator,rhs.denominator);
+        let second_gcd = gcd(rhs.numerator,self.denominator);
+        Some(Rational{
+            sign: self.sign ^ rhs.sign,
+            numerator: (self.numerator/first_gcd).checked_mul(rhs.numerator/second_gcd)?,
+            denominator: (self.denominator/second_gcd).checked_mul(rhs.denominator/first_gcd)?,
+        })
+    }
+
+    //checked_div
+    //Purpose:
+    //    Returns the current rational divided by rhs, or None if rhs is zero or the quotient can not be represented.
+    //Pre-conditions:
+    //    None.
+    pub fn checked_div(&self, rhs: Rational) -> Option<Rational>{
+        if rhs.numerator == 0 {
+            return None;
+        }
+        self.checked_mul(Rational{
+            sign: rhs.sign,
+            numerator: rhs.denominator,
+            denominator: rhs.numerator,
+        })
+    }
+
+    //try_div
+    //Purpose:
+    //    Returns the current rational divided by rhs, or a GeometryError if rhs is zero or the quotient overflows.
+    //Pre-conditions:
+    //    None.
+    pub fn try_div(&self, rhs: Rational) -> Result<Rational, GeometryError>{
+        if rhs.numerator == 0 {
+            return Err(GeometryError::DivisionByZero);
+        }
+        self.checked_div(rhs).ok_or(GeometryError::Overflow)
+    }
+
+    //checked_cmp
+    //Purpose:
+    //    Compares the current rational with rhs.
+    //Pre-conditions:
+    //    None.
+    //Notes:
+    //    Comparisons cross-multiply in a u128 and so never overflow, this always returns Some.
+    //    It is provided so that code written with the other checked operations can use it throughout.
+    pub fn checked_cmp(&self, rhs: &Rational) -> Option<Ordering>{
+        Some(self.cmp(rhs))
+    }
+
+    //from_f64_exact
+    //Purpose:
+    //    Returns the rational equal to the given f64.
+    //    Every finite f64 is a dyadic rational (an integer divided by a power of two), so no rounding takes place.
+    //Pre-conditions:
+    //    None.
+    //Notes:
+    //    Returns None if x is infinite or NaN, or if its numerator or denominator does not fit in a usize
+    //    (very large values and values with more than usize::BITS - 1 binary places, such as subnormals).
+    pub fn from_f64_exact(x: f64) -> Option<Rational>{
+        let (sign, mantissa, exponent) = decompose_f64(x)?;
+        if mantissa == 0 {
+            return Some(Rational::new_rational_from_integer(0));
+        }
+        if exponent >= 0 {
+            if 64 - mantissa.leading_zeros() + exponent as u32 > usize::BITS {
+                return None;
+            }
+            let numerator = usize::try_from(mantissa).ok()? << exponent;
+            Some(Rational::new_signed_rational(numerator,1,sign))
+        }else{
+            if -exponent >= usize::BITS as i32 {
+                return None;
+            }
+            let numerator = usize::try_from(mantissa).ok()?;
+            Some(Rational::new_signed_rational(numerator,1 << -exponent,sign))
+        }
+    }
+
+    //approximate
+    //Purpose:
+    //    Returns the rational closest to the given f64 whose denominator is at most max_denominator.
+    //    If there are two, the one with the smaller denominator is returned, and if those are equal as well the one nearer zero.
+    //Pre-conditions:
+    //    None.
+    //Notes:
+    //    Returns None if max_denominator is zero, or if x is infinite, NaN or does not fit in a usize.
+    //    If x is a rational with a denominator of at most max_denominator then x itself is returned.
+    //    When x is large max_denominator is lowered so that the numerator of the result fits in a usize.
+    //    The result is found with the continued fraction expansion of x, which is computed exactly.
+    pub fn approximate(x: f64, max_denominator: usize) -> Option<Rational>{
+        if max_denominator == 0 {
+            return None;
+        }
+        let (sign, mantissa, exponent) = decompose_f64(x)?;
+        let whole: u128;
+        let remainder: u128;
+        let scale: u128; // the fractional part of |x| is remainder/scale
+        if exponent >= 0 {
+            if 64 - mantissa.leading_zeros() + exponent as u32 > usize::BITS {
+                return None;
+            }
+            whole = (mantissa as u128) << exponent;
+            remainder = 0;
+            scale = 1;
+        }else if -exponent < 127 {
+            scale = 1 << -exponent;
+            whole = mantissa as u128 / scale;
+            remainder = mantissa as u128 % scale;
+        }else{ // |x| < 2^-74, which is closer to 0 than to 1/usize::MAX.
+            whole = 0;
+            remainder = 0;
+            scale = 1;
+        }
+        if whole > usize::MAX as u128 {
+            return None;
+        }
+        let limit = cmp::min(max_denominator as u128, usize::MAX as u128 / (whole + 1));
+        let (numerator, denominator) = best_fraction(remainder,scale,limit);
+        Some(Rational::new_signed_rational((whole*denominator + numerator) as usize,denominator as usize,sign))
+    }
+}
+
+//from_wide
+//Purpose:
+//    Returns the rational numerator/denominator with the given sign, or None if it does not fit once reduced.
+//Pre-conditions:
+//    denominator is positive.
+fn from_wide(numerator: u128, denominator: u128, sign: bool) -> Option<Rational>{
+    if numerator == 0 {
+        return Some(Rational::new_rational_from_integer(0));
+    }
+    let common = gcd_wide(numerator,denominator);
+    Some(Rational{
+        sign,
+        numerator: usize::try_from(numerator/common).ok()?,
+        denominator: usize::try_from(denominator/common).ok()?,
+    })
+}
+
+//gcd_wide
+//Purpose:
+//    Given two positive numbers returns their gcd.
+//Pre-conditions:
+//    Both numbers x and y are positive.
+fn gcd_wide(mut x: u128, mut y: u128) -> u128{
+    while y != 0 {
+        let remainder = x % y;
+        x = y;
+        y = remainder;
+    }
+    x
+}
+
+//decompose_f64
+//Purpose:
+//    Splits a finite f64 into its sign, mantissa and exponent, so that x = (-1)^sign * mantissa * 2^exponent.
+//Pre-conditions:
+//    None.
+//Notes:
+//    Returns None if x is infinite or NaN.
+pub(crate) fn decompose_f64(x: f64) -> Option<(bool, u64, i32)>{
+    let bits = x.to_bits();
+    let sign = bits >> 63 == 1;
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & ((1 << 52) - 1);
+    if biased_exponent == 0x7ff {
+        return None;
+    }
+    let (mut mantissa, mut exponent) = if biased_exponent == 0 { // subnormal
+        (fraction, -1074)
+    }else{
+        (fraction | (1 << 52), biased_exponent - 1075)
+    };
+    if mantissa != 0 {
+        let zeros = mantissa.trailing_zeros();
+        mantissa >>= zeros;
+        exponent += zeros as i32;
+    }
+    Some((sign, mantissa, exponent))
+}
+
+//best_fraction
+//Purpose:
+//    Returns the fraction p/q closest to numerator/denominator with q at most limit, as (p, q).
+//    Ties go to the fraction with the smaller denominator, and then to the smaller fraction.
+//Pre-conditions:
+//    numerator < denominator, and limit is positive and fits in a usize.
+fn best_fraction(numerator: u128, denominator: u128, limit: u128) -> (u128, u128){
+    if numerator == 0 {
+        return (0, 1);
+    }
+    //p0/q0 and p1/q1 are the last two convergents, numerator/denominator = (p1*t + p0)/(q1*t + q0) where t = n/d.
+    let (mut p0, mut q0, mut p1, mut q1) = (1, 0, 0, 1);
+    let (mut n, mut d) = (denominator, numerator);
+    loop {
+        let a = n / d;
+        if a > (limit - q0) / q1 { // the next convergent has too large a denominator.
+            break;
+        }
+        let (p2, q2) = (p0 + a*p1, q0 + a*q1);
+        p0 = p1;
+        q0 = q1;
+        p1 = p2;
+        q1 = q2;
+        let r = n - a*d;
+        n = d;
+        d = r;
+        if d == 0 { // the expansion has ended, so the convergent is exact.
+            return (p1, q1);
+        }
+    }
+    //the best approximation is either the convergent p1/q1 or the semiconvergent with the largest allowed k,
+    //p1/q1 is at least as close exactly when (2*k*q1 + q0)/q1 <= t.
+    let k = (limit - q0) / q1;
+    let semiconvergent = (p0 + k*p1, q0 + k*q1);
+    match compare_fractions(2*k*q1 + q0,q1,n,d) {
+        Ordering::Less => (p1, q1),
+        Ordering::Greater => semiconvergent,
+        Ordering::Equal => {
+            if semiconvergent.1 < q1 || (semiconvergent.1 == q1 && semiconvergent.0 < p1) {
+                semiconvergent
+            }else{
+                (p1, q1)
+            }
+        },
+    }
+}
+
+//compare_fractions
+//Purpose:
+//    Compares a/b with c/d without multiplying, so it can not overflow.
+//Pre-conditions:
+//    b and d are positive.
+fn compare_fractions(mut a: u128, mut b: u128, mut c: u128, mut d: u128) -> Ordering{
+    loop {
+        let (whole_ab, whole_cd) = (a / b, c / d);
+        if whole_ab != whole_cd {
+            return whole_ab.cmp(&whole_cd);
+        }
+        let (remainder_ab, remainder_cd) = (a % b, c % d);
+        if remainder_ab == 0 || remainder_cd == 0 {
+            return remainder_ab.cmp(&remainder_cd);
+        }
+        //remainder_ab/b compared to remainder_cd/d is d/remainder_cd compared to b/remainder_ab.
+        a = d;
+        c = b;
+        b = remainder_cd;
+        d = remainder_ab;
+    }
 }
 
+//OverflowError
+//Purpose:
+//    The error returned when a computation on rationals has a result (or an intermediate value) that can not be represented.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct OverflowError;
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "overflow in a computation on rationals")
+    }
+}
+
+impl Error for OverflowError {}
+
 impl fmt::Display for Rational {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
             match (self.sign, self.denominator){
@@ -290,32 +621,20 @@ impl Ord for Rational {
         }else if !self.sign & rhs.sign{
             Ordering::Greater
         }else if self.sign & rhs.sign{
-            match (self.numerator*rhs.denominator).cmp(&(self.denominator*rhs.numerator)){
+            match (self.numerator as u128*rhs.denominator as u128).cmp(&(self.denominator as u128*rhs.numerator as u128)){
                 Ordering::Equal => Ordering::Equal,
                 Ordering::Less => Ordering::Greater,
                 Ordering::Greater => Ordering::Less,
             }
         }else{
-            (self.numerator*rhs.denominator).cmp(&(self.denominator*rhs.numerator))
+            (self.numerator as u128*rhs.denominator as u128).cmp(&(self.denominator as u128*rhs.numerator as u128)) //widened so that it can not overflow.
         }
     }
 }
 
 impl PartialOrd for Rational {
     fn partial_cmp(&self, rhs: &Rational) -> Option<Ordering> {
-        if self.sign & !rhs.sign {
-            Some(Ordering::Less)
-        }else if !self.sign & rhs.sign{
-            Some(Ordering::Greater)
-        }else if self.sign & rhs.sign{
-            match (self.numerator*rhs.denominator).cmp(&(self.denominator*rhs.numerator)){
-                Ordering::Equal => Some(Ordering::Equal),
-                Ordering::Less => Some(Ordering::Greater),
-                Ordering::Greater => Some(Ordering::Less),
-            }
-        }else{
-            Some((self.numerator*rhs.denominator).cmp(&(self.denominator*rhs.numerator)))
-        }
+        Some(self.cmp(rhs))
     }
 }
 
@@ -323,25 +642,9 @@ impl Add<Rational> for Rational {
     type Output = Rational;
 
     fn add(self, rhs: Rational) -> Rational {
-        if self.numerator == 0 {
-            return rhs;
-        }else if  rhs.numerator == 0 {
-            return self;
-        } else {
-            match (self.sign, rhs.sign){
-                (true,true) => {
-                    return Rational::new_signed_rational(self.denominator*rhs.numerator + self.numerator*rhs.denominator,self.denominator*rhs.denominator,true);
-                }
-                (false,false) => {
-                    return Rational::new_rational(self.denominator*rhs.numerator + self.numerator*rhs.denominator,self.denominator*rhs.denominator);
-                }
-                (false,true) => {
-                    return Rational::new_rational_from_integers(((self.numerator*rhs.denominator) as isize) - ((self.denominator*rhs.numerator) as isize),(self.denominator*rhs.denominator) as isize);
-                }
-                (true,false) => {
-                    return Rational::new_rational_from_integers(((self.denominator*rhs.numerator) as isize) - ((self.numerator*rhs.denominator) as isize),(self.denominator*rhs.denominator) as isize);
-                }
-            }
+        match self.checked_add(rhs) {
+            Some(sum) => sum,
+            None => panic!("Overflow in addition (Rationals)!"),
         }
     }
 }
@@ -374,7 +677,7 @@ impl Sub<usize> for Rational {
     type Output = Rational;
 
     fn sub(self, rhs: usize) -> Rational {
-        self + (-(rhs as isize))
+        self - Rational::new_rational_from_unsigned_integer(rhs)
     }
 }
 
@@ -382,7 +685,7 @@ impl Sub<isize> for Rational {
     type Output = Rational;
 
     fn sub(self, rhs: isize) -> Rational {
-        self + (-rhs)
+        self - Rational::new_rational_from_integer(rhs)
     }
 }
 
@@ -390,28 +693,9 @@ impl Mul<Rational> for Rational {
     type Output = Rational;
 
     fn mul(self, rhs: Rational) -> Rational {
-        let my_first_gcd;
-        let my_second_gcd;
-        let mut my_sign = false;
-        let my_denominator;
-        let my_numerator;
-        if (self.numerator == 0) || (rhs.numerator == 0) {
-            my_sign = false;
-            my_denominator = 1;
-            my_numerator = 0;
-        }else {
-            my_first_gcd = gcd(self.numerator,rhs.denominator);
-            my_second_gcd = gcd(rhs.numerator,self.denominator);
-            if (self.sign & !rhs.sign) | (!self.sign & rhs.sign) {
-                my_sign = true;
-            }
-            my_denominator = (self.denominator/my_second_gcd)*(rhs.denominator/my_first_gcd);
-            my_numerator = (self.numerator/my_first_gcd)*(rhs.numerator/my_second_gcd);
-        }
-        Rational{
-            sign: my_sign,
-            numerator: my_numerator,
-            denominator: my_denominator,
+        match self.checked_mul(rhs) {
+            Some(product) => product,
+            None => panic!("Overflow in multiplication (Rationals)!"),
         }
     }
 }
@@ -420,25 +704,7 @@ impl Mul<usize> for Rational {
     type Output = Rational;
 
     fn mul(self, rhs: usize) -> Rational {
-        let my_gcd;
-        let my_sign;
-        let my_denominator;
-        let my_numerator;
-        if (rhs == 0) || (self.numerator == 0) {
-            my_sign = false;
-            my_denominator = 1;
-            my_numerator = 0;
-        }else {
-            my_gcd = gcd(self.denominator,rhs);
-            my_sign = self.sign;
-            my_denominator = self.denominator/my_gcd;
-            my_numerator = self.numerator*(rhs/my_gcd);
-        }
-        Rational{
-            sign: my_sign,
-            numerator: my_numerator,
-            denominator: my_denominator,
-        }
+        self * Rational::new_rational_from_unsigned_integer(rhs)
     }
 }
 
@@ -446,32 +712,7 @@ impl Mul<isize> for Rational {
     type Output = Rational;
 
     fn mul(self, rhs: isize) -> Rational {
-        let my_gcd;
-        let my_sign;
-        let my_denominator;
-        let my_numerator;
-        let my_rhs;
-        if (rhs == 0) || (self.numerator == 0) {
-            my_sign = false;
-            my_denominator = 1;
-            my_numerator = 0;
-        }else {
-            if rhs < 0 {
-                my_sign = !self.sign;
-                my_rhs = (-rhs) as usize;
-            }else{
-                my_rhs = rhs as usize;
-                my_sign = self.sign;
-            }
-            my_gcd = gcd(self.denominator,my_rhs);
-            my_denominator = self.denominator/my_gcd;
-            my_numerator = self.numerator*(my_rhs/my_gcd);
-        }
-        Rational{
-            sign: my_sign,
-            numerator: my_numerator,
-            denominator: my_denominator,
-        }
+        self * Rational::new_rational_from_integer(rhs)
     }
 }
 
@@ -479,30 +720,12 @@ impl Div<Rational> for Rational {
     type Output = Rational;
 
     fn div(self, rhs: Rational) -> Rational {
-        let my_first_gcd;
-        let my_second_gcd;
-        let mut my_sign = false;
-        let my_denominator;
-        let my_numerator;
         if rhs.numerator  == 0 {
             panic!("Attempted division by zero (Rationals)!");
-        } else if self.numerator == 0 {
-            my_sign = false;
-            my_denominator = 1;
-            my_numerator = 0;
-        } else {
-            my_first_gcd = gcd(self.numerator,rhs.numerator);
-            my_second_gcd = gcd(rhs.denominator,self.denominator);
-            if (self.sign & !rhs.sign) | (!self.sign & rhs.sign) {
-                my_sign = true;
-            }
-            my_denominator = (self.denominator/my_second_gcd)*(rhs.numerator/my_first_gcd);
-            my_numerator = (self.numerator/my_first_gcd)*(rhs.denominator/my_second_gcd);
         }
-        Rational{
-            sign: my_sign,
-            numerator: my_numerator,
-            denominator: my_denominator,
+        match self.checked_div(rhs) {
+            Some(quotient) => quotient,
+            None => panic!("Overflow in division (Rationals)!"),
         }
     }
 }
@@ -523,25 +746,114 @@ impl Neg for Rational {
     }
 }
 
+//RationalNumber
+//Purpose:
+//    The operations the line algorithms need from an exact rational type.
+//    It is implemented by Rational and, with the big-rational feature, by BigRational,
+//    so the same algorithms can run without any limit on the size of the numbers involved.
+//Notes:
+//    Operations that can overflow return None instead, for BigRational only checked_floor and checked_ceil
+//    (whose results must fit in an isize) and division by zero can fail.
+pub trait RationalNumber: Clone + Ord + Neg<Output = Self> + fmt::Debug {
+    //Returns the given integer as a rational.
+    fn from_integer(integer: isize) -> Self;
+
+    //Returns numerator/denominator, the denominator must not be zero.
+    fn from_integers(numerator: isize, denominator: isize) -> Self;
+
+    fn checked_add(&self, rhs: &Self) -> Option<Self>;
+
+    fn checked_sub(&self, rhs: &Self) -> Option<Self>;
+
+    fn checked_mul(&self, rhs: &Self) -> Option<Self>;
+
+    //Returns None if rhs is zero.
+    fn checked_div(&self, rhs: &Self) -> Option<Self>;
+
+    fn checked_floor(&self) -> Option<isize>;
+
+    fn checked_ceil(&self) -> Option<isize>;
+
+    fn is_integer(&self) -> bool;
+}
+
+impl RationalNumber for Rational {
+    fn from_integer(integer: isize) -> Rational {
+        Rational::new_rational_from_integer(integer)
+    }
+
+    fn from_integers(numerator: isize, denominator: isize) -> Rational {
+        Rational::new_rational_from_integers(numerator,denominator)
+    }
+
+    fn checked_add(&self, rhs: &Rational) -> Option<Rational> {
+        Rational::checked_add(self,*rhs)
+    }
+
+    fn checked_sub(&self, rhs: &Rational) -> Option<Rational> {
+        Rational::checked_sub(self,*rhs)
+    }
+
+    fn checked_mul(&self, rhs: &Rational) -> Option<Rational> {
+        Rational::checked_mul(self,*rhs)
+    }
+
+    fn checked_div(&self, rhs: &Rational) -> Option<Rational> {
+        Rational::checked_div(self,*rhs)
+    }
+
+    fn checked_floor(&self) -> Option<isize> {
+        Rational::checked_floor(self)
+    }
+
+    fn checked_ceil(&self) -> Option<isize> {
+        Rational::checked_ceil(self)
+    }
+
+    fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+}
+
 //gcd
 //Purpose:
-//    Given two positive numbers returns their gcd.
+//    Given two numbers returns their gcd, where the gcd of a number and zero is the number itself.
 //Pre-conditions:
-//    Both numbers x and y are positive.
+//    None.
+//Notes:
+//    gcd(0,0) is 0.
 pub fn gcd(x: usize, y: usize) -> usize{
     let mut first_num = x;
 	let mut second_num = y;
 	let mut remainder;
-	remainder = first_num % second_num; //since x and y are not zero, don't  need to worry about % 0.
-	while remainder != 0{
+	while second_num != 0{
+		remainder = first_num % second_num;
 	    first_num = second_num;
         second_num = remainder;
-		remainder = first_num % second_num;
 	}
-	second_num
+	first_num
+}
+
+//isqrt
+//Purpose:
+//    Returns the largest integer whose square is at most n.
+//Pre-conditions:
+//    None.
+//Notes:
+//    Starts from the floating point square root and corrects it, so the result is exact for every usize.
+pub fn isqrt(n: usize) -> usize{
+    let mut root = (n as f64).sqrt() as usize;
+    while root.checked_mul(root).is_none_or(|square| square > n) {
+        root -= 1;
+    }
+    while (root + 1).checked_mul(root + 1).is_some_and(|square| square <= n) {
+        root += 1;
+    }
+    root
 }
 
 #[cfg(test)]
+#[allow(clippy::erasing_op, clippy::nonminimal_bool)] //these tests deliberately multiply by zero and negate comparisons.
 mod tests {
     use super::*;
 
@@ -652,24 +964,24 @@ mod tests {
         let neg_two_thirds = Rational::new_rational_from_integers(-2,3);
         let neg_one = Rational::new_rational_from_integers(-1,1);
         let neg_two = Rational::new_rational_from_integers(-2,1);
-        assert_eq!(zero + (0 as isize),zero);
-        assert_eq!(zero + (1 as isize),one);
-        assert_eq!(zero + (-1 as isize),neg_one);
-        assert_eq!(one + (0 as isize),one);
-        assert_eq!(one + (1 as isize),two);
-        assert_eq!(one + (-1 as isize),zero);
-        assert_eq!(neg_one + (0 as isize),neg_one);
-        assert_eq!(neg_one + (1 as isize),zero);
-        assert_eq!(neg_one + (-1 as isize),neg_two);
-        assert_eq!(neg_one_half + (1 as isize),one_half);
-        assert_eq!(neg_one_half + (2 as isize),three_halves);
-        assert_eq!(neg_one_half + (0 as isize),neg_one_half);
-        assert_eq!(one_half + (0 as isize),one_half);
-        assert_eq!(one_half + (1 as isize),three_halves);
-        assert_eq!(one_half + (-1 as isize),neg_one_half);
-        assert_eq!(three_halves + (-2 as isize),neg_one_half);
-        assert_eq!(one_third + (-1 as isize),neg_two_thirds);
-        assert_eq!(neg_two_thirds + (1 as isize),one_third);
+        assert_eq!(zero + (0_isize),zero);
+        assert_eq!(zero + (1_isize),one);
+        assert_eq!(zero + (-1_isize),neg_one);
+        assert_eq!(one + (0_isize),one);
+        assert_eq!(one + (1_isize),two);
+        assert_eq!(one + (-1_isize),zero);
+        assert_eq!(neg_one + (0_isize),neg_one);
+        assert_eq!(neg_one + (1_isize),zero);
+        assert_eq!(neg_one + (-1_isize),neg_two);
+        assert_eq!(neg_one_half + (1_isize),one_half);
+        assert_eq!(neg_one_half + (2_isize),three_halves);
+        assert_eq!(neg_one_half + (0_isize),neg_one_half);
+        assert_eq!(one_half + (0_isize),one_half);
+        assert_eq!(one_half + (1_isize),three_halves);
+        assert_eq!(one_half + (-1_isize),neg_one_half);
+        assert_eq!(three_halves + (-2_isize),neg_one_half);
+        assert_eq!(one_third + (-1_isize),neg_two_thirds);
+        assert_eq!(neg_two_thirds + (1_isize),one_third);
     }
 
     #[test]
@@ -683,18 +995,18 @@ mod tests {
         let neg_one_half = Rational::new_rational_from_integers(-1,2);
         let neg_two_thirds = Rational::new_rational_from_integers(-2,3);
         let neg_one = Rational::new_rational_from_integers(-1,1);
-        assert_eq!(zero + (0 as usize),zero);
-        assert_eq!(zero + (1 as usize),one);
-        assert_eq!(one + (0 as usize),one);
-        assert_eq!(one + (1 as usize),two);
-        assert_eq!(neg_one + (0 as usize),neg_one);
-        assert_eq!(neg_one + (1 as usize),zero);
-        assert_eq!(neg_one_half + (1 as usize),one_half);
-        assert_eq!(neg_one_half + (2 as usize),three_halves);
-        assert_eq!(neg_one_half + (0 as usize),neg_one_half);
-        assert_eq!(one_half + (0 as usize),one_half);
-        assert_eq!(one_half + (1 as usize),three_halves);
-        assert_eq!(neg_two_thirds + (1 as usize),one_third);
+        assert_eq!(zero + (0_usize),zero);
+        assert_eq!(zero + (1_usize),one);
+        assert_eq!(one + (0_usize),one);
+        assert_eq!(one + (1_usize),two);
+        assert_eq!(neg_one + (0_usize),neg_one);
+        assert_eq!(neg_one + (1_usize),zero);
+        assert_eq!(neg_one_half + (1_usize),one_half);
+        assert_eq!(neg_one_half + (2_usize),three_halves);
+        assert_eq!(neg_one_half + (0_usize),neg_one_half);
+        assert_eq!(one_half + (0_usize),one_half);
+        assert_eq!(one_half + (1_usize),three_halves);
+        assert_eq!(neg_two_thirds + (1_usize),one_third);
     }
 
     #[test]
@@ -741,24 +1053,24 @@ mod tests {
         let neg_two_thirds = Rational::new_rational_from_integers(-2,3);
         let neg_one = Rational::new_rational_from_integers(-1,1);
         let neg_two = Rational::new_rational_from_integers(-2,1);
-        assert_eq!(zero - (0 as isize),zero);
-        assert_eq!(zero - (-1 as isize),one);
-        assert_eq!(zero - (1 as isize),neg_one);
-        assert_eq!(one - (0 as isize),one);
-        assert_eq!(one - (-1 as isize),two);
-        assert_eq!(one - (1 as isize),zero);
-        assert_eq!(neg_one - (0 as isize),neg_one);
-        assert_eq!(neg_one - (-1 as isize),zero);
-        assert_eq!(neg_one - (1 as isize),neg_two);
-        assert_eq!(neg_one_half - (-1 as isize),one_half);
-        assert_eq!(neg_one_half - (-2 as isize),three_halves);
-        assert_eq!(neg_one_half - (0 as isize),neg_one_half);
-        assert_eq!(one_half - (0 as isize),one_half);
-        assert_eq!(one_half - (-1 as isize),three_halves);
-        assert_eq!(one_half - (1 as isize),neg_one_half);
-        assert_eq!(three_halves - (2 as isize),neg_one_half);
-        assert_eq!(one_third - (1 as isize),neg_two_thirds);
-        assert_eq!(neg_two_thirds - (-1 as isize),one_third);
+        assert_eq!(zero - (0_isize),zero);
+        assert_eq!(zero - (-1_isize),one);
+        assert_eq!(zero - (1_isize),neg_one);
+        assert_eq!(one - (0_isize),one);
+        assert_eq!(one - (-1_isize),two);
+        assert_eq!(one - (1_isize),zero);
+        assert_eq!(neg_one - (0_isize),neg_one);
+        assert_eq!(neg_one - (-1_isize),zero);
+        assert_eq!(neg_one - (1_isize),neg_two);
+        assert_eq!(neg_one_half - (-1_isize),one_half);
+        assert_eq!(neg_one_half - (-2_isize),three_halves);
+        assert_eq!(neg_one_half - (0_isize),neg_one_half);
+        assert_eq!(one_half - (0_isize),one_half);
+        assert_eq!(one_half - (-1_isize),three_halves);
+        assert_eq!(one_half - (1_isize),neg_one_half);
+        assert_eq!(three_halves - (2_isize),neg_one_half);
+        assert_eq!(one_third - (1_isize),neg_two_thirds);
+        assert_eq!(neg_two_thirds - (-1_isize),one_third);
     }
 
     #[test]
@@ -772,17 +1084,17 @@ mod tests {
         let neg_one = Rational::new_rational_from_integers(-1,1);
         let neg_two = Rational::new_rational_from_integers(-2,1);
         let neg_three_halves = Rational::new_rational_from_integers(-3,2);
-        assert_eq!(zero - (0 as usize),zero);
-        assert_eq!(zero - (1 as usize),neg_one);
-        assert_eq!(one - (0 as usize),one);
-        assert_eq!(one - (1 as usize),zero);
-        assert_eq!(neg_one - (0 as usize),neg_one);
-        assert_eq!(neg_one - (1 as usize),neg_two);
-        assert_eq!(neg_one_half - (1 as usize),neg_three_halves);
-        assert_eq!(neg_one_half - (0 as usize),neg_one_half);
-        assert_eq!(one_half - (0 as usize),one_half);
-        assert_eq!(one_half - (1 as usize),neg_one_half);
-        assert_eq!(one_third - (1 as usize),neg_two_thirds);
+        assert_eq!(zero - (0_usize),zero);
+        assert_eq!(zero - (1_usize),neg_one);
+        assert_eq!(one - (0_usize),one);
+        assert_eq!(one - (1_usize),zero);
+        assert_eq!(neg_one - (0_usize),neg_one);
+        assert_eq!(neg_one - (1_usize),neg_two);
+        assert_eq!(neg_one_half - (1_usize),neg_three_halves);
+        assert_eq!(neg_one_half - (0_usize),neg_one_half);
+        assert_eq!(one_half - (0_usize),one_half);
+        assert_eq!(one_half - (1_usize),neg_one_half);
+        assert_eq!(one_third - (1_usize),neg_two_thirds);
     }
 
     #[test]
@@ -836,23 +1148,23 @@ mod tests {
         let neg_one = Rational::new_rational_from_integers(-1,1);
         let neg_one_half = Rational::new_rational_from_integers(-1,2);
         let zero = Rational::new_rational_from_integers(0,1);
-        assert_eq!(zero * (0 as isize) , zero);
-        assert_eq!(zero * (1 as isize) , zero);
-        assert_eq!(zero * (-1 as isize) , zero);
-        assert_eq!(one * (1 as isize) , one);
-        assert_eq!(one * (0 as isize) , zero);
-        assert_eq!(one * (-1 as isize) , neg_one);
-        assert_eq!(neg_one * (1 as isize) , neg_one);
-        assert_eq!(neg_one * (0 as isize) , zero);
-        assert_eq!(neg_one * (-1 as isize) , one);
-        assert_eq!(one_half * (1 as isize) , one_half);
-        assert_eq!(one_half * (-1 as isize) , neg_one_half);
-        assert_eq!(one_half * (2 as isize) , one);
-        assert_eq!(one_half * (-2 as isize) , neg_one);
-        assert_eq!(one_half * (3 as isize) , three_halves);
-        assert_eq!(one_third * (2 as isize) , two_thirds);
-        assert_eq!(one_third * (3 as isize) , one);
-        assert_eq!(one_third * (-3 as isize) , neg_one);
+        assert_eq!(zero * (0_isize) , zero);
+        assert_eq!(zero * (1_isize) , zero);
+        assert_eq!(zero * (-1_isize) , zero);
+        assert_eq!(one * (1_isize) , one);
+        assert_eq!(one * (0_isize) , zero);
+        assert_eq!(one * (-1_isize) , neg_one);
+        assert_eq!(neg_one * (1_isize) , neg_one);
+        assert_eq!(neg_one * (0_isize) , zero);
+        assert_eq!(neg_one * (-1_isize) , one);
+        assert_eq!(one_half * (1_isize) , one_half);
+        assert_eq!(one_half * (-1_isize) , neg_one_half);
+        assert_eq!(one_half * (2_isize) , one);
+        assert_eq!(one_half * (-2_isize) , neg_one);
+        assert_eq!(one_half * (3_isize) , three_halves);
+        assert_eq!(one_third * (2_isize) , two_thirds);
+        assert_eq!(one_third * (3_isize) , one);
+        assert_eq!(one_third * (-3_isize) , neg_one);
     }
 
     #[test]
@@ -864,17 +1176,17 @@ mod tests {
         let two_thirds = Rational::new_rational(2,3);
         let neg_one = Rational::new_rational_from_integers(-1,1);
         let zero = Rational::new_rational_from_integers(0,1);
-        assert_eq!(zero * (0 as isize) , zero);
-        assert_eq!(zero * (1 as isize) , zero);
-        assert_eq!(one * (1 as isize) , one);
-        assert_eq!(one * (0 as isize) , zero);
-        assert_eq!(neg_one * (1 as isize) , neg_one);
-        assert_eq!(neg_one * (0 as isize) , zero);
-        assert_eq!(one_half * (1 as isize) , one_half);
-        assert_eq!(one_half * (2 as isize) , one);
-        assert_eq!(one_half * (3 as isize) , three_halves);
-        assert_eq!(one_third * (2 as isize) , two_thirds);
-        assert_eq!(one_third * (3 as isize) , one);
+        assert_eq!(zero * (0_isize) , zero);
+        assert_eq!(zero * (1_isize) , zero);
+        assert_eq!(one * (1_isize) , one);
+        assert_eq!(one * (0_isize) , zero);
+        assert_eq!(neg_one * (1_isize) , neg_one);
+        assert_eq!(neg_one * (0_isize) , zero);
+        assert_eq!(one_half * (1_isize) , one_half);
+        assert_eq!(one_half * (2_isize) , one);
+        assert_eq!(one_half * (3_isize) , three_halves);
+        assert_eq!(one_third * (2_isize) , two_thirds);
+        assert_eq!(one_third * (3_isize) , one);
     }
 
     #[test]
@@ -921,4 +1233,102 @@ mod tests {
         assert_eq!(-(-one),one);
         assert_eq!(neg_one,-(-neg_one));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn rational_from_f64(){
+        assert_eq!(Rational::from_f64_exact(0.5),Some(Rational::new_rational(1,2)));
+        assert_eq!(Rational::from_f64_exact(-0.75),Some(Rational::new_rational_from_integers(-3,4)));
+        assert_eq!(Rational::from_f64_exact(-0.0),Some(Rational::new_rational_from_integer(0)));
+        assert_eq!(Rational::from_f64_exact(3072.0),Some(Rational::new_rational_from_integer(3072)));
+        assert_eq!(Rational::from_f64_exact(0.1),Some(Rational::new_rational(3602879701896397,36028797018963968)));
+        assert_eq!(Rational::from_f64_exact(1e30),None);
+        assert_eq!(Rational::from_f64_exact(5e-324),None);
+        assert_eq!(Rational::from_f64_exact(f64::NAN),None);
+        assert_eq!(Rational::from_f64_exact(f64::NEG_INFINITY),None);
+    }
+
+    #[test]
+    fn rational_approximate(){
+        assert_eq!(Rational::approximate(std::f64::consts::PI,7),Some(Rational::new_rational(22,7)));
+        assert_eq!(Rational::approximate(std::f64::consts::PI,1000),Some(Rational::new_rational(355,113)));
+        assert_eq!(Rational::approximate(-std::f64::consts::PI,112),Some(Rational::new_rational_from_integers(-333,106)));
+        assert_eq!(Rational::approximate(0.1,100),Some(Rational::new_rational(1,10)));
+        assert_eq!(Rational::approximate(0.1,usize::MAX),Rational::from_f64_exact(0.1));
+        assert_eq!(Rational::approximate(-2.375,8),Some(Rational::new_rational_from_integers(-19,8)));
+        assert_eq!(Rational::approximate(1e-30,usize::MAX),Some(Rational::new_rational_from_integer(0)));
+        assert_eq!(Rational::approximate(2.5,1),Some(Rational::new_rational_from_integer(2)));
+        assert_eq!(Rational::approximate(-2.5,1),Some(Rational::new_rational_from_integer(-2)));
+        assert_eq!(Rational::approximate(0.75,2),Some(Rational::new_rational_from_integer(1)));
+        assert_eq!(Rational::approximate(1e18,3),Some(Rational::new_rational_from_integer(1_000_000_000_000_000_000)));
+        assert_eq!(Rational::approximate(1e30,3),None);
+        assert_eq!(Rational::approximate(f64::INFINITY,3),None);
+        assert_eq!(Rational::approximate(0.5,0),None);
+        assert_eq!(Rational::approximate(0.0,0),None);
+    }
+
+    #[test]
+    fn checked_operations(){
+        let big = Rational::new_rational_from_unsigned_integer(usize::MAX);
+        let half_big = Rational::new_rational(usize::MAX,2);
+        assert_eq!(half_big.checked_add(half_big),Some(big));
+        assert_eq!(big.checked_add(Rational::new_rational(1,1)),None);
+        assert_eq!(big.checked_sub(Rational::new_rational_from_unsigned_integer(usize::MAX - 1)),Some(Rational::new_rational(1,1)));
+        assert_eq!((-big).checked_add(big),Some(Rational::new_rational(0,1)));
+        assert_eq!(Rational::new_rational(1,usize::MAX).checked_add(Rational::new_rational(1,usize::MAX - 1)),None);
+        assert_eq!(Rational::new_rational(1 << 40,3).checked_mul(Rational::new_rational(1 << 40,5)),None);
+        assert_eq!(Rational::new_rational(1 << 40,3).checked_mul(Rational::new_rational(3,1 << 40)),Some(Rational::new_rational(1,1)));
+        assert_eq!(Rational::new_rational(1,3).checked_div(Rational::new_rational(0,1)),None);
+        assert_eq!(Rational::new_rational(1,1 << 40).checked_div(Rational::new_rational(1 << 40,1)),None);
+        assert_eq!(Rational::new_rational(usize::MAX,usize::MAX - 1).checked_cmp(&Rational::new_rational(usize::MAX - 1,usize::MAX - 2)),Some(Ordering::Less));
+        assert!(-Rational::new_rational(usize::MAX,usize::MAX - 1) > -Rational::new_rational(usize::MAX - 1,usize::MAX - 2));
+        assert_eq!(big.checked_floor(),None);
+        assert_eq!(Rational::new_rational_from_integer(isize::MIN).checked_floor(),Some(isize::MIN));
+        assert_eq!(Rational::new_rational_from_integers(isize::MIN,3).checked_ceil(),Some(isize::MIN/3));
+        assert_eq!(big.checked_rational_floor(2),None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Overflow in addition (Rationals)!")]
+    fn overflowing_addition() {
+        let _ = Rational::new_rational(1,usize::MAX) + Rational::new_rational(1,usize::MAX - 1);
+    }
+
+    #[test]
+    fn fallible_rationals(){
+        assert_eq!(Rational::try_new(-4,6),Ok(Rational::new_rational_from_integers(-2,3)));
+        assert_eq!(Rational::try_new(isize::MIN,-1),Ok(Rational::new_rational(isize::MIN.unsigned_abs(),1)));
+        assert_eq!(Rational::try_new(1,0),Err(GeometryError::ZeroDenominator));
+        assert_eq!(Rational::try_new(0,0),Err(GeometryError::ZeroDenominator));
+        assert_eq!(Rational::try_new_signed(4,6,true),Ok(Rational::new_rational_from_integers(-2,3)));
+        assert_eq!(Rational::try_new_signed(0,6,true),Ok(Rational::new_rational_from_integer(0)));
+        assert_eq!(Rational::try_new_signed(usize::MAX,usize::MAX,false),Ok(Rational::new_rational_from_integer(1)));
+        assert_eq!(Rational::try_new_signed(1,0,true),Err(GeometryError::ZeroDenominator));
+        assert_eq!(Rational::try_new_unsigned(6,4),Ok(Rational::new_rational(3,2)));
+        assert_eq!(Rational::try_new_unsigned(6,4),Ok(Rational::new_rational_from_unsigned_integers(3,2)));
+        assert_eq!(Rational::try_new_unsigned(0,0),Err(GeometryError::ZeroDenominator));
+        assert_eq!(Rational::new_rational(1,2).try_div(Rational::new_rational(1,4)),Ok(Rational::new_rational(2,1)));
+        assert_eq!(Rational::new_rational(1,2).try_div(Rational::new_rational(0,1)),Err(GeometryError::DivisionByZero));
+        assert_eq!(Rational::new_rational(usize::MAX,1).try_div(Rational::new_rational(1,2)),Err(GeometryError::Overflow));
+        assert_eq!(gcd(12,18),6);
+        assert_eq!(gcd(0,7),7);
+        assert_eq!(gcd(7,0),7);
+        assert_eq!(gcd(0,0),0);
+    }
+
+    #[test]
+    fn integer_square_root(){
+        for n in 0 .. 10000 {
+            let root = isqrt(n);
+            assert!(root*root <= n && n < (root + 1)*(root + 1));
+        }
+        for root in [4095usize, 4096, 4097, 16_777_215, 16_777_217, 94_906_265, 3_037_000_499, (1 << 32) - 1].iter() {
+            let square = root*root;
+            assert_eq!(isqrt(square),*root);
+            assert_eq!(isqrt(square - 1),root - 1);
+            assert_eq!(isqrt(square + 1),*root);
+            assert_eq!(isqrt(square + 2*root),*root);
+        }
+        assert_eq!(isqrt(usize::MAX),(1 << 32) - 1);
+        assert_eq!(isqrt(((1 << 32) - 1)*((1 << 32) - 1) - 1),(1 << 32) - 2);
+    }
+}
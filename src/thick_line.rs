@@ -0,0 +1,663 @@
+//Purpose: Contains functions for finding the integer cells within a distance of a line segment in 2D and 3D (thick line segments),
+//         using the L1, L2 or L infinity metric.
+//Notes:
+//    A thick line segment is convex, so its integer cells in any row along the x axis are an interval.
+//    The rows are found exactly with rationals, and only the rows of the thick segment projected onto the remaining axes are visited.
+
+use std::cmp;
+
+use crate::ball::{l_2_range, Metric};
+use crate::error::{check_cell_count, GeometryError};
+use crate::point::{Point2D, Point3D, RationalPoint2D, RationalPoint3D};
+use crate::rational::Rational;
+
+//linear_range
+//Purpose:
+//    Returns the smallest and largest integers x with low <= slope*x + offset <= high, or None if a computation overflows.
+//    The smallest is greater than the largest when there are none, and the range is all of isize when slope is zero and offset is in range.
+//Pre-conditions:
+//    None.
+fn linear_range(slope: Rational, offset: Rational, low: Rational, high: Rational) -> Option<(isize, isize)>{
+    let zero = Rational::new_rational_from_integer(0);
+    if slope == zero {
+        return Some(if low <= offset && offset <= high { (isize::MIN, isize::MAX) } else { (1, 0) });
+    }
+    let first = low.checked_sub(offset)?.checked_div(slope)?;
+    let second = high.checked_sub(offset)?.checked_div(slope)?;
+    let (first, second) = if slope.is_neg() { (second, first) } else { (first, second) };
+    Some((first.checked_ceil()?, second.checked_floor()?))
+}
+
+//l_2_row
+//Purpose:
+//    Returns the smallest and largest x such that (x, rest...) is within distance sqrt(radius_squared) of the segment from point1 to point2,
+//    or None if a computation overflows. The smallest is greater than the largest when there are none.
+//Pre-conditions:
+//    rest has one coordinate less than the points.
+//Notes:
+//    The thick segment is the union of the balls around both endpoints and the points p = point1 + w that project onto the segment,
+//    0 <= w.d <= |d|^2 with d = point2 - point1, and are close to the line, |w|^2|d|^2 - (w.d)^2 <= radius_squared*|d|^2.
+//    Write w = (X, u) and d = (d_x, e). By Lagrange's identity the second condition is
+//    (X - d_x(u.e)/|e|^2)^2 <= (|d|^2/|e|^2)(radius_squared - |u x e|^2/|e|^2), where |u x e|^2 is the sum of (u_i*e_j - u_j*e_i)^2 over i < j,
+//    and when e = 0 it holds for every X exactly when |u|^2 <= radius_squared. This keeps the numbers small near the segment.
+//    Since the union is convex, its integers are those between the smallest and largest integers of the three parts.
+fn l_2_row(point1: &[Rational], point2: &[Rational], radius_squared: Rational, rest: &[isize]) -> Option<(isize, isize)>{
+    let zero = Rational::new_rational_from_integer(0);
+    let mut pieces: Vec<(isize, isize)> = Vec::new();
+    for point in [point1,point2].iter() {
+        let mut remaining = radius_squared;
+        for coordinate in 1 .. point.len() {
+            let offset = Rational::new_rational_from_integer(rest[coordinate - 1]).checked_sub(point[coordinate])?;
+            remaining = remaining.checked_sub(offset.checked_mul(offset)?)?;
+        }
+        pieces.push(l_2_range(point[0],remaining)?);
+    }
+    let step = point2[0].checked_sub(point1[0])?;
+    let mut offsets: Vec<Rational> = Vec::with_capacity(rest.len());
+    let mut steps: Vec<Rational> = Vec::with_capacity(rest.len());
+    for coordinate in 1 .. point1.len() {
+        offsets.push(Rational::new_rational_from_integer(rest[coordinate - 1]).checked_sub(point1[coordinate])?);
+        steps.push(point2[coordinate].checked_sub(point1[coordinate])?);
+    }
+    let mut along = zero;
+    let mut offset_squared = zero;
+    let mut rest_squared = zero;
+    let mut cross_squared = zero;
+    for first in 0 .. steps.len() {
+        along = along.checked_add(offsets[first].checked_mul(steps[first])?)?;
+        offset_squared = offset_squared.checked_add(offsets[first].checked_mul(offsets[first])?)?;
+        rest_squared = rest_squared.checked_add(steps[first].checked_mul(steps[first])?)?;
+        for second in first + 1 .. steps.len() {
+            let cross = offsets[first].checked_mul(steps[second])?.checked_sub(offsets[second].checked_mul(steps[first])?)?;
+            cross_squared = cross_squared.checked_add(cross.checked_mul(cross)?)?;
+        }
+    }
+    let direction_squared = rest_squared.checked_add(step.checked_mul(step)?)?;
+    if direction_squared != zero {
+        //w.d = step*x - step*point1.x + along.
+        let (low, high) = linear_range(step,along.checked_sub(step.checked_mul(point1[0])?)?,zero,direction_squared)?;
+        let (near_low, near_high) = if rest_squared == zero {
+            if offset_squared <= radius_squared { (isize::MIN, isize::MAX) } else { (1, 0) }
+        }else{
+            let center = point1[0].checked_add(step.checked_mul(along)?.checked_div(rest_squared)?)?;
+            let width = radius_squared.checked_sub(cross_squared.checked_div(rest_squared)?)?;
+            l_2_range(center,direction_squared.checked_div(rest_squared)?.checked_mul(width)?)?
+        };
+        pieces.push((cmp::max(low,near_low),cmp::min(high,near_high)));
+    }
+    let pieces: Vec<(isize, isize)> = pieces.into_iter().filter(|(low, high)| low <= high).collect();
+    Some((pieces.iter().map(|piece| piece.0).min().unwrap_or(1),pieces.iter().map(|piece| piece.1).max().unwrap_or(0)))
+}
+
+//l_infinity_row
+//Purpose:
+//    Returns the smallest and largest x such that (x, rest...) is within radius of the segment from point1 to point2 in the L infinity metric,
+//    or None if a computation overflows. The smallest is greater than the largest when there are none.
+//Pre-conditions:
+//    rest has one coordinate less than the points.
+//Notes:
+//    Each of the rest of the coordinates keeps the times t in [0,1] with |rest - p(t)| <= radius to an interval,
+//    where p(t) = point1 + t*(point2 - point1), and x can then be within radius of p(t).x for any t left.
+fn l_infinity_row(point1: &[Rational], point2: &[Rational], radius: Rational, rest: &[isize]) -> Option<(isize, isize)>{
+    let zero = Rational::new_rational_from_integer(0);
+    let (mut start, mut end) = (zero, Rational::new_rational_from_integer(1));
+    for coordinate in 1 .. point1.len() {
+        let offset = Rational::new_rational_from_integer(rest[coordinate - 1]).checked_sub(point1[coordinate])?;
+        let step = point2[coordinate].checked_sub(point1[coordinate])?;
+        if step == zero {
+            if offset.abs() > radius {
+                return Some((1, 0));
+            }
+        }else{
+            let first = offset.checked_sub(radius)?.checked_div(step)?;
+            let second = offset.checked_add(radius)?.checked_div(step)?;
+            let (first, second) = if step.is_neg() { (second, first) } else { (first, second) };
+            start = cmp::max(start,first);
+            end = cmp::min(end,second);
+        }
+    }
+    if start > end {
+        return Some((1, 0));
+    }
+    let step = point2[0].checked_sub(point1[0])?;
+    let at_start = point1[0].checked_add(start.checked_mul(step)?)?;
+    let at_end = point1[0].checked_add(end.checked_mul(step)?)?;
+    Some((cmp::min(at_start,at_end).checked_sub(radius)?.checked_ceil()?, cmp::max(at_start,at_end).checked_add(radius)?.checked_floor()?))
+}
+
+//l_1_row
+//Purpose:
+//    Returns the smallest and largest x such that (x, rest...) is within radius of the segment from point1 to point2 in the L1 metric,
+//    or None if a computation overflows. The smallest is greater than the largest when there are none.
+//Pre-conditions:
+//    rest has one coordinate less than the points.
+//Notes:
+//    With p(t) = point1 + t*(point2 - point1) and f(t) the L1 distance from rest to the rest of p(t), x can be within radius - f(t) of p(t).x
+//    for any t in [0,1] with f(t) <= radius. Since f is convex and piecewise linear those t are an interval,
+//    and the ends of the row p(t).x -+ (radius - f(t)) are extreme at the ends of that interval or at the breaks of f inside it.
+fn l_1_row(point1: &[Rational], point2: &[Rational], radius: Rational, rest: &[isize]) -> Option<(isize, isize)>{
+    let zero = Rational::new_rational_from_integer(0);
+    let one = Rational::new_rational_from_integer(1);
+    let mut offsets: Vec<Rational> = Vec::with_capacity(rest.len());
+    let mut steps: Vec<Rational> = Vec::with_capacity(rest.len());
+    let mut times: Vec<Rational> = vec![zero,one];
+    for coordinate in 1 .. point1.len() {
+        let offset = Rational::new_rational_from_integer(rest[coordinate - 1]).checked_sub(point1[coordinate])?;
+        let step = point2[coordinate].checked_sub(point1[coordinate])?;
+        if step != zero {
+            let time = offset.checked_div(step)?;
+            if zero < time && time < one {
+                times.push(time);
+            }
+        }
+        offsets.push(offset);
+        steps.push(step);
+    }
+    times.sort();
+    times.dedup();
+    let distance = |time: Rational| -> Option<Rational>{
+        let mut total = zero;
+        for (offset, step) in offsets.iter().zip(steps.iter()) {
+            total = total.checked_add(offset.checked_sub(time.checked_mul(*step)?)?.abs())?;
+        }
+        Some(total)
+    };
+    let distances = times.iter().map(|time| distance(*time)).collect::<Option<Vec<Rational>>>()?;
+    let (first, last) = match (distances.iter().position(|value| *value <= radius), distances.iter().rposition(|value| *value <= radius)) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Some((1, 0)),
+    };
+    //the time between times[outside] and times[inside] where f reaches radius, f being linear between them.
+    let crossing = |outside: usize, inside: usize| -> Option<Rational>{
+        let fraction = distances[outside].checked_sub(radius)?.checked_div(distances[outside].checked_sub(distances[inside])?)?;
+        times[outside].checked_add(fraction.checked_mul(times[inside].checked_sub(times[outside])?)?)
+    };
+    let mut candidates: Vec<Rational> = times[first ..= last].to_vec();
+    if first > 0 {
+        candidates.push(crossing(first - 1,first)?);
+    }
+    if last + 1 < times.len() {
+        candidates.push(crossing(last + 1,last)?);
+    }
+    let step = point2[0].checked_sub(point1[0])?;
+    let mut low: Option<Rational> = None;
+    let mut high: Option<Rational> = None;
+    for time in candidates {
+        let slack = radius.checked_sub(distance(time)?)?;
+        let center = point1[0].checked_add(time.checked_mul(step)?)?;
+        let (left, right) = (center.checked_sub(slack)?, center.checked_add(slack)?);
+        low = Some(low.map_or(left,|low| cmp::min(low,left)));
+        high = Some(high.map_or(right,|high| cmp::max(high,right)));
+    }
+    Some((low?.checked_ceil()?, high?.checked_floor()?))
+}
+
+//check_thick_segment
+//Purpose:
+//    Returns GeometryError::RangeTooLarge if the cells within radius of the segment from point1 to point2 might not fit in a Vec of T.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    Slicing across the axis along which the segment is longest (by m) gives at most m + 2*radius + 2 slices,
+//    and in any of the metrics each slice of the thick segment is within 2*radius of a point in every coordinate,
+//    so has at most (4*radius + 2)^(dimension - 1) cells.
+fn check_thick_segment<T>(point1: &[Rational], point2: &[Rational], radius: Rational) -> Result<(), GeometryError>{
+    let radius = radius.checked_ceil().ok_or(GeometryError::RangeTooLarge)? as usize;
+    let mut longest: usize = 0;
+    for coordinate in 0 .. point1.len() {
+        let step = point2[coordinate].checked_sub(point1[coordinate]).and_then(|step| step.abs().checked_ceil()).ok_or(GeometryError::RangeTooLarge)?;
+        longest = cmp::max(longest,step as usize);
+    }
+    let slices = longest.checked_add(radius).and_then(|slices| slices.checked_add(radius)?.checked_add(2));
+    let slice = radius.checked_mul(4).and_then(|slice| slice.checked_add(2)?.checked_pow(point1.len() as u32 - 1));
+    check_cell_count::<T>(slices.and_then(|slices| slices.checked_mul(slice?)))
+}
+
+//Finds the interval of x in the row of a thick segment with the given endpoints and rest of the coordinates, see l_2_row, l_1_row and l_infinity_row.
+type RowFinder<'a> = dyn Fn(&[Rational], &[Rational], &[isize]) -> Option<(isize, isize)> + 'a;
+
+//Receives every non-empty row (rest, low, high) of a thick segment.
+type RowSink<'a> = dyn FnMut(&[isize], isize, isize) -> Result<(), GeometryError> + 'a;
+
+//thick_segment_rows
+//Purpose:
+//    Calls emit_row with every non-empty row (rest, low, high) of the segment from point1 to point2 thickened by radius,
+//    where row finds the interval of x for the given rest of the coordinates.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    Projecting the thick segment onto the rest of the coordinates gives the thick projected segment (in the same metric,
+//    as balls in each of the metrics project to balls), so its cells are the rows to visit.
+//    In one dimension the thick segment is just an interval.
+fn thick_segment_rows(point1: &[Rational], point2: &[Rational], radius: Rational, row: &RowFinder, emit_row: &mut RowSink) -> Result<(), GeometryError>{
+    if point1.len() == 1 {
+        let (low, high) = cmp::min(point1[0],point2[0]).checked_sub(radius).and_then(|low| low.checked_ceil())
+            .zip(cmp::max(point1[0],point2[0]).checked_add(radius).and_then(|high| high.checked_floor())).ok_or(GeometryError::Overflow)?;
+        return emit_row(&[],low,high);
+    }
+    let mut rest: Vec<isize> = Vec::with_capacity(point1.len() - 1);
+    thick_segment_rows(&point1[1 ..],&point2[1 ..],radius,row,&mut |projected_rest, low, high| {
+        for coordinate in low ..= high {
+            rest.clear();
+            rest.push(coordinate);
+            rest.extend_from_slice(projected_rest);
+            let (row_low, row_high) = row(point1,point2,&rest).ok_or(GeometryError::Overflow)?;
+            if row_low <= row_high {
+                emit_row(&rest,row_low,row_high)?;
+            }
+        }
+        Ok(())
+    })
+}
+
+//thick_segment
+//Purpose:
+//    Calls emit with the coordinates of every integer cell within radius of the segment from point1 to point2 in the given metric,
+//    or returns a GeometryError if the radius is negative, the cells do not fit in a Vec of T or a computation overflows.
+//Pre-conditions:
+//    The points have the same number of coordinates.
+fn thick_segment<T>(point1: &[Rational], point2: &[Rational], radius: Rational, metric: Metric, emit: &mut dyn FnMut(&[isize])) -> Result<(), GeometryError>{
+    if radius.is_neg() {
+        return Err(GeometryError::NegativeRadius);
+    }
+    check_thick_segment::<T>(point1,point2,radius)?;
+    let radius_squared = radius.checked_mul(radius).ok_or(GeometryError::Overflow)?;
+    let mut coordinates: Vec<isize> = Vec::with_capacity(point1.len());
+    let row = |point1: &[Rational], point2: &[Rational], rest: &[isize]| match metric {
+        Metric::L1 => l_1_row(point1,point2,radius,rest),
+        Metric::L2 => l_2_row(point1,point2,radius_squared,rest),
+        Metric::LInfinity => l_infinity_row(point1,point2,radius,rest),
+    };
+    thick_segment_rows(point1,point2,radius,&row,&mut |rest, low, high| {
+        for x_cord in low ..= high {
+            coordinates.clear();
+            coordinates.push(x_cord);
+            coordinates.extend_from_slice(rest);
+            emit(&coordinates);
+        }
+        Ok(())
+    })
+}
+
+fn try_thick_segment_2d(point1: RationalPoint2D, point2: RationalPoint2D, radius: Rational, metric: Metric) -> Result<Vec<Point2D>, GeometryError>{
+    let mut squares: Vec<Point2D> = Vec::new();
+    thick_segment::<Point2D>(&[point1.x,point1.y],&[point2.x,point2.y],radius,metric,&mut |cords| squares.push(Point2D{ x: cords[0], y: cords[1],}))?;
+    Ok(squares)
+}
+
+fn try_thick_segment_3d(point1: RationalPoint3D, point2: RationalPoint3D, radius: Rational, metric: Metric) -> Result<Vec<Point3D>, GeometryError>{
+    let mut cubes: Vec<Point3D> = Vec::new();
+    thick_segment::<Point3D>(&[point1.x,point1.y,point1.z],&[point2.x,point2.y,point2.z],radius,metric,&mut |cords| cubes.push(Point3D{ x: cords[0], y: cords[1], z: cords[2],}))?;
+    Ok(cubes)
+}
+
+//The functions without the try_ prefix return no cells for a negative radius and panic if a computation overflows.
+fn thick_segment_or_panic<T>(cells: Result<Vec<T>, GeometryError>) -> Vec<T>{
+    match cells {
+        Ok(cells) => cells,
+        Err(GeometryError::NegativeRadius) => Vec::new(),
+        Err(_) => panic!("Overflow while finding the cells on a line segment (Rationals)!"),
+    }
+}
+
+//integer_cells_on_thick_line_segment_2d
+//Purpose:
+//    Returns all integer points (x,y) that are within the given rational radius (using the L2 metric) of the line segment
+//    given by the two 2D points, that is the integer points inside the capsule around the segment.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    A negative radius has no cells.
+//    The cells are ordered by row, not along the segment.
+pub fn integer_cells_on_thick_line_segment_2d(point1: Point2D, point2: Point2D, radius: Rational) -> Vec<Point2D>{
+    thick_segment_or_panic(try_thick_segment_2d(point1.into(),point2.into(),radius,Metric::L2))
+}
+
+//try_integer_cells_on_thick_line_segment_2d
+//Purpose:
+//    Returns the same cells as integer_cells_on_thick_line_segment_2d, or a GeometryError if the radius is negative,
+//    the cells do not fit in an isize or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_thick_line_segment_2d(point1: Point2D, point2: Point2D, radius: Rational) -> Result<Vec<Point2D>, GeometryError>{
+    try_thick_segment_2d(point1.into(),point2.into(),radius,Metric::L2)
+}
+
+//integer_cells_on_thick_line_segment_2d_rational
+//Purpose:
+//    Returns all integer points (x,y) that are within the given rational radius (using the L2 metric) of the line segment
+//    given by the two rational 2D points.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    A negative radius has no cells.
+pub fn integer_cells_on_thick_line_segment_2d_rational(point1: RationalPoint2D, point2: RationalPoint2D, radius: Rational) -> Vec<Point2D>{
+    thick_segment_or_panic(try_thick_segment_2d(point1,point2,radius,Metric::L2))
+}
+
+//try_integer_cells_on_thick_line_segment_2d_rational
+//Purpose:
+//    Returns the same cells as integer_cells_on_thick_line_segment_2d_rational, or a GeometryError if the radius is negative,
+//    the cells do not fit in an isize or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_thick_line_segment_2d_rational(point1: RationalPoint2D, point2: RationalPoint2D, radius: Rational) -> Result<Vec<Point2D>, GeometryError>{
+    try_thick_segment_2d(point1,point2,radius,Metric::L2)
+}
+
+//integer_cells_on_thick_line_segment_3d
+//Purpose:
+//    Returns all integer points (x,y,z) that are within the given rational radius (using the L2 metric) of the line segment
+//    given by the two 3D points, that is the integer points inside the cylinder with hemispherical caps around the segment.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    A negative radius has no cells.
+//    The cells are ordered by row, not along the segment.
+pub fn integer_cells_on_thick_line_segment_3d(point1: Point3D, point2: Point3D, radius: Rational) -> Vec<Point3D>{
+    thick_segment_or_panic(try_thick_segment_3d(point1.into(),point2.into(),radius,Metric::L2))
+}
+
+//try_integer_cells_on_thick_line_segment_3d
+//Purpose:
+//    Returns the same cells as integer_cells_on_thick_line_segment_3d, or a GeometryError if the radius is negative,
+//    the cells do not fit in an isize or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_thick_line_segment_3d(point1: Point3D, point2: Point3D, radius: Rational) -> Result<Vec<Point3D>, GeometryError>{
+    try_thick_segment_3d(point1.into(),point2.into(),radius,Metric::L2)
+}
+
+//integer_cells_on_thick_line_segment_3d_rational
+//Purpose:
+//    Returns all integer points (x,y,z) that are within the given rational radius (using the L2 metric) of the line segment
+//    given by the two rational 3D points.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    A negative radius has no cells.
+pub fn integer_cells_on_thick_line_segment_3d_rational(point1: RationalPoint3D, point2: RationalPoint3D, radius: Rational) -> Vec<Point3D>{
+    thick_segment_or_panic(try_thick_segment_3d(point1,point2,radius,Metric::L2))
+}
+
+//try_integer_cells_on_thick_line_segment_3d_rational
+//Purpose:
+//    Returns the same cells as integer_cells_on_thick_line_segment_3d_rational, or a GeometryError if the radius is negative,
+//    the cells do not fit in an isize or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_thick_line_segment_3d_rational(point1: RationalPoint3D, point2: RationalPoint3D, radius: Rational) -> Result<Vec<Point3D>, GeometryError>{
+    try_thick_segment_3d(point1,point2,radius,Metric::L2)
+}
+
+//integer_cells_on_thick_line_segment_2d_metric
+//Purpose:
+//    Returns all integer points (x,y) that are within the given rational radius (using the given metric) of the line segment
+//    given by the two 2D points, each exactly once.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    A negative radius has no cells.
+//    Sweeping a square (Metric::LInfinity) or a dimond (Metric::L1) brush of any size along the segment gives these cells.
+//    For radii up to .5 Metric::LInfinity gives the cells of integer_cells_on_line_segment_2d_rational_scale,
+//    plus the cells exactly radius away from the segment, which that function leaves out for a positive radius.
+pub fn integer_cells_on_thick_line_segment_2d_metric(point1: Point2D, point2: Point2D, radius: Rational, metric: Metric) -> Vec<Point2D>{
+    thick_segment_or_panic(try_thick_segment_2d(point1.into(),point2.into(),radius,metric))
+}
+
+//try_integer_cells_on_thick_line_segment_2d_metric
+//Purpose:
+//    Returns the same cells as integer_cells_on_thick_line_segment_2d_metric, or a GeometryError if the radius is negative,
+//    the cells do not fit in an isize or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_thick_line_segment_2d_metric(point1: Point2D, point2: Point2D, radius: Rational, metric: Metric) -> Result<Vec<Point2D>, GeometryError>{
+    try_thick_segment_2d(point1.into(),point2.into(),radius,metric)
+}
+
+//integer_cells_on_thick_line_segment_2d_rational_metric
+//Purpose:
+//    Returns all integer points (x,y) that are within the given rational radius (using the given metric) of the line segment
+//    given by the two rational 2D points, each exactly once.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    A negative radius has no cells.
+pub fn integer_cells_on_thick_line_segment_2d_rational_metric(point1: RationalPoint2D, point2: RationalPoint2D, radius: Rational, metric: Metric) -> Vec<Point2D>{
+    thick_segment_or_panic(try_thick_segment_2d(point1,point2,radius,metric))
+}
+
+//try_integer_cells_on_thick_line_segment_2d_rational_metric
+//Purpose:
+//    Returns the same cells as integer_cells_on_thick_line_segment_2d_rational_metric, or a GeometryError if the radius is negative,
+//    the cells do not fit in an isize or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_thick_line_segment_2d_rational_metric(point1: RationalPoint2D, point2: RationalPoint2D, radius: Rational, metric: Metric) -> Result<Vec<Point2D>, GeometryError>{
+    try_thick_segment_2d(point1,point2,radius,metric)
+}
+
+//integer_cells_on_thick_line_segment_3d_metric
+//Purpose:
+//    Returns all integer points (x,y,z) that are within the given rational radius (using the given metric) of the line segment
+//    given by the two 3D points, each exactly once.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    A negative radius has no cells.
+//    Sweeping a cube (Metric::LInfinity) or an octahedron (Metric::L1) brush of any size along the segment gives these cells.
+pub fn integer_cells_on_thick_line_segment_3d_metric(point1: Point3D, point2: Point3D, radius: Rational, metric: Metric) -> Vec<Point3D>{
+    thick_segment_or_panic(try_thick_segment_3d(point1.into(),point2.into(),radius,metric))
+}
+
+//try_integer_cells_on_thick_line_segment_3d_metric
+//Purpose:
+//    Returns the same cells as integer_cells_on_thick_line_segment_3d_metric, or a GeometryError if the radius is negative,
+//    the cells do not fit in an isize or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_thick_line_segment_3d_metric(point1: Point3D, point2: Point3D, radius: Rational, metric: Metric) -> Result<Vec<Point3D>, GeometryError>{
+    try_thick_segment_3d(point1.into(),point2.into(),radius,metric)
+}
+
+//integer_cells_on_thick_line_segment_3d_rational_metric
+//Purpose:
+//    Returns all integer points (x,y,z) that are within the given rational radius (using the given metric) of the line segment
+//    given by the two rational 3D points, each exactly once.
+//Pre-conditions:
+//    radius is non-negative.
+//Notes:
+//    A negative radius has no cells.
+pub fn integer_cells_on_thick_line_segment_3d_rational_metric(point1: RationalPoint3D, point2: RationalPoint3D, radius: Rational, metric: Metric) -> Vec<Point3D>{
+    thick_segment_or_panic(try_thick_segment_3d(point1,point2,radius,metric))
+}
+
+//try_integer_cells_on_thick_line_segment_3d_rational_metric
+//Purpose:
+//    Returns the same cells as integer_cells_on_thick_line_segment_3d_rational_metric, or a GeometryError if the radius is negative,
+//    the cells do not fit in an isize or a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn try_integer_cells_on_thick_line_segment_3d_rational_metric(point1: RationalPoint3D, point2: RationalPoint3D, radius: Rational, metric: Metric) -> Result<Vec<Point3D>, GeometryError>{
+    try_thick_segment_3d(point1,point2,radius,metric)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ball::{integer_points_inside_circle, integer_points_inside_sphere};
+    use crate::line::{integer_cells_on_line_segment_2d_rational_scale, integer_points_on_line_segment_2d, integer_points_on_line_segment_3d};
+    use crate::{rational, sort};
+
+    //Returns the squared L2 distance from the point to the segment, computed directly.
+    fn distance_squared(point: &[Rational], point1: &[Rational], point2: &[Rational]) -> Rational{
+        let zero = rational(0,1);
+        let mut along = zero;
+        let mut length = zero;
+        for coordinate in 0 .. point.len() {
+            along = along + (point[coordinate] - point1[coordinate])*(point2[coordinate] - point1[coordinate]);
+            length = length + (point2[coordinate] - point1[coordinate])*(point2[coordinate] - point1[coordinate]);
+        }
+        let time = if length == zero { zero } else { cmp::max(zero,cmp::min(rational(1,1),along/length)) };
+        let mut distance = zero;
+        for coordinate in 0 .. point.len() {
+            let offset = point[coordinate] - point1[coordinate] - time*(point2[coordinate] - point1[coordinate]);
+            distance = distance + offset*offset;
+        }
+        distance
+    }
+
+    #[test]
+    fn thick_segments_2d(){
+        let segments = [
+            (RationalPoint2D{ x: rational(0,1), y: rational(0,1),},RationalPoint2D{ x: rational(7,1), y: rational(3,1),}),
+            (RationalPoint2D{ x: rational(-2,1), y: rational(5,1),},RationalPoint2D{ x: rational(4,1), y: rational(5,1),}),
+            (RationalPoint2D{ x: rational(1,1), y: rational(-4,1),},RationalPoint2D{ x: rational(1,1), y: rational(2,1),}),
+            (RationalPoint2D{ x: rational(1,2), y: rational(-1,3),},RationalPoint2D{ x: rational(-9,4), y: rational(11,5),}),
+            (RationalPoint2D{ x: rational(3,2), y: rational(3,2),},RationalPoint2D{ x: rational(3,2), y: rational(3,2),}),
+        ];
+        let radii = [rational(0,1),rational(1,2),rational(1,1),rational(3,2),rational(7,3),rational(4,1)];
+        for (point1, point2) in segments.iter() {
+            for radius in radii.iter() {
+                let mut expected: Vec<Point2D> = Vec::new();
+                for x in -10 ..= 12 {
+                    for y in -10 ..= 12 {
+                        if distance_squared(&[rational(x,1),rational(y,1)],&[point1.x,point1.y],&[point2.x,point2.y]) <= *radius*(*radius) {
+                            expected.push(Point2D{ x, y,});
+                        }
+                    }
+                }
+                assert_eq!(sort(integer_cells_on_thick_line_segment_2d_rational(*point1,*point2,*radius)),sort(expected));
+            }
+        }
+        let (point1, point2) = (Point2D{ x: -3, y: 1,},Point2D{ x: 6, y: 7,});
+        assert_eq!(sort(integer_cells_on_thick_line_segment_2d(point1,point2,rational(0,1))),sort(integer_points_on_line_segment_2d(point1,point2)));
+        assert_eq!(sort(integer_cells_on_thick_line_segment_2d(point1,point1,rational(5,1))),sort(integer_points_inside_circle(point1,5)));
+        assert_eq!(integer_cells_on_thick_line_segment_2d(point1,point2,rational(-1,2)),Vec::new());
+        assert_eq!(try_integer_cells_on_thick_line_segment_2d(point1,point2,rational(-1,2)),Err(GeometryError::NegativeRadius));
+        assert_eq!(try_integer_cells_on_thick_line_segment_2d(point1,Point2D{ x: 1 << 40, y: 1 << 41,},rational(1 << 20,1)),Err(GeometryError::RangeTooLarge));
+    }
+
+    #[test]
+    fn thick_segments_3d(){
+        let segments = [
+            (RationalPoint3D{ x: rational(0,1), y: rational(0,1), z: rational(0,1),},RationalPoint3D{ x: rational(5,1), y: rational(3,1), z: rational(-2,1),}),
+            (RationalPoint3D{ x: rational(-1,1), y: rational(2,1), z: rational(1,1),},RationalPoint3D{ x: rational(4,1), y: rational(2,1), z: rational(1,1),}),
+            (RationalPoint3D{ x: rational(1,3), y: rational(-1,2), z: rational(2,1),},RationalPoint3D{ x: rational(-2,1), y: rational(7,2), z: rational(-5,3),}),
+        ];
+        let radii = [rational(0,1),rational(1,2),rational(1,1),rational(5,3),rational(3,1)];
+        for (point1, point2) in segments.iter() {
+            for radius in radii.iter() {
+                let mut expected: Vec<Point3D> = Vec::new();
+                for x in -6 ..= 9 {
+                    for y in -6 ..= 9 {
+                        for z in -6 ..= 6 {
+                            if distance_squared(&[rational(x,1),rational(y,1),rational(z,1)],&[point1.x,point1.y,point1.z],&[point2.x,point2.y,point2.z]) <= *radius*(*radius) {
+                                expected.push(Point3D{ x, y, z,});
+                            }
+                        }
+                    }
+                }
+                assert_eq!(sort(integer_cells_on_thick_line_segment_3d_rational(*point1,*point2,*radius)),sort(expected));
+            }
+        }
+        let point = Point3D{ x: 2, y: -1, z: 3,};
+        assert_eq!(sort(integer_cells_on_thick_line_segment_3d(point,point,rational(4,1))),sort(integer_points_inside_sphere(point,4)));
+        assert_eq!(try_integer_cells_on_thick_line_segment_3d(point,point,rational(-1,1)),Err(GeometryError::NegativeRadius));
+        //Only the rows near the segment are visited, so a long diagonal segment is quick.
+        let far = Point3D{ x: 100_000, y: 100_001, z: 99_999,};
+        assert_eq!(integer_cells_on_thick_line_segment_3d(Point3D{ x: 0, y: 0, z: 0,},far,rational(0,1)),vec![Point3D{ x: 0, y: 0, z: 0,},far]);
+    }
+
+    //Returns the L1 or L infinity distance from the point to the segment, computed directly.
+    //The distance is convex and piecewise linear in the time along the segment, so it is smallest at an end
+    //or where two of the terms |point - p(t)| in each coordinate (or their negations) cross.
+    fn polyhedral_distance(point: &[Rational], point1: &[Rational], point2: &[Rational], metric: Metric) -> Rational{
+        let zero = rational(0,1);
+        let one = rational(1,1);
+        let offsets: Vec<Rational> = (0 .. point.len()).map(|coordinate| point[coordinate] - point1[coordinate]).collect();
+        let steps: Vec<Rational> = (0 .. point.len()).map(|coordinate| point2[coordinate] - point1[coordinate]).collect();
+        let mut times = vec![zero,one];
+        for first in 0 .. point.len() {
+            for second in first .. point.len() {
+                for sign in [1isize,-1].iter() {
+                    //offsets[first] - t*steps[first] = sign*(offsets[second] - t*steps[second])
+                    let slope = steps[first] - steps[second]*(*sign);
+                    if slope != zero {
+                        let time = (offsets[first] - offsets[second]*(*sign))/slope;
+                        if zero <= time && time <= one {
+                            times.push(time);
+                        }
+                    }
+                }
+            }
+        }
+        times.into_iter().map(|time| {
+            let terms = (0 .. point.len()).map(|coordinate| (offsets[coordinate] - time*steps[coordinate]).abs());
+            match metric {
+                Metric::L1 => terms.fold(zero,|total, term| total + term),
+                _ => terms.fold(zero,cmp::max),
+            }
+        }).min().unwrap()
+    }
+
+    #[test]
+    fn polyhedral_thick_segments(){
+        let segments = [
+            (RationalPoint3D{ x: rational(0,1), y: rational(0,1), z: rational(0,1),},RationalPoint3D{ x: rational(6,1), y: rational(-3,1), z: rational(2,1),}),
+            (RationalPoint3D{ x: rational(1,2), y: rational(2,1), z: rational(-1,3),},RationalPoint3D{ x: rational(-3,1), y: rational(5,2), z: rational(4,1),}),
+            (RationalPoint3D{ x: rational(2,1), y: rational(-1,1), z: rational(1,1),},RationalPoint3D{ x: rational(2,1), y: rational(4,1), z: rational(1,1),}),
+            (RationalPoint3D{ x: rational(1,3), y: rational(1,3), z: rational(1,3),},RationalPoint3D{ x: rational(1,3), y: rational(1,3), z: rational(1,3),}),
+        ];
+        let radii = [rational(0,1),rational(1,2),rational(1,1),rational(5,3),rational(3,1)];
+        for metric in [Metric::L1,Metric::LInfinity].iter() {
+            for (point1, point2) in segments.iter() {
+                for radius in radii.iter() {
+                    let mut expected_2d: Vec<Point2D> = Vec::new();
+                    let mut expected_3d: Vec<Point3D> = Vec::new();
+                    for x in -7 ..= 10 {
+                        for y in -7 ..= 10 {
+                            if polyhedral_distance(&[rational(x,1),rational(y,1)],&[point1.x,point1.y],&[point2.x,point2.y],*metric) <= *radius {
+                                expected_2d.push(Point2D{ x, y,});
+                            }
+                            for z in -5 ..= 8 {
+                                if polyhedral_distance(&[rational(x,1),rational(y,1),rational(z,1)],&[point1.x,point1.y,point1.z],&[point2.x,point2.y,point2.z],*metric) <= *radius {
+                                    expected_3d.push(Point3D{ x, y, z,});
+                                }
+                            }
+                        }
+                    }
+                    let (flat1, flat2) = (RationalPoint2D{ x: point1.x, y: point1.y,},RationalPoint2D{ x: point2.x, y: point2.y,});
+                    let cells_2d = integer_cells_on_thick_line_segment_2d_rational_metric(flat1,flat2,*radius,*metric);
+                    let cells_3d = integer_cells_on_thick_line_segment_3d_rational_metric(*point1,*point2,*radius,*metric);
+                    //sorting the expected cells, which have no duplicates, also checks that none were returned twice.
+                    assert_eq!(sort(cells_2d),sort(expected_2d));
+                    assert_eq!(sort(cells_3d),sort(expected_3d));
+                }
+            }
+        }
+        let (point1, point2) = (Point2D{ x: -2, y: 3,},Point2D{ x: 9, y: -4,});
+        for radius in [rational(0,1),rational(1,5),rational(1,3),rational(2,5),rational(1,2)].iter() {
+            let scaled = integer_cells_on_line_segment_2d_rational_scale(point1,point2,*radius);
+            let thick = integer_cells_on_thick_line_segment_2d_metric(point1,point2,*radius,Metric::LInfinity);
+            assert!(scaled.iter().all(|cell| thick.contains(cell)));
+            for cell in thick {
+                let distance = polyhedral_distance(&[rational(cell.x,1),rational(cell.y,1)],&[rational(point1.x,1),rational(point1.y,1)],&[rational(point2.x,1),rational(point2.y,1)],Metric::LInfinity);
+                assert!(scaled.contains(&cell) || distance == *radius);
+            }
+        }
+        assert_eq!(sort(integer_cells_on_thick_line_segment_2d_metric(point1,point2,rational(0,1),Metric::L1)),sort(integer_points_on_line_segment_2d(point1,point2)));
+        assert_eq!(sort(integer_cells_on_thick_line_segment_2d_metric(point1,point2,rational(7,4),Metric::L2)),sort(integer_cells_on_thick_line_segment_2d(point1,point2,rational(7,4))));
+        let (point1, point2) = (Point3D{ x: 0, y: 3, z: -6,},Point3D{ x: 8, y: -1, z: 6,});
+        for metric in [Metric::L1,Metric::LInfinity].iter() {
+            assert_eq!(sort(integer_cells_on_thick_line_segment_3d_metric(point1,point2,rational(0,1),*metric)),sort(integer_points_on_line_segment_3d(point1,point2)));
+            assert_eq!(try_integer_cells_on_thick_line_segment_3d_metric(point1,point2,rational(-1,1),*metric),Err(GeometryError::NegativeRadius));
+        }
+        //A brush of radius 1 swept along an axis: the square covers a 7 by 3 rectangle, its end caps as wide as the strip,
+        //while the diamond covers the 5 by 3 strip over the segment plus a single tip cell beyond each end.
+        let strip = integer_cells_on_thick_line_segment_2d_metric(Point2D{ x: 0, y: 0,},Point2D{ x: 4, y: 0,},rational(1,1),Metric::LInfinity);
+        assert_eq!(strip.len(),21);
+        let strip = integer_cells_on_thick_line_segment_2d_metric(Point2D{ x: 0, y: 0,},Point2D{ x: 4, y: 0,},rational(1,1),Metric::L1);
+        assert_eq!(strip.len(),17);
+    }
+}
@@ -0,0 +1,276 @@
+//Purpose: Contains an exact voxel traversal (in the style of Amanatides and Woo) of a line segment in 3D,
+//         which records where the segment enters and leaves every cell it passes through.
+
+use std::cmp;
+
+use crate::point::{Point3D, RationalPoint3D};
+use crate::rational::{OverflowError, Rational, RationalNumber};
+
+//VoxelCrossing
+//Purpose:
+//    A cell passed through by a segment, together with the parameters (0 at point1, 1 at point2) at which
+//    the segment enters and leaves the cell.
+//    entry_face gives the side of the cell the segment entered through, each component is -1 or 1 if the segment
+//    crossed the cell's face on the negative or positive side of that axis and 0 otherwise.
+//    A segment passing exactly through an edge or corner of the grid has two or three non-zero components,
+//    the first cell has an entry_face of (0,0,0).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VoxelCrossing<N = Rational> {
+    pub cell: Point3D,
+    pub entry: N,
+    pub exit: N,
+    pub entry_face: Point3D,
+}
+
+//VoxelTraversal3D
+//Purpose:
+//    Iterator over the cells (x,y,z) from a grid that lie on the line segment given by the two 3D points,
+//    as VoxelCrossings ordered from point1 to point2.
+//    The cells are the same as the ones returned by integer_cells_on_line_segment_3d.
+//Notes:
+//    All parameters are computed exactly, cells that the segment only touches on an edge or corner are skipped.
+//    All arithmetic is checked, if a computation overflows the traversal stops early and overflowed returns true.
+pub struct VoxelTraversal3D<N = Rational> {
+    cell: [isize; 3],
+    step: [isize; 3],
+    next_crossing: [Option<N>; 3], //parameter at which the segment next leaves the current cell along each axis
+    crossing_gap: [N; 3], //difference in the parameter between consecutive crossings along each axis
+    entry: N,
+    entry_face: [isize; 3],
+    done: bool,
+    overflowed: bool,
+}
+
+impl VoxelTraversal3D {
+    pub fn new(point1: Point3D, point2: Point3D) -> VoxelTraversal3D{
+        VoxelTraversal3D::new_rational(RationalPoint3D::from(point1),RationalPoint3D::from(point2))
+    }
+}
+
+impl<N: RationalNumber> VoxelTraversal3D<N> {
+    pub fn new_rational(point1: RationalPoint3D<N>, point2: RationalPoint3D<N>) -> VoxelTraversal3D<N>{
+        let zero = N::from_integer(0);
+        let mut voxels = VoxelTraversal3D{
+            cell: [0; 3],
+            step: [0; 3],
+            next_crossing: [None, None, None],
+            crossing_gap: [zero.clone(), zero.clone(), zero.clone()],
+            entry: zero,
+            entry_face: [0; 3],
+            done: false,
+            overflowed: false,
+        };
+        if voxels.start(point1,point2).is_none() {
+            voxels.overflowed = true;
+            voxels.done = true;
+        }
+        voxels
+    }
+
+    //Returns true if the traversal was stopped because a computation overflowed.
+    pub fn overflowed(&self) -> bool{
+        self.overflowed
+    }
+
+    //Starts a traversal of point1 + t*(point2 - point1) in the cell the segment is in just after t = 0.
+    fn start(&mut self, point1: RationalPoint3D<N>, point2: RationalPoint3D<N>) -> Option<()>{
+        let zero = N::from_integer(0);
+        let one = N::from_integer(1);
+        let half = N::from_integers(1,2);
+        let direction = [point2.x.checked_sub(&point1.x)?,point2.y.checked_sub(&point1.y)?,point2.z.checked_sub(&point1.z)?];
+        let start = [point1.x,point1.y,point1.z];
+        for axis in 0 .. 3 {
+            let shifted = start[axis].checked_add(&half)?;
+            if !shifted.is_integer() || direction[axis] > zero { // a point between cells starts in the cell it is moving into.
+                self.cell[axis] = shifted.checked_floor()?;
+            }else if direction[axis] < zero {
+                self.cell[axis] = start[axis].checked_sub(&half)?.checked_floor()?;
+            }else{ // the segment lies on the boundary between cells, so it never passes through the interior of one.
+                self.done = true;
+            }
+            let cell = N::from_integer(self.cell[axis]);
+            if direction[axis] > zero {
+                self.step[axis] = 1;
+                self.next_crossing[axis] = Some(cell.checked_add(&half)?.checked_sub(&start[axis])?.checked_div(&direction[axis])?);
+                self.crossing_gap[axis] = one.checked_div(&direction[axis])?;
+            }else if direction[axis] < zero {
+                self.step[axis] = -1;
+                self.next_crossing[axis] = Some(cell.checked_sub(&half)?.checked_sub(&start[axis])?.checked_div(&direction[axis])?);
+                self.crossing_gap[axis] = (-one.clone()).checked_div(&direction[axis])?;
+            }
+        }
+        Some(())
+    }
+
+    //Moves to the next cell, the segment leaves the current cell at exit.
+    fn step_cell(&mut self, exit: N) -> Option<()>{
+        //step every axis whose boundary is crossed at exit, more than one means the segment passes through an edge or corner.
+        self.entry_face = [0; 3];
+        for axis in 0 .. 3 {
+            if self.next_crossing[axis].as_ref() == Some(&exit) {
+                self.next_crossing[axis] = Some(exit.checked_add(&self.crossing_gap[axis])?);
+                self.cell[axis] += self.step[axis];
+                self.entry_face[axis] = -self.step[axis];
+            }
+        }
+        self.entry = exit;
+        Some(())
+    }
+}
+
+impl<N: RationalNumber> Iterator for VoxelTraversal3D<N> {
+    type Item = VoxelCrossing<N>;
+
+    fn next(&mut self) -> Option<VoxelCrossing<N>> {
+        if self.done {
+            return None;
+        }
+        let one = N::from_integer(1);
+        let mut exit = &one;
+        for crossing in self.next_crossing.iter().flatten() {
+            exit = cmp::min(exit,crossing);
+        }
+        let exit = exit.clone();
+        let voxel = VoxelCrossing{
+            cell: Point3D{ x: self.cell[0], y: self.cell[1], z: self.cell[2],},
+            entry: self.entry.clone(),
+            exit: exit.clone(),
+            entry_face: Point3D{ x: self.entry_face[0], y: self.entry_face[1], z: self.entry_face[2],},
+        };
+        if exit >= one { // the segment ends in (or on the boundary of) this cell.
+            self.done = true;
+        }else if self.step_cell(exit).is_none() {
+            self.overflowed = true;
+            self.done = true;
+        }
+        Some(voxel)
+    }
+}
+
+//voxel_crossings_on_line_segment_3d
+//Purpose:
+//    Returns the cells (x,y,z) from a grid that lie on the line segment given by the two 3D points, ordered from point1 to point2,
+//    with the exact parameters at which the segment enters and leaves each cell and the face it enters through.
+//Pre-conditions:
+//    None.
+pub fn voxel_crossings_on_line_segment_3d(point1: Point3D, point2: Point3D) -> Vec<VoxelCrossing>{
+    match checked_voxel_crossings_on_line_segment_3d(point1,point2) {
+        Ok(cells) => cells,
+        Err(_) => panic!("Overflow while finding the cells on a line segment (Rationals)!"),
+    }
+}
+
+//checked_voxel_crossings_on_line_segment_3d
+//Purpose:
+//    Returns the same crossings as voxel_crossings_on_line_segment_3d, or an OverflowError if a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn checked_voxel_crossings_on_line_segment_3d(point1: Point3D, point2: Point3D) -> Result<Vec<VoxelCrossing>, OverflowError>{
+    let mut walk = VoxelTraversal3D::new(point1,point2);
+    let cells: Vec<VoxelCrossing> = walk.by_ref().collect();
+    if walk.overflowed() {
+        Err(OverflowError)
+    }else{
+        Ok(cells)
+    }
+}
+
+//voxel_crossings_on_line_segment_rational_3d
+//Purpose:
+//    Returns the cells (x,y,z) from a grid that lie on the line segment given by the two rational 3D points, ordered from point1 to point2,
+//    with the exact parameters at which the segment enters and leaves each cell and the face it enters through.
+//Pre-conditions:
+//    None.
+//Notes:
+//    The first cell has an entry of 0 even if point1 lies on its boundary.
+pub fn voxel_crossings_on_line_segment_rational_3d<N: RationalNumber>(point1: RationalPoint3D<N>, point2: RationalPoint3D<N>) -> Vec<VoxelCrossing<N>>{
+    match checked_voxel_crossings_on_line_segment_rational_3d(point1,point2) {
+        Ok(cells) => cells,
+        Err(_) => panic!("Overflow while finding the cells on a line segment (Rationals)!"),
+    }
+}
+
+//checked_voxel_crossings_on_line_segment_rational_3d
+//Purpose:
+//    Returns the same crossings as voxel_crossings_on_line_segment_rational_3d, or an OverflowError if a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn checked_voxel_crossings_on_line_segment_rational_3d<N: RationalNumber>(point1: RationalPoint3D<N>, point2: RationalPoint3D<N>) -> Result<Vec<VoxelCrossing<N>>, OverflowError>{
+    let mut walk = VoxelTraversal3D::new_rational(point1,point2);
+    let cells: Vec<VoxelCrossing<N>> = walk.by_ref().collect();
+    if walk.overflowed() {
+        Err(OverflowError)
+    }else{
+        Ok(cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line::integer_cells_on_line_segment_3d;
+    use crate::sort;
+
+    #[test]
+    fn voxel_crossings_match_cells(){
+        for x in -3 .. 4 {
+            for y in -3 .. 4 {
+                for z in -3 .. 4 {
+                    let point1 = Point3D{ x: 1, y: -1, z: 2,};
+                    let point2 = Point3D{ x, y, z,};
+                    let crossings = voxel_crossings_on_line_segment_3d(point1,point2);
+                    assert_eq!(crossings[0].entry,Rational::new_rational_from_integer(0));
+                    assert_eq!(crossings[crossings.len()-1].exit,Rational::new_rational_from_integer(1));
+                    for pair in crossings.windows(2) {
+                        assert_eq!(pair[0].exit,pair[1].entry);
+                        assert!(pair[1].entry < pair[1].exit);
+                        assert_eq!(pair[1].cell.x - pair[0].cell.x,-pair[1].entry_face.x);
+                        assert_eq!(pair[1].cell.y - pair[0].cell.y,-pair[1].entry_face.y);
+                        assert_eq!(pair[1].cell.z - pair[0].cell.z,-pair[1].entry_face.z);
+                    }
+                    let cells: Vec<Point3D> = crossings.iter().map(|crossing| crossing.cell).collect();
+                    assert_eq!(sort(cells),sort(integer_cells_on_line_segment_3d(point1,point2)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn voxel_crossings_parameters(){
+        let crossings = voxel_crossings_on_line_segment_3d(Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 2, y: 1, z: 0,});
+        assert_eq!(crossings,vec![
+            VoxelCrossing{ cell: Point3D{ x: 0, y: 0, z: 0,}, entry: Rational::new_rational(0,1), exit: Rational::new_rational(1,4), entry_face: Point3D{ x: 0, y: 0, z: 0,}},
+            VoxelCrossing{ cell: Point3D{ x: 1, y: 0, z: 0,}, entry: Rational::new_rational(1,4), exit: Rational::new_rational(1,2), entry_face: Point3D{ x: -1, y: 0, z: 0,}},
+            VoxelCrossing{ cell: Point3D{ x: 1, y: 1, z: 0,}, entry: Rational::new_rational(1,2), exit: Rational::new_rational(3,4), entry_face: Point3D{ x: 0, y: -1, z: 0,}},
+            VoxelCrossing{ cell: Point3D{ x: 2, y: 1, z: 0,}, entry: Rational::new_rational(3,4), exit: Rational::new_rational(1,1), entry_face: Point3D{ x: -1, y: 0, z: 0,}},
+        ]);
+        let crossings = voxel_crossings_on_line_segment_3d(Point3D{ x: 1, y: 1, z: 1,},Point3D{ x: 0, y: 0, z: 0,});
+        assert_eq!(crossings[1].entry_face,Point3D{ x: 1, y: 1, z: 1,});
+        assert_eq!(crossings[1].entry,Rational::new_rational(1,2));
+        assert_eq!(crossings.len(),2);
+    }
+
+    #[test]
+    fn voxel_crossings_rational_endpoints(){
+        let zero = Rational::new_rational(0,1);
+        let half = Rational::new_rational(1,2);
+        let one = Rational::new_rational(1,1);
+        let crossings = voxel_crossings_on_line_segment_rational_3d(RationalPoint3D{ x: half, y: zero, z: zero,},RationalPoint3D{ x: -half, y: one, z: zero,});
+        assert_eq!(crossings,vec![
+            VoxelCrossing{ cell: Point3D{ x: 0, y: 0, z: 0,}, entry: zero, exit: half, entry_face: Point3D{ x: 0, y: 0, z: 0,}},
+            VoxelCrossing{ cell: Point3D{ x: 0, y: 1, z: 0,}, entry: half, exit: one, entry_face: Point3D{ x: 0, y: -1, z: 0,}},
+        ]);
+        assert_eq!(voxel_crossings_on_line_segment_rational_3d(RationalPoint3D{ x: half, y: zero, z: zero,},RationalPoint3D{ x: half, y: one, z: zero,}),
+                   vec![]);
+    }
+
+    #[test]
+    fn voxel_crossings_overflow(){
+        let zero = Rational::new_rational(0,1);
+        let point1 = RationalPoint3D{ x: Rational::new_rational(1,(1 << 33) + 1), y: zero, z: zero,};
+        let point2 = RationalPoint3D{ x: Rational::new_rational(1,(1 << 33) - 1), y: zero, z: zero,};
+        assert_eq!(checked_voxel_crossings_on_line_segment_rational_3d(point1,point2),Err(OverflowError));
+        assert_eq!(checked_voxel_crossings_on_line_segment_3d(Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 2, y: 1, z: 0,}),
+                   Ok(voxel_crossings_on_line_segment_3d(Point3D{ x: 0, y: 0, z: 0,},Point3D{ x: 2, y: 1, z: 0,})));
+    }
+}
@@ -0,0 +1,318 @@
+//Purpose: Contains a triangular grid and functions for finding the triangles on line segments and inside balls.
+//Notes:
+//    Points are given in lattice coordinates (u,v), the point (u,v) being u*(1,0) + v*(1/2,sqrt(3)/2) in the plane.
+//    The grid is cut by the lines u = k, v = k and u + v = k for every integer k, which splits each unit parallelogram
+//    [x,x+1]x[y,y+1] into an upward pointing triangle (u + v < x + y + 1) and a downward pointing triangle (u + v > x + y + 1).
+
+use std::cmp;
+
+use crate::error::{check_cell_count, GeometryError};
+use crate::point::RationalPoint2D;
+use crate::rational::{OverflowError, Rational};
+
+//Triangle
+//Purpose:
+//    A triangle of the grid, the upward or downward pointing half of the parallelogram with lower corner (x,y).
+#[derive(Debug, Copy, Clone,PartialEq,Eq,PartialOrd,Ord)]
+pub struct Triangle {
+    pub x: isize,
+    pub y: isize,
+    pub up: bool,
+}
+
+impl Triangle {
+    //Returns the centroid of the triangle in lattice coordinates.
+    pub fn centroid(&self) -> RationalPoint2D{
+        let offset = if self.up { Rational::new_rational(1,3) } else { Rational::new_rational(2,3) };
+        RationalPoint2D{ x: offset + self.x, y: offset + self.y,}
+    }
+
+    //Returns the index of the line u + v = k just below the triangle,
+    //so each step to a neighbouring triangle changes exactly one of x, y and this by one.
+    fn z(&self) -> isize{
+        if self.up { self.x + self.y } else { self.x + self.y + 1 }
+    }
+}
+
+//triangle_distance
+//Purpose:
+//    Returns the number of steps between triangles sharing an edge needed to get from triangle1 to triangle2.
+//Pre-conditions:
+//    None.
+pub fn triangle_distance(triangle1: Triangle, triangle2: Triangle) -> isize{
+    (triangle2.x - triangle1.x).abs() + (triangle2.y - triangle1.y).abs() + (triangle2.z() - triangle1.z()).abs()
+}
+
+//triangle_neighbours
+//Purpose:
+//    Returns the three triangles sharing an edge with the given triangle.
+//Pre-conditions:
+//    None.
+pub fn triangle_neighbours(triangle: Triangle) -> Vec<Triangle>{
+    let Triangle{ x, y, up } = triangle;
+    if up {
+        vec![Triangle{ x, y, up: false,}, Triangle{ x: x - 1, y, up: false,}, Triangle{ x, y: y - 1, up: false,}]
+    }else{
+        vec![Triangle{ x, y, up: true,}, Triangle{ x: x + 1, y, up: true,}, Triangle{ x, y: y + 1, up: true,}]
+    }
+}
+
+//triangle_vertex_neighbours
+//Purpose:
+//    Returns the twelve triangles sharing an edge or a corner with the given triangle.
+//Pre-conditions:
+//    None.
+pub fn triangle_vertex_neighbours(triangle: Triangle) -> Vec<Triangle>{
+    //every such triangle is in one of the nine parallelograms around the triangle's own.
+    let mut triangles: Vec<Triangle> = Vec::new();
+    for x_cord in triangle.x - 1 .. triangle.x + 2 {
+        for y_cord in triangle.y - 1 .. triangle.y + 2 {
+            for up in [true, false].iter() {
+                let other = Triangle{ x: x_cord, y: y_cord, up: *up,};
+                let corners_shared = corners(triangle).iter().filter(|corner| corners(other).contains(corner)).count();
+                if other != triangle && corners_shared > 0 {
+                    triangles.push(other);
+                }
+            }
+        }
+    }
+    triangles
+}
+
+//corners
+//Purpose:
+//    Returns the three corners of the triangle in lattice coordinates.
+//Pre-conditions:
+//    None.
+fn corners(triangle: Triangle) -> [(isize, isize); 3]{
+    let Triangle{ x, y, up } = triangle;
+    if up {
+        [(x, y), (x + 1, y), (x, y + 1)]
+    }else{
+        [(x + 1, y + 1), (x + 1, y), (x, y + 1)]
+    }
+}
+
+//triangle_cells_inside_ball
+//Purpose:
+//    Returns all triangles within the given number of steps (between triangles sharing an edge) of the center.
+//    Mirrors integer_points_inside_dimond_2d for triangles.
+//Pre-conditions:
+//    radius is non-negative.
+pub fn triangle_cells_inside_ball(center: Triangle, radius: isize) -> Vec<Triangle>{
+    let mut triangles: Vec<Triangle> = Vec::new();
+    for x_cord in -radius .. radius + 1 {
+        for y_cord in -radius + x_cord.abs() .. radius - x_cord.abs() + 1 {
+            for up in [true, false].iter() {
+                let triangle = Triangle{ x: x_cord + center.x, y: y_cord + center.y, up: *up,};
+                if triangle_distance(center,triangle) <= radius {
+                    triangles.push(triangle);
+                }
+            }
+        }
+    }
+    triangles
+}
+
+//try_triangle_cells_inside_ball
+//Purpose:
+//    Returns the same triangles as triangle_cells_inside_ball, or a GeometryError if the radius is negative
+//    or the triangles do not fit in an isize.
+//Pre-conditions:
+//    None.
+pub fn try_triangle_cells_inside_ball(center: Triangle, radius: isize) -> Result<Vec<Triangle>, GeometryError>{
+    if radius < 0 {
+        return Err(GeometryError::NegativeRadius);
+    }
+    for coordinate in [center.x, center.y].iter() {
+        if coordinate.checked_add(radius + 1).is_none() || coordinate.checked_sub(radius + 1).is_none() {
+            return Err(GeometryError::RangeTooLarge);
+        }
+    }
+    let side = (radius as usize).checked_mul(2).and_then(|diameter| diameter.checked_add(1));
+    check_cell_count::<Triangle>(side.and_then(|side| side.checked_mul(side)).and_then(|count| count.checked_mul(2)))?;
+    Ok(triangle_cells_inside_ball(center,radius))
+}
+
+//push_crossings
+//Purpose:
+//    Adds to times every parameter t strictly between 0 and 1 at which start + t*delta is an integer.
+//Pre-conditions:
+//    None.
+//Notes:
+//    Returns None if a computation overflows.
+fn push_crossings(start: Rational, delta: Rational, times: &mut Vec<Rational>) -> Option<()>{
+    if delta.numerator() == 0 {
+        return Some(());
+    }
+    let end = start.checked_add(delta)?;
+    let first = cmp::min(start,end).checked_floor()?.checked_add(1)?;
+    let last = cmp::max(start,end).checked_ceil()?.checked_sub(1)?;
+    for line in first .. last + 1 {
+        times.push(Rational::new_rational_from_integer(line).checked_sub(start)?.checked_div(delta)?);
+    }
+    Some(())
+}
+
+//walk_triangles
+//Purpose:
+//    Returns the triangles the segment between the two points passes through the interior of, ordered from point1 to point2,
+//    or None if a computation overflows.
+//Pre-conditions:
+//    None.
+fn walk_triangles(point1: RationalPoint2D, point2: RationalPoint2D) -> Option<Vec<Triangle>>{
+    let du = point2.x.checked_sub(point1.x)?;
+    let dv = point2.y.checked_sub(point1.y)?;
+    let mut times = vec![Rational::new_rational_from_integer(0), Rational::new_rational_from_integer(1)];
+    push_crossings(point1.x,du,&mut times)?;
+    push_crossings(point1.y,dv,&mut times)?;
+    push_crossings(point1.x.checked_add(point1.y)?,du.checked_add(dv)?,&mut times)?;
+    times.sort();
+    times.dedup();
+    //no line is crossed between consecutive times, so the triangle at the middle of each piece is the one passed through.
+    let mut triangles: Vec<Triangle> = Vec::new();
+    for pair in times.windows(2) {
+        let middle = pair[0].checked_add(pair[1])?.checked_div(Rational::new_rational(2,1))?;
+        let u = point1.x.checked_add(du.checked_mul(middle)?)?;
+        let v = point1.y.checked_add(dv.checked_mul(middle)?)?;
+        let w = u.checked_add(v)?;
+        if u.denominator() == 1 || v.denominator() == 1 || w.denominator() == 1 { //the piece lies along a line, so no interior is hit.
+            continue;
+        }
+        let x = u.checked_floor()?;
+        let y = v.checked_floor()?;
+        let triangle = Triangle{ x, y, up: w.checked_floor()? == x.checked_add(y)?,};
+        if triangles.last() != Some(&triangle) {
+            triangles.push(triangle);
+        }
+    }
+    Some(triangles)
+}
+
+//triangle_cells_on_line_segment_rational
+//Purpose:
+//    Returns all triangles that the segment between the two points (in lattice coordinates) passes through, ordered from point1 to point2.
+//    A triangle is hit when the segment passes through its interior, so a segment which only touches the boundary of a triangle does not hit it.
+//Pre-conditions:
+//    None.
+pub fn triangle_cells_on_line_segment_rational(point1: RationalPoint2D, point2: RationalPoint2D) -> Vec<Triangle>{
+    match checked_triangle_cells_on_line_segment_rational(point1,point2) {
+        Ok(triangles) => triangles,
+        Err(_) => panic!("Overflow while finding the cells on a line segment (Rationals)!"),
+    }
+}
+
+//checked_triangle_cells_on_line_segment_rational
+//Purpose:
+//    Returns the same triangles as triangle_cells_on_line_segment_rational, or an OverflowError if a computation overflows.
+//Pre-conditions:
+//    None.
+pub fn checked_triangle_cells_on_line_segment_rational(point1: RationalPoint2D, point2: RationalPoint2D) -> Result<Vec<Triangle>, OverflowError>{
+    walk_triangles(point1,point2).ok_or(OverflowError)
+}
+
+//triangle_cells_on_line_segment
+//Purpose:
+//    Returns all triangles that the segment between the centroids of the two triangles passes through, ordered from triangle1 to triangle2.
+//    Mirrors integer_cells_on_line_segment_2d_ordered for triangles.
+//Pre-conditions:
+//    None.
+//Notes:
+//    Triangles the segment only touches at a corner are not included.
+pub fn triangle_cells_on_line_segment(triangle1: Triangle, triangle2: Triangle) -> Vec<Triangle>{
+    triangle_cells_on_line_segment_rational(triangle1.centroid(),triangle2.centroid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort;
+
+    fn up(x: isize, y: isize) -> Triangle{
+        Triangle{ x, y, up: true,}
+    }
+
+    fn down(x: isize, y: isize) -> Triangle{
+        Triangle{ x, y, up: false,}
+    }
+
+    #[test]
+    fn triangle_neighbourhoods(){
+        assert_eq!(sort(triangle_neighbours(up(0,0))),sort(vec![down(0,0),down(-1,0),down(0,-1)]));
+        assert_eq!(sort(triangle_neighbours(down(2,-1))),sort(vec![up(2,-1),up(3,-1),up(2,0)]));
+        for triangle in [up(0,0), down(0,0), up(-3,2)].iter() {
+            assert_eq!(triangle_vertex_neighbours(*triangle).len(),12);
+            for neighbour in triangle_neighbours(*triangle) {
+                assert_eq!(triangle_distance(*triangle,neighbour),1);
+                assert!(triangle_neighbours(neighbour).contains(triangle));
+                assert!(triangle_vertex_neighbours(*triangle).contains(&neighbour));
+            }
+        }
+        //the distance is the length of the shortest path, found here by a breadth first search.
+        let center = down(1,-1);
+        let mut seen = vec![center];
+        let mut frontier = vec![center];
+        for steps in 1 .. 6 {
+            let mut next: Vec<Triangle> = Vec::new();
+            for triangle in frontier {
+                for neighbour in triangle_neighbours(triangle) {
+                    if !seen.contains(&neighbour) {
+                        assert_eq!(triangle_distance(center,neighbour),steps);
+                        seen.push(neighbour);
+                        next.push(neighbour);
+                    }
+                }
+            }
+            frontier = next;
+            assert_eq!(sort(triangle_cells_inside_ball(center,steps)),sort(seen.clone()));
+        }
+        assert_eq!(triangle_cells_inside_ball(center,0),vec![center]);
+        assert_eq!(try_triangle_cells_inside_ball(center,3),Ok(triangle_cells_inside_ball(center,3)));
+        assert_eq!(try_triangle_cells_inside_ball(center,-1),Err(GeometryError::NegativeRadius));
+        assert_eq!(try_triangle_cells_inside_ball(up(isize::MAX - 1,0),1),Err(GeometryError::RangeTooLarge));
+    }
+
+    #[test]
+    fn triangle_lines(){
+        assert_eq!(triangle_cells_on_line_segment(up(0,0),up(0,0)),vec![up(0,0)]);
+        assert_eq!(triangle_cells_on_line_segment(up(0,0),down(0,0)),vec![up(0,0),down(0,0)]);
+        assert_eq!(triangle_cells_on_line_segment(up(0,0),up(2,0)),vec![up(0,0),down(0,0),up(1,0),down(1,0),up(2,0)]);
+        //passes through the corner (1,1) without entering down(0,1) or down(1,0).
+        assert_eq!(triangle_cells_on_line_segment(up(0,0),up(1,1)),vec![up(0,0),down(0,0),up(1,1)]);
+        //a segment along the line v = 0 only touches boundaries.
+        let zero = Rational::new_rational(0,1);
+        assert_eq!(triangle_cells_on_line_segment_rational(RationalPoint2D{ x: zero, y: zero,},RationalPoint2D{ x: Rational::new_rational(3,1), y: zero,}),vec![]);
+        assert_eq!(triangle_cells_on_line_segment_rational(RationalPoint2D{ x: zero, y: Rational::new_rational(1,2),},
+                                                           RationalPoint2D{ x: Rational::new_rational(2,1), y: Rational::new_rational(1,2),}),
+                   vec![up(0,0),down(0,0),up(1,0),down(1,0)]);
+        for x1 in -2 .. 3 {
+            for y1 in -2 .. 3 {
+                for x2 in -2 .. 3 {
+                    for y2 in -2 .. 3 {
+                        for (up1, up2) in [(true,true),(true,false),(false,true),(false,false)].iter() {
+                            let triangle1 = Triangle{ x: x1, y: y1, up: *up1,};
+                            let triangle2 = Triangle{ x: x2, y: y2, up: *up2,};
+                            let triangles = triangle_cells_on_line_segment(triangle1,triangle2);
+                            assert_eq!((triangles[0],triangles[triangles.len() - 1]),(triangle1,triangle2));
+                            for pair in triangles.windows(2) { //consecutive triangles share an edge or a corner.
+                                assert!(triangle_vertex_neighbours(pair[0]).contains(&pair[1]));
+                            }
+                            let mut reversed = triangle_cells_on_line_segment(triangle2,triangle1);
+                            reversed.reverse();
+                            assert_eq!(triangles,reversed);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn overflowing_triangle_lines(){
+        let point1 = RationalPoint2D{ x: Rational::new_rational(1,(1 << 33) + 1), y: Rational::new_rational(0,1),};
+        let point2 = RationalPoint2D{ x: Rational::new_rational(1,(1 << 33) - 1), y: Rational::new_rational(1,1),};
+        assert_eq!(checked_triangle_cells_on_line_segment_rational(point1,point2),Err(OverflowError));
+        assert_eq!(checked_triangle_cells_on_line_segment_rational(point1,RationalPoint2D{ x: Rational::new_rational(1,2), y: Rational::new_rational(1,4),}),
+                   Ok(vec![up(0,0)]));
+    }
+}